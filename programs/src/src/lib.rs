@@ -3,6 +3,7 @@ use std::mem::size_of;
 use std::fmt;
 use std::usize::MAX;
 use sha256::digest;
+use anchor_lang::system_program::{self as system_instructions, CreateAccount, Allocate, Assign, Transfer};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self,TokenAccount, Mint, Token, MintTo}
@@ -14,12 +15,66 @@ declare_id!("Hd3HLLMfbMJonaCvcQ8GugmTdKsGoHvce1JfAUU2gmiS");
 const SOLANA_ACCOUNT_MAX_SIZE: usize = 1 * 10_usize.pow(4);  // 10 KB
 
 const ENDING_TIMESTAMP_OFFSET: i64 = 2592000;
+const APPEAL_WINDOW: i64 = 604800;                   // The creator has one week since the rejection to appeal it
+const MAXIMUM_DISCUSSION_PERIOD: i64 = 2592000;       // The votation of a proposal can be scheduled up to one month after its publication
 const EXTRA_VOTES_EXPECTED: u32 = 20;
 
 /// CHECK: Modified to 20 to test the use cases --> real value = 2500;
 const MAXIMUM_PARTICIPATION: u32 = 20;
 
 const TOKENS_RECEIVED_AS_REWARD: u8 = 1;
+const MAXIMUM_CO_AUTHORS: usize = 5;
+const MAXIMUM_PROFESSOR_PROPOSAL_COLLABORATORS: usize = 5;
+const MAXIMUM_DEADLINE_EXTENSIONS: usize = 3;
+const MAXIMUM_DRAFT_REVISIONS: usize = 10;
+
+const MAXIMUM_EVALUATION_COMPONENTS: usize = 10;
+const MAXIMUM_EVALUATION_COMPONENT_NAME_LENGTH: usize = 32;
+const MAXIMUM_GRADE: u8 = 100;                       // Measured in tenths --> 10,0
+
+const MAXIMUM_REPUTATION_SUBJECTS: usize = 20;        // Outcomes of further subjects are only added to the totals
+const REPUTATION_POINTS_PER_GRANTED_PROPOSAL: u32 = 10;
+const REPUTATION_POINTS_PER_CO_AUTHORSHIP: u32 = 5;
+const REPUTATION_POINTS_PER_ON_TIME_DELIVERY: u32 = 10;
+const REPUTATION_POINTS_PER_LATE_DELIVERY: u32 = 5;   // Subtracted from the score
+const REPUTATION_SCORE_TO_MITIGATE_PENALTIES: u32 = 50;  // One point of every penalty is forgiven from this score on
+
+const REUSED_ID_CAPACITY: usize = 19;                // 140 extra bytes reserved in every IdHandler --> 19 ids of 8 bytes (+ 4 alineación)
+
+const MAXIMUM_BATCH_SIZE: usize = 10;                // Bounded by the compute budget of a single transaction
+
+const SUBJECT_METADATA_VERSION: u8 = 1;
+const SUBJECT_METADATA_RESERVED_SPACE: usize = 64;   // Room for the fields of future versions of the extension
+const MAXIMUM_ECTS_CREDITS: u16 = 600;               // Measured in tenths of credit --> 60 ECTS
+
+const MAXIMUM_PROPOSAL_SUMMARY_LENGTH: usize = 280;  // The full body of the proposal is stored off-chain (IPFS or Arweave)
+const MAXIMUM_PROPOSAL_ATTACHMENTS: usize = 10;
+const MAXIMUM_ATTACHMENT_FIELD_LENGTH: usize = 64;   // Applies to the name and the mime type of every attachment
+const MAXIMUM_COMMENT_LENGTH: usize = 280;           // Longer comments must be published off-chain and referenced
+
+const MAXIMUM_CONTENT_IDENTIFIER_LENGTH: usize = 100;  // Base32 CIDv1 of longer hash functions still fit
+const CONTENT_REFERENCE_RESERVED_SPACE: usize = MAXIMUM_CONTENT_IDENTIFIER_LENGTH + 64;   // Room for a reference that is not known yet (identifier + sha256)
+
+const DEFAULT_APPROVAL_THRESHOLD: u8 = 66;           // Percentage of supporting votes used by categories without a config (applied as exactly 2/3)
+const MAXIMUM_PROPOSAL_TAGS: usize = 5;
+const MAXIMUM_TAG_LENGTH: usize = 20;
+
+const PROPOSAL_INDEX_CAPACITY: usize = 50;           // Oldest entries are discarded once the index of the subject is full
+const DEFAULT_DUPLICATE_PROPOSAL_COOLDOWN: i64 = 2592000;
+
+// Penalty policy applied while no 'PenaltyPolicy' has been configured by a HighRank
+const DEFAULT_PENALTY_GRACE_PERIOD: i64 = 0;
+const DEFAULT_PENALTY_INTERVAL: i64 = ENDING_TIMESTAMP_OFFSET / 2;
+const DEFAULT_PENALTY_PER_INTERVAL: u8 = 1;
+const DEFAULT_MAXIMUM_PENALTY: u8 = 10;
+const DEFAULT_PENALTY_DECAY_PER_TERM: u8 = 1;
+const DEFAULT_TERM_DURATION: i64 = 15552000;         // ≈ 6 months
+const MAXIMUM_PENALTY_POLICY_PERIOD: i64 = 315360000; // ≈ 10 years --> bound of the grace period, the penalty interval and the term
+
+// Reward of the professors that deliver accepted teaching projects while no 'ProfessorRewardConfig' has been configured
+const DEFAULT_PROFESSOR_REWARD_TOKENS: u8 = 1;
+const DEFAULT_EARLY_DELIVERY_BONUS_TOKENS: u8 = 1;
+const DEFAULT_EARLY_DELIVERY_PERIOD: i64 = 604800;   // Deliveries at least one week before the deadline get the bonus
 
 #[program]
 pub mod teaching_project_handler {
@@ -33,7 +88,7 @@ pub mod teaching_project_handler {
         let high_rank_id_handler = &mut *ctx.accounts.high_rank_id_handler;
         update_internally_initializated_id_generator(high_rank_id_handler);
 
-        high_rank_account.id = general_id_generator(&mut ctx.accounts.high_rank_id_handler);
+        high_rank_account.id = general_id_generator(&mut ctx.accounts.high_rank_id_handler)?;
         high_rank_account.identifier_code_hash = digest(user_type_code);
         high_rank_account.authority = *ctx.accounts.authority.key;
 
@@ -47,7 +102,7 @@ pub mod teaching_project_handler {
         let professor_id_handler = &mut *ctx.accounts.professor_id_handler;
         update_internally_initializated_id_generator(professor_id_handler);
 
-        professor_account.id = general_id_generator(&mut ctx.accounts.professor_id_handler);
+        professor_account.id = general_id_generator(&mut ctx.accounts.professor_id_handler)?;
         professor_account.identifier_code_hash = digest(user_type_code);
         professor_account.authority = *ctx.accounts.authority.key;
 
@@ -72,7 +127,7 @@ pub mod teaching_project_handler {
         let student_id_handler = &mut *ctx.accounts.student_id_handler;
         update_internally_initializated_id_generator(student_id_handler);
 
-        student_account.id = general_id_generator(&mut ctx.accounts.student_id_handler);
+        student_account.id = general_id_generator(&mut ctx.accounts.student_id_handler)?;
         student_account.identifier_code_hash = digest(user_type_code);
         student_account.authority = *ctx.accounts.authority.key;
 
@@ -93,7 +148,7 @@ pub mod teaching_project_handler {
     pub fn create_faculty (ctx: Context<CreateFaculty>, name:String) -> Result<bool> {
 
         let faculty_account = &mut *ctx.accounts.faculty_account;
-        faculty_account.id = general_id_generator(&mut ctx.accounts.faculty_id_handler);
+        faculty_account.id = general_id_generator(&mut ctx.accounts.faculty_id_handler)?;
         faculty_account.name = name;
 
         Ok(true)
@@ -102,7 +157,7 @@ pub mod teaching_project_handler {
     pub fn create_degree (ctx: Context<CreateDegree>, name:String, faculty_id: i32) -> Result<bool> {
 
         let degree_account = &mut *ctx.accounts.degree_account;
-        degree_account.id = general_id_generator(&mut ctx.accounts.degree_id_handler);
+        degree_account.id = general_id_generator(&mut ctx.accounts.degree_id_handler)?;
         degree_account.name = name;
         degree_account.faculty_id = faculty_id;
 
@@ -113,26 +168,19 @@ pub mod teaching_project_handler {
     pub fn create_specialty (ctx: Context<CreateSpecialty>, name:String, degree_id: i32) -> Result<bool> {
 
         let specialty_account = &mut *ctx.accounts.specialty_account;
-        specialty_account.id = general_id_generator(&mut ctx.accounts.specialty_id_handler);
+        specialty_account.id = general_id_generator(&mut ctx.accounts.specialty_id_handler)?;
         specialty_account.name = name;
         specialty_account.degree_id = degree_id;
 
         Ok(true)
     }
 
-    pub fn create_subject(ctx: Context<CreateSubject>, name:String, degree_id: i32, specialty_id: i32, course: SubjectCourse, code: u32, teaching_project_reference: String) -> Result<bool> {
+    pub fn create_subject(ctx: Context<CreateSubject>, name:String, degree_id: i32, specialty_id: i32, course: SubjectCourse, code: u32, teaching_project_reference: ContentReference, prerequisites: Vec<u32>, corequisites: Vec<u32>, sections: Vec<SectionReference>) -> Result<bool> {
 
-        let subject_account = &mut *ctx.accounts.subject_account;
-        subject_account.id = general_id_generator(&mut ctx.accounts.subject_id_handler);
-        subject_account.name = name;
-        subject_account.degree_id = degree_id;
-        subject_account.specialty_id = specialty_id;
-        subject_account.course = course;
-        subject_account.code = code;
-        subject_account.teaching_project_reference = teaching_project_reference;
+        let subject_id = general_id_generator(&mut ctx.accounts.subject_id_handler)?;
+        let subject_data = SubjectData { name, degree_id, specialty_id, course, code, teaching_project_reference, prerequisites, corequisites, sections };
 
-        let code_id_relation_account = &mut *ctx.accounts.code_id_subject_relation_account;
-        code_id_relation_account.add_key_value_subject_pair(subject_account.id, code, false, false);
+        initialize_subject_account(&mut ctx.accounts.subject_account, subject_id, subject_data, &mut ctx.accounts.code_id_subject_relation_account, &mut ctx.accounts.subject_dependency_graph)?;
 
         //Creating the associated ProposalIdHandler and ProfessorProposalIdHandler
         update_internally_initializated_id_generator(&mut *ctx.accounts.proposal_id_handler);
@@ -142,7 +190,258 @@ pub mod teaching_project_handler {
 
     }
 
-    pub fn create_proposal_by_student(ctx: Context<CreateProposalByStudent>, title:String, content:String) -> Result<bool> {
+    // Every degree is created in the PDA passed (in the same order) through 'remaining_accounts'
+    pub fn create_degrees_batch<'info>(ctx: Context<'_, '_, '_, 'info, CreateDegreesBatch<'info>>, degrees: Vec<DegreeData>) -> Result<bool> {
+
+        if degrees.is_empty() || degrees.len() > MAXIMUM_BATCH_SIZE { return Err(error!(ErrorCode::IncorrectBatchSize)) }
+        if ctx.remaining_accounts.len() != degrees.len() { return Err(error!(ErrorCode::IncorrectBatchAccounts)) }
+
+        // The whole batch is validated before creating any account
+        for degree_data in &degrees {
+            validate_degree_data(degree_data, ctx.accounts.faculty_id_handler.smaller_id_available)?;
+        }
+
+        let mut ids: Vec<i32> = vec![];
+
+        for (degree_data, degree_account_info) in degrees.into_iter().zip(ctx.remaining_accounts.iter()) {
+
+            let id = general_id_generator(&mut ctx.accounts.degree_id_handler)?;
+            let space = size_of::<Degree>() + degree_data.name.as_bytes().len() + 4;
+            let degree_account = Degree { id, name: degree_data.name, faculty_id: degree_data.faculty_id };
+
+            create_batch_account(&ctx.accounts.authority, degree_account_info, &ctx.accounts.system_program, space, &[b"degree", id.to_le_bytes().as_ref()], &degree_account)?;
+            ids.push(id);
+        }
+
+        emit! (AcademicCatalogueBatchCreated {entity: AcademicEntity::Degree, ids});
+
+        Ok(true)
+    }
+
+    // Every specialty is created in the PDA passed (in the same order) through 'remaining_accounts'
+    pub fn create_specialties_batch<'info>(ctx: Context<'_, '_, '_, 'info, CreateSpecialtiesBatch<'info>>, specialties: Vec<SpecialtyData>) -> Result<bool> {
+
+        if specialties.is_empty() || specialties.len() > MAXIMUM_BATCH_SIZE { return Err(error!(ErrorCode::IncorrectBatchSize)) }
+        if ctx.remaining_accounts.len() != specialties.len() { return Err(error!(ErrorCode::IncorrectBatchAccounts)) }
+
+        // The whole batch is validated before creating any account
+        for specialty_data in &specialties {
+            validate_specialty_data(specialty_data, ctx.accounts.degree_id_handler.smaller_id_available)?;
+        }
+
+        let mut ids: Vec<i32> = vec![];
+
+        for (specialty_data, specialty_account_info) in specialties.into_iter().zip(ctx.remaining_accounts.iter()) {
+
+            let id = general_id_generator(&mut ctx.accounts.specialty_id_handler)?;
+            let space = size_of::<Specialty>() + specialty_data.name.as_bytes().len() + 4;
+            let specialty_account = Specialty { id, name: specialty_data.name, degree_id: specialty_data.degree_id };
+
+            create_batch_account(&ctx.accounts.authority, specialty_account_info, &ctx.accounts.system_program, space, &[b"specialty", id.to_le_bytes().as_ref()], &specialty_account)?;
+            ids.push(id);
+        }
+
+        emit! (AcademicCatalogueBatchCreated {entity: AcademicEntity::Specialty, ids});
+
+        Ok(true)
+    }
+
+    // Every subject needs three PDAs in 'remaining_accounts' (in the same order): the Subject, its ProposalIdHandler and its ProfessorProposalIdHandler
+    pub fn create_subjects_batch<'info>(ctx: Context<'_, '_, '_, 'info, CreateSubjectsBatch<'info>>, subjects: Vec<SubjectData>) -> Result<bool> {
+
+        if subjects.is_empty() || subjects.len() > MAXIMUM_BATCH_SIZE { return Err(error!(ErrorCode::IncorrectBatchSize)) }
+        if ctx.remaining_accounts.len() != subjects.len() * 3 { return Err(error!(ErrorCode::IncorrectBatchAccounts)) }
+
+        // The whole batch is validated before creating any account (dependencies are evaluated in order so that a subject can depend on a previous one of the batch)
+        for (position, subject_data) in subjects.iter().enumerate() {
+            validate_subject_data(subject_data, ctx.accounts.degree_id_handler.smaller_id_available, ctx.accounts.specialty_id_handler.smaller_id_available)?;
+            if subjects[..position].iter().any(|x| x.code == subject_data.code) { return Err(error!(ErrorCode::DuplicatedSubjectCodeInBatch)) }
+        }
+
+        let mut ids: Vec<i32> = vec![];
+
+        for (subject_data, subject_accounts) in subjects.into_iter().zip(ctx.remaining_accounts.chunks(3)) {
+
+            let id = general_id_generator(&mut ctx.accounts.subject_id_handler)?;
+            let code = subject_data.code;
+            let space = size_of::<Subject>() + subject_data.name.as_bytes().len() + subject_data.teaching_project_reference.space() + (subject_data.prerequisites.len() + subject_data.corequisites.len()) * size_of::<u32>() + section_references_space(&subject_data.sections);
+
+            let mut subject_account = Subject::default();
+            initialize_subject_account(&mut subject_account, id, subject_data, &mut ctx.accounts.code_id_subject_relation_account, &mut ctx.accounts.subject_dependency_graph)?;
+
+            create_batch_account(&ctx.accounts.authority, &subject_accounts[0], &ctx.accounts.system_program, space, &[b"subject", id.to_le_bytes().as_ref()], &subject_account)?;
+            initialize_batch_id_handler(&ctx.accounts.authority, &subject_accounts[1], &ctx.accounts.system_program, &[b"proposalIdHandler", code.to_le_bytes().as_ref()])?;
+            initialize_batch_id_handler(&ctx.accounts.authority, &subject_accounts[2], &ctx.accounts.system_program, &[b"professorProposalIdHandler", code.to_le_bytes().as_ref()])?;
+            ids.push(id);
+        }
+
+        emit! (AcademicCatalogueBatchCreated {entity: AcademicEntity::Subject, ids});
+
+        Ok(true)
+    }
+
+    // The program keeps no degree-level index nor degree scopes for the HighRanks --> the cross-listings are only stored here so that
+    // off-chain queries of a degree (or specialty) match 'cross_listings' as well as the subject's own 'degree_id' / 'specialty_id'
+    pub fn cross_list_subject(ctx: Context<CrossListSubject>, degree_id: i32, specialty_id: i32) -> Result<bool> {
+
+        let subject_account = &mut *ctx.accounts.subject_account;
+
+        let is_own_degree = subject_account.degree_id == degree_id && subject_account.specialty_id == specialty_id;
+        let is_cross_listed = subject_account.cross_listings.contains(&CrossListing { degree_id, specialty_id });
+        if is_own_degree || is_cross_listed { return Err(error!(ErrorCode::SubjectAlreadyOfferedInDegree)) }
+
+        subject_account.cross_listings.push(CrossListing { degree_id, specialty_id });
+
+        emit! (SubjectCrossListed {subject_id: subject_account.id, subject_code: subject_account.code, degree_id, specialty_id});
+
+        Ok(true)
+    }
+
+    pub fn remove_subject_cross_listing(ctx: Context<RemoveSubjectCrossListing>, degree_id: i32, specialty_id: i32) -> Result<bool> {
+
+        let subject_account = &mut *ctx.accounts.subject_account;
+
+        match subject_account.cross_listings.iter().position(|x| x.degree_id == degree_id && x.specialty_id == specialty_id) {
+            Some(position) => { subject_account.cross_listings.remove(position); },
+            None => return Err(error!(ErrorCode::CrossListingNotFound))
+        }
+
+        Ok(true)
+    }
+
+    pub fn update_subject_metadata(ctx: Context<UpdateSubjectMetadata>, ects_credits: u16, semester: SubjectSemester, teaching_language: String, modality: SubjectModality, coordinator_professor_id: i32, course_number: u8) -> Result<bool> {
+
+        let subject_metadata_account = &mut *ctx.accounts.subject_metadata_account;
+        let subject_account = &*ctx.accounts.subject_account;
+
+        if !is_valid_language_code(&teaching_language) { return Err(error!(ErrorCode::IncorrectTeachingLanguage)) }
+
+        subject_metadata_account.version = SUBJECT_METADATA_VERSION;
+        subject_metadata_account.subject_id = subject_account.id;
+        subject_metadata_account.ects_credits = ects_credits;
+        subject_metadata_account.semester = semester;
+        subject_metadata_account.teaching_language = teaching_language;
+        subject_metadata_account.modality = modality;
+        subject_metadata_account.coordinator_professor_id = coordinator_professor_id;
+        subject_metadata_account.course_number = course_number;
+        subject_metadata_account.last_update_timestamp = Clock::get().unwrap().unix_timestamp;
+        subject_metadata_account.updated_by_high_rank_id = ctx.accounts.high_rank.id;
+
+        emit! (SubjectMetadataUpdated {subject_id: subject_account.id, version: subject_metadata_account.version});
+
+        Ok(true)
+    }
+
+    pub fn create_evaluation_scheme(ctx: Context<CreateEvaluationScheme>, scheme: EvaluationSchemeData) -> Result<bool> {
+
+        validate_evaluation_scheme(&scheme)?;
+
+        let evaluation_scheme_account = &mut *ctx.accounts.evaluation_scheme;
+        evaluation_scheme_account.subject_code = ctx.accounts.subject_account.code;
+        evaluation_scheme_account.scheme = scheme;
+        evaluation_scheme_account.proposal_id = -1;
+        evaluation_scheme_account.approved_by_high_rank_id = ctx.accounts.high_rank.id;
+        evaluation_scheme_account.last_update_timestamp = Clock::get().unwrap().unix_timestamp;
+
+        emit! (EvaluationSchemeUpdated {subject_code: evaluation_scheme_account.subject_code, proposal_id: -1});
+
+        Ok(true)
+    }
+
+    pub fn update_proposal_category_config(ctx: Context<UpdateProposalCategoryConfig>, category: ProposalCategory, approval_threshold: u8, high_rank_validation_required: bool) -> Result<bool> {
+
+        let proposal_category_config_account = &mut *ctx.accounts.proposal_category_config;
+
+        proposal_category_config_account.category = category;
+        proposal_category_config_account.approval_threshold = approval_threshold;
+        proposal_category_config_account.high_rank_validation_required = high_rank_validation_required;
+        proposal_category_config_account.last_update_timestamp = Clock::get().unwrap().unix_timestamp;
+        proposal_category_config_account.updated_by_high_rank_id = ctx.accounts.high_rank.id;
+
+        emit! (ProposalCategoryConfigUpdated {category, approval_threshold, high_rank_validation_required});
+
+        Ok(true)
+    }
+
+    pub fn update_proposal_deposit_config(ctx: Context<UpdateProposalDepositConfig>, deposit_lamports: u64, maximum_open_proposals_per_user: u8) -> Result<bool> {
+
+        let proposal_deposit_config_account = &mut *ctx.accounts.proposal_deposit_config;
+
+        proposal_deposit_config_account.deposit_lamports = deposit_lamports;
+        proposal_deposit_config_account.maximum_open_proposals_per_user = maximum_open_proposals_per_user;
+        proposal_deposit_config_account.last_update_timestamp = Clock::get().unwrap().unix_timestamp;
+        proposal_deposit_config_account.updated_by_high_rank_id = ctx.accounts.high_rank.id;
+
+        emit! (ProposalDepositConfigUpdated {deposit_lamports, maximum_open_proposals_per_user});
+
+        Ok(true)
+    }
+
+    pub fn update_duplicate_proposal_cooldown(ctx: Context<UpdateDuplicateProposalCooldown>, cooldown: i64) -> Result<bool> {
+
+        let proposal_index_account = &mut *ctx.accounts.proposal_index;
+        initialize_proposal_index(proposal_index_account, ctx.accounts.subject_account.code);
+        proposal_index_account.cooldown = cooldown;
+
+        Ok(true)
+    }
+
+    pub fn update_penalty_policy(ctx: Context<UpdatePenaltyPolicy>, grace_period: i64, penalty_interval: i64, penalty_per_interval: u8, maximum_penalty: u8, decay_per_term: u8, term_duration: i64) -> Result<bool> {
+
+        let penalty_policy_account = &mut *ctx.accounts.penalty_policy;
+
+        penalty_policy_account.grace_period = grace_period;
+        penalty_policy_account.penalty_interval = penalty_interval;
+        penalty_policy_account.penalty_per_interval = penalty_per_interval;
+        penalty_policy_account.maximum_penalty = maximum_penalty;
+        penalty_policy_account.decay_per_term = decay_per_term;
+        penalty_policy_account.term_duration = term_duration;
+        penalty_policy_account.last_update_timestamp = Clock::get().unwrap().unix_timestamp;
+        penalty_policy_account.updated_by_high_rank_id = ctx.accounts.high_rank.id;
+
+        emit! (PenaltyPolicyUpdated {grace_period, penalty_interval, penalty_per_interval, maximum_penalty, decay_per_term, term_duration});
+
+        Ok(true)
+    }
+
+    pub fn update_professor_reward_config(ctx: Context<UpdateProfessorRewardConfig>, reward_tokens: u8, early_delivery_bonus_tokens: u8, early_delivery_period: i64) -> Result<bool> {
+
+        let professor_reward_config_account = &mut *ctx.accounts.professor_reward_config;
+
+        professor_reward_config_account.reward_tokens = reward_tokens;
+        professor_reward_config_account.early_delivery_bonus_tokens = early_delivery_bonus_tokens;
+        professor_reward_config_account.early_delivery_period = early_delivery_period;
+        professor_reward_config_account.last_update_timestamp = Clock::get().unwrap().unix_timestamp;
+        professor_reward_config_account.updated_by_high_rank_id = ctx.accounts.high_rank.id;
+
+        emit! (ProfessorRewardConfigUpdated {reward_tokens, early_delivery_bonus_tokens, early_delivery_period});
+
+        Ok(true)
+    }
+
+    pub fn void_penalty(ctx: Context<VoidPenalty>, void_reference: ContentReference) -> Result<bool> {
+
+        let penalty_record_account = &mut *ctx.accounts.penalty_record;
+        let professor_account = &mut *ctx.accounts.professor_account;
+
+        // The punishments may have already decayed --> they can never become negative
+        professor_account.punishments = professor_account.punishments.saturating_sub(penalty_record_account.penalty);
+
+        penalty_record_account.voided = true;
+        penalty_record_account.void_reference = void_reference;
+        penalty_record_account.voided_by_high_rank_id = ctx.accounts.high_rank.id;
+
+        emit! (PenaltyVoided {professor_id: professor_account.id, professor_proposal_id: penalty_record_account.professor_proposal_id, penalty: penalty_record_account.penalty});
+
+        Ok(true)
+    }
+
+    pub fn create_proposal_by_student(ctx: Context<CreateProposalByStudent>, title:String, content:String, content_reference: ContentReference, content_sha256: String, attachments: Vec<ProposalAttachment>, category: ProposalCategory, tags: Vec<String>, voting_starts_at: Option<i64>, sections: Vec<TeachingProjectSection>, evaluation_scheme: Option<EvaluationSchemeData>) -> Result<bool> {
+
+        validate_proposal_body(&content_reference, &content_sha256, &attachments)?;
+        validate_proposal_tags(&tags)?;
+        validate_teaching_project_sections(&sections)?;
+        if let Some(scheme) = &evaluation_scheme { validate_evaluation_scheme(scheme)? }
 
         let proposal_account = &mut *ctx.accounts.proposal_account;
         let subject_account = &mut *ctx.accounts.subject_account;
@@ -154,23 +453,38 @@ pub mod teaching_project_handler {
         
         proposal_account.title = title;
         proposal_account.content = content;
+        proposal_account.content_reference = content_reference;
+        proposal_account.content_sha256 = content_sha256;
+        proposal_account.attachments = attachments;
         proposal_account.subject_id = subject_account.id;
 
-        proposal_account.publishing_timestamp = Clock::get().unwrap().unix_timestamp;
-        proposal_account.ending_timestamp = proposal_account.publishing_timestamp + ENDING_TIMESTAMP_OFFSET as i64;
+        // The rules of the category are copied so that later changes of the config do not affect the ongoing votations
+        proposal_account.category = category;
+        proposal_account.tags = tags;
+        proposal_account.sections = sections;
+        proposal_account.evaluation_scheme = evaluation_scheme;
+        (proposal_account.approval_threshold, proposal_account.high_rank_validation_required) = proposal_category_rules(&ctx.accounts.proposal_category_config)?;
+
+        schedule_proposal_votation(proposal_account, voting_starts_at)?;
 
         proposal_account.creator_id = creator_account.id;
         proposal_account.creator_public_key = creator_account.authority.key();
 
-        let proposal_id = general_id_generator(&mut ctx.accounts.proposal_id_handler);
+        let proposal_id = general_id_generator(&mut ctx.accounts.proposal_id_handler)?;
         proposal_account.id = proposal_id;
         subject_account.pending_proposals.push(proposal_id);
 
+        lock_proposal_deposit(&ctx.accounts.authority, &mut ctx.accounts.proposal_deposit, &mut ctx.accounts.open_proposals_counter, &ctx.accounts.proposal_deposit_config, &ctx.accounts.system_program, proposal_id, subject_account)?;
+        register_proposal_in_index(&mut ctx.accounts.proposal_index, subject_account.code, proposal_account)?;
+
         proposal_account.user_type = ProposalUserType::Student;
 
         proposal_account.high_rank_validation = false;
         proposal_account.updated_by_teacher = false;
 
+        proposal_account.version = 1;
+        proposal_account.content_hash_chain = proposal_content_hash("", &proposal_account.title, &proposal_account.content, &proposal_account.content_sha256);
+
         let code_id_relation_account = &mut *ctx.accounts.code_id_subject_relation;
         let subject_info: AdditionalSubjectInfo;
         match code_id_relation_account.get_info_value_from_id_key(subject_account.id.clone()) {
@@ -180,9 +494,21 @@ pub mod teaching_project_handler {
 
         proposal_account.expected_votes = (subject_info.number_of_students as u32 + subject_info.number_of_professors as u32) as u32 + EXTRA_VOTES_EXPECTED;
 
+        // Professors of the subjects depending on this one are also expected to vote since the proposal affects their teaching
+        proposal_account.downstream_subjects = ctx.accounts.subject_dependency_graph.dependent_subjects(subject_account.code);
+        for downstream_subject_code in proposal_account.downstream_subjects.clone() {
+            if let Some(position) = code_id_relation_account.code_value.iter().position(|&x| x.code == downstream_subject_code) {
+                proposal_account.expected_votes += code_id_relation_account.code_value[position].number_of_professors as u32;
+            }
+        }
+
+        if !proposal_account.downstream_subjects.is_empty() {
+            emit! (ProposalAffectsDownstreamSubjects {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, downstream_subjects: proposal_account.downstream_subjects.clone()});
+        }
+
         //Initializating associated professor_proposal_account for possible future uses
         update_internally_initializated_id_generator(&mut *ctx.accounts.professor_proposal_id_handler);
-        associated_professor_proposal_account.id = general_id_generator(&mut ctx.accounts.professor_proposal_id_handler);
+        associated_professor_proposal_account.id = general_id_generator(&mut ctx.accounts.professor_proposal_id_handler)?;
         associated_professor_proposal_account.original_proposal_id = proposal_account.id;
         associated_professor_proposal_account.name = proposal_account.title.clone();
 
@@ -194,7 +520,12 @@ pub mod teaching_project_handler {
 
     }
 
-    pub fn create_proposal_by_professor(ctx: Context<CreateProposalByProfessor>, title:String, content:String) -> Result<bool> {
+    pub fn create_proposal_by_professor(ctx: Context<CreateProposalByProfessor>, title:String, content:String, content_reference: ContentReference, content_sha256: String, attachments: Vec<ProposalAttachment>, category: ProposalCategory, tags: Vec<String>, voting_starts_at: Option<i64>, sections: Vec<TeachingProjectSection>, evaluation_scheme: Option<EvaluationSchemeData>) -> Result<bool> {
+
+        validate_proposal_body(&content_reference, &content_sha256, &attachments)?;
+        validate_proposal_tags(&tags)?;
+        validate_teaching_project_sections(&sections)?;
+        if let Some(scheme) = &evaluation_scheme { validate_evaluation_scheme(scheme)? }
 
         let proposal_account = &mut *ctx.accounts.proposal_account;
         let subject_account = &mut *ctx.accounts.subject_account;
@@ -206,22 +537,37 @@ pub mod teaching_project_handler {
 
         proposal_account.title = title;
         proposal_account.content = content;
+        proposal_account.content_reference = content_reference;
+        proposal_account.content_sha256 = content_sha256;
+        proposal_account.attachments = attachments;
         proposal_account.subject_id = subject_account.id;
 
-        subject_account.pending_proposals.push(proposal_account.id);
+        // The rules of the category are copied so that later changes of the config do not affect the ongoing votations
+        proposal_account.category = category;
+        proposal_account.tags = tags;
+        proposal_account.sections = sections;
+        proposal_account.evaluation_scheme = evaluation_scheme;
+        (proposal_account.approval_threshold, proposal_account.high_rank_validation_required) = proposal_category_rules(&ctx.accounts.proposal_category_config)?;
 
-        proposal_account.publishing_timestamp = Clock::get().unwrap().unix_timestamp;
-        proposal_account.ending_timestamp = proposal_account.publishing_timestamp + ENDING_TIMESTAMP_OFFSET;
+        schedule_proposal_votation(proposal_account, voting_starts_at)?;
 
         proposal_account.creator_id = creator_account.id;
         proposal_account.creator_public_key = creator_account.authority.key();
 
-        proposal_account.id = general_id_generator(&mut ctx.accounts.proposal_id_handler);
+        proposal_account.id = general_id_generator(&mut ctx.accounts.proposal_id_handler)?;
+        subject_account.pending_proposals.push(proposal_account.id);
+
+        lock_proposal_deposit(&ctx.accounts.authority, &mut ctx.accounts.proposal_deposit, &mut ctx.accounts.open_proposals_counter, &ctx.accounts.proposal_deposit_config, &ctx.accounts.system_program, proposal_account.id, subject_account)?;
+        register_proposal_in_index(&mut ctx.accounts.proposal_index, subject_account.code, proposal_account)?;
+
         proposal_account.user_type = ProposalUserType::Professor;
 
         proposal_account.high_rank_validation = false;
         proposal_account.updated_by_teacher = false;
 
+        proposal_account.version = 1;
+        proposal_account.content_hash_chain = proposal_content_hash("", &proposal_account.title, &proposal_account.content, &proposal_account.content_sha256);
+
         let code_id_relation_account = &mut *ctx.accounts.code_id_subject_relation;
         let subject_info: AdditionalSubjectInfo;
         match code_id_relation_account.get_info_value_from_id_key(subject_account.id) {
@@ -231,9 +577,21 @@ pub mod teaching_project_handler {
 
         proposal_account.expected_votes = (subject_info.number_of_students as u32 + subject_info.number_of_professors as u32) as u32 + EXTRA_VOTES_EXPECTED;
 
+        // Professors of the subjects depending on this one are also expected to vote since the proposal affects their teaching
+        proposal_account.downstream_subjects = ctx.accounts.subject_dependency_graph.dependent_subjects(subject_account.code);
+        for downstream_subject_code in proposal_account.downstream_subjects.clone() {
+            if let Some(position) = code_id_relation_account.code_value.iter().position(|&x| x.code == downstream_subject_code) {
+                proposal_account.expected_votes += code_id_relation_account.code_value[position].number_of_professors as u32;
+            }
+        }
+
+        if !proposal_account.downstream_subjects.is_empty() {
+            emit! (ProposalAffectsDownstreamSubjects {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, downstream_subjects: proposal_account.downstream_subjects.clone()});
+        }
+
         //Initializating associated professor_proposal_account for possible future uses
         update_internally_initializated_id_generator(&mut *ctx.accounts.professor_proposal_id_handler);
-        associated_professor_proposal_account.id = general_id_generator(&mut ctx.accounts.professor_proposal_id_handler);
+        associated_professor_proposal_account.id = general_id_generator(&mut ctx.accounts.professor_proposal_id_handler)?;
         associated_professor_proposal_account.original_proposal_id = proposal_account.id;
         associated_professor_proposal_account.name = proposal_account.title.clone();
 
@@ -255,6 +613,8 @@ pub mod teaching_project_handler {
         let subject_professors = student_account.subjects.clone();
         if !evaluate_if_user_belong_to_subject(subject_professors, subject_account.code) { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
 
+        start_scheduled_votation(proposal_account)?;
+
         // Evaluating if student has already voted
         for student_id in &(proposal_account.students_that_have_voted) {
             if student_id.clone() == student_account.id {
@@ -287,18 +647,21 @@ pub mod teaching_project_handler {
             if proposal_has_reached_minimum_partitipation(proposal_account.supporting_votes,proposal_account.against_votes, proposal_account.expected_votes) {
                proposal_must_be_evaluated = true;
             } else {
-                proposal_account.state = ProposalState::Rejected
+                // The votation lapsed without quorum --> the proposal was rejected when it ended
+                proposal_account.state = ProposalState::Rejected;
+                proposal_account.rejection_timestamp = proposal_account.ending_timestamp;
             }
         }
 
         if proposal_must_be_evaluated {
 
-            if proposal_has_reached_agreement(proposal_account.supporting_votes, proposal_account.against_votes) {
+            if proposal_has_reached_agreement(proposal_account.supporting_votes, proposal_account.against_votes, proposal_account.approval_threshold) {
                 proposal_account.state = ProposalState::WaitingForTeacher;
                 initialize_professor_proposal_account(professor_proposal_account, ENDING_TIMESTAMP_OFFSET);
                 emit! (NewProfessorProposalCreated {proposal_id: proposal_account.id , professor_proposal_id: professor_proposal_account.id});
             } else {
                 proposal_account.state = ProposalState::Rejected;
+                proposal_account.rejection_timestamp = Clock::get().unwrap().unix_timestamp;
             }
         }
 
@@ -317,7 +680,10 @@ pub mod teaching_project_handler {
         let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
 
         let subject_professors = professor_account.subjects.clone();
-        if !evaluate_if_user_belong_to_subject(subject_professors, subject_account.code) { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
+        let teaches_downstream_subject = proposal_account.downstream_subjects.iter().any(|x| evaluate_if_user_belong_to_subject(subject_professors.clone(), *x));
+        if !evaluate_if_user_belong_to_subject(subject_professors, subject_account.code) && !teaches_downstream_subject { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
+
+        start_scheduled_votation(proposal_account)?;
 
         // Evaluating if student has already voted
         for professor_id in &(proposal_account.professors_that_have_voted) {
@@ -351,17 +717,20 @@ pub mod teaching_project_handler {
           if proposal_has_reached_minimum_partitipation(proposal_account.supporting_votes,proposal_account.against_votes, proposal_account.expected_votes) {
              proposal_must_be_evaluated = true;
           } else {
-              proposal_account.state = ProposalState::Rejected
+              // The votation lapsed without quorum --> the proposal was rejected when it ended
+              proposal_account.state = ProposalState::Rejected;
+              proposal_account.rejection_timestamp = proposal_account.ending_timestamp;
           }
       }
 
       if proposal_must_be_evaluated {
-          if proposal_has_reached_agreement(proposal_account.supporting_votes, proposal_account.against_votes) {
+          if proposal_has_reached_agreement(proposal_account.supporting_votes, proposal_account.against_votes, proposal_account.approval_threshold) {
               proposal_account.state = ProposalState::WaitingForTeacher;
               initialize_professor_proposal_account(professor_proposal_account, ENDING_TIMESTAMP_OFFSET);
               emit! (NewProfessorProposalCreated {proposal_id: proposal_account.id , professor_proposal_id: professor_proposal_account.id});
           } else {
               proposal_account.state = ProposalState::Rejected;
+              proposal_account.rejection_timestamp = Clock::get().unwrap().unix_timestamp;
           }
       }
 
@@ -372,30 +741,238 @@ pub mod teaching_project_handler {
 
     }
 
-    pub fn update_proposal_by_professor (ctx: Context<UpdateProposalByProfessor>, teaching_project_reference: String) -> Result <bool> {
+    pub fn update_proposal_by_professor (ctx: Context<UpdateProposalByProfessor>, teaching_project_reference: ContentReference, section_references: Vec<SectionReference>) -> Result <bool> {
 
         let proposal_account = &mut *ctx.accounts.proposal_account;
         let associated_professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
         let professor_account = &mut *ctx.accounts.professor_account;
 
+        // Only the professor responsible for the work (or the collaborators they have added) can submit it --> any professor of the subject while it is unassigned
+        let professor_is_eligible = if associated_professor_proposal_account.assigned_professor_id == -1 {
+            evaluate_if_user_belong_to_subject(professor_account.subjects.clone(), ctx.accounts.subject_account.code)
+        } else {
+            associated_professor_proposal_account.is_assigned_to(professor_account.id)
+        };
+        if !professor_is_eligible { return Err(error!(ErrorCode::ProfessorIsNotAssigned)) }
+
+        // Works shared by several professors must be the last draft, signed off by all of them
+        if !associated_professor_proposal_account.collaborators.is_empty() {
+            match associated_professor_proposal_account.draft_revisions.last() {
+                Some(revision) => if revision.reference != teaching_project_reference { return Err(error!(ErrorCode::IncorrectTeachingProjectReference)) },
+                None => return Err(error!(ErrorCode::DraftRevisionNotFound))
+            }
+            if !associated_professor_proposal_account.draft_is_signed_off() { return Err(error!(ErrorCode::MissingDraftSignOffs)) }
+        }
+
+        validate_section_references(&proposal_account.sections, &section_references)?;
+
+        // Reputation earned before this delivery (it mitigates the penalty if the work is delivered late)
+        let reputation_score = reputation_score_of(&ctx.accounts.professor_reputation)?;
+
         associated_professor_proposal_account.teaching_project_reference = teaching_project_reference;
+        associated_professor_proposal_account.section_references = section_references;
+        associated_professor_proposal_account.delivered_by = professor_account.authority;
+        associated_professor_proposal_account.delivery_timestamp = Clock::get().unwrap().unix_timestamp;
 
         associated_professor_proposal_account.state = ProfessorProposalState::Complete;
-        proposal_account.state = ProposalState::WaitingForHighRank;
+
+        // Categories without mandatory HighRank validation (i.e. minor bibliography updates) are accepted straight away
+        if proposal_account.high_rank_validation_required {
+            proposal_account.state = ProposalState::WaitingForHighRank;
+        } else {
+            proposal_account.state = ProposalState::Accepted;
+            record_teaching_project_version(&ctx.accounts.authority, &ctx.accounts.teaching_project_version, &ctx.accounts.system_program, &mut ctx.accounts.subject_account, &associated_professor_proposal_account.teaching_project_reference, proposal_account.id, -1)?;
+            update_teaching_project_sections(&mut ctx.accounts.subject_account, &associated_professor_proposal_account.section_references);
+            if let Some(scheme) = &proposal_account.evaluation_scheme {
+                record_evaluation_scheme(&ctx.accounts.authority, &ctx.accounts.evaluation_scheme, &ctx.accounts.system_program, ctx.accounts.subject_account.code, scheme, proposal_account.id, -1)?;
+            }
+
+            let outcome = if associated_professor_proposal_account.delivery_timestamp <= associated_professor_proposal_account.ending_timestamp { ReputationOutcome::OnTimeDelivery } else { ReputationOutcome::LateDelivery };
+            record_reputation(&ctx.accounts.authority, &ctx.accounts.professor_reputation, &ctx.accounts.system_program, professor_account.authority, ctx.accounts.subject_account.code, outcome)?;
+            professor_account.rewards += 1;
+        }
 
 
         // Evaluating if professor has delayed more than permitted --> if so, applying the appropiate penalty
 
-        let penalty = evaluating_professor_penalty(associated_professor_proposal_account, ENDING_TIMESTAMP_OFFSET);
+        let current_timestamp = Clock::get().unwrap().unix_timestamp;
+        let penalty_policy = penalty_policy_rules(&ctx.accounts.penalty_policy)?;
+        apply_penalty_decay(professor_account, &penalty_policy, current_timestamp);
+
+        let (penalty, delay) = evaluating_professor_penalty(associated_professor_proposal_account, &penalty_policy, current_timestamp);
+        let penalty = mitigate_penalty(penalty, reputation_score);
 
         if penalty > 0 {
-            professor_account.punishments = professor_account.punishments + penalty;
+            professor_account.punishments = professor_account.punishments.saturating_add(penalty);
+
+            let penalty_record = PenaltyRecord {
+                professor_id: professor_account.id,
+                professor_proposal_id: associated_professor_proposal_account.id,
+                subject_code: ctx.accounts.subject_account.code,
+                penalty,
+                delay,
+                cause: PenaltyCause::LateTeachingProjectUpdate,
+                timestamp: current_timestamp,
+                voided: false,
+                void_reference: ContentReference::default(),
+                voided_by_high_rank_id: -1
+            };
+
+            let professor_id_bytes = professor_account.id.to_le_bytes();
+            let penalty_index_bytes = professor_account.number_of_penalties.to_le_bytes();
+            create_batch_account(&ctx.accounts.authority, &ctx.accounts.penalty_record, &ctx.accounts.system_program, size_of::<PenaltyRecord>() + CONTENT_REFERENCE_RESERVED_SPACE, &[b"penaltyRecord", professor_id_bytes.as_ref(), penalty_index_bytes.as_ref()], &penalty_record)?;
+            professor_account.number_of_penalties += 1;
+
+            emit! (ProfessorPenalized {professor_id: professor_account.id, professor_proposal_id: associated_professor_proposal_account.id, penalty});
         }
 
         Ok(true)
     }
    
-    pub fn update_proposal_by_high_rank (ctx: Context<UpdateProposalByHighRank>, accepted:bool) -> Result <bool> {
+    pub fn assign_professor_proposal (ctx: Context<AssignProfessorProposal>) -> Result<bool> {
+
+        let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
+        professor_proposal_account.assigned_professor_id = ctx.accounts.assigned_professor.id;
+        professor_proposal_account.collaborators = vec![];
+
+        emit! (ProfessorAssigned {proposal_id: ctx.accounts.proposal_account.id, professor_proposal_id: professor_proposal_account.id, professor_id: professor_proposal_account.assigned_professor_id});
+
+        Ok(true)
+    }
+
+    pub fn assign_professor_proposal_by_round_robin<'info> (ctx: Context<'_, '_, '_, 'info, AssignProfessorProposalByRoundRobin<'info>>) -> Result<bool> {
+
+        let subject_code = ctx.accounts.subject_account.code;
+
+        // Every professor of the subject must be submitted (in the 'remaining_accounts') so that none of them can be skipped
+        let number_of_professors = match ctx.accounts.code_id_subject_relation.code_value.iter().find(|x| x.code == subject_code) {
+            Some(info) => info.number_of_professors as usize,
+            None => return Err(error!(ErrorCode::AdditionalSubjectInfoNotFound))
+        };
+
+        let mut professor_ids: Vec<i32> = vec![];
+        for professor_account_info in ctx.remaining_accounts.iter() {
+            let professor_account: Account<Professor> = Account::try_from(professor_account_info)?;
+            if !evaluate_if_user_belong_to_subject(professor_account.subjects.clone(), subject_code) { return Err(error!(ErrorCode::IncorrectSubjectProfessorAccounts)) }
+            if professor_ids.contains(&professor_account.id) { return Err(error!(ErrorCode::IncorrectSubjectProfessorAccounts)) }
+            professor_ids.push(professor_account.id);
+        }
+
+        if professor_ids.is_empty() || professor_ids.len() != number_of_professors { return Err(error!(ErrorCode::IncorrectSubjectProfessorAccounts)) }
+        professor_ids.sort();
+
+        // The work is given to the professor following the last one that was assigned in the subject
+        let professor_assignment_account = &mut *ctx.accounts.professor_assignment_account;
+        let assigned_professor_id = match professor_ids.iter().find(|&&x| x > professor_assignment_account.last_assigned_professor_id) {
+            Some(id) => *id,
+            None => professor_ids[0]
+        };
+
+        professor_assignment_account.subject_code = subject_code;
+        professor_assignment_account.last_assigned_professor_id = assigned_professor_id;
+
+        let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
+        professor_proposal_account.assigned_professor_id = assigned_professor_id;
+
+        emit! (ProfessorAssigned {proposal_id: ctx.accounts.proposal_account.id, professor_proposal_id: professor_proposal_account.id, professor_id: assigned_professor_id});
+
+        Ok(true)
+    }
+
+    pub fn add_professor_proposal_collaborator (ctx: Context<AddProfessorProposalCollaborator>) -> Result<bool> {
+
+        let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
+        let collaborator_id = ctx.accounts.collaborator_account.id;
+
+        if professor_proposal_account.is_assigned_to(collaborator_id) { return Err(error!(ErrorCode::IncorrectCollaborator)) }
+        if professor_proposal_account.collaborators.len() >= MAXIMUM_PROFESSOR_PROPOSAL_COLLABORATORS { return Err(error!(ErrorCode::TooManyCollaborators)) }
+
+        professor_proposal_account.collaborators.push(collaborator_id);
+
+        Ok(true)
+    }
+
+    pub fn submit_draft_revision (ctx: Context<SubmitDraftRevision>, reference: ContentReference) -> Result<bool> {
+
+        let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
+        let professor_id = ctx.accounts.professor_account.id;
+
+        if !professor_proposal_account.is_assigned_to(professor_id) { return Err(error!(ErrorCode::ProfessorIsNotAssigned)) }
+        if professor_proposal_account.draft_revisions.len() >= MAXIMUM_DRAFT_REVISIONS { return Err(error!(ErrorCode::TooManyDraftRevisions)) }
+
+        professor_proposal_account.draft_revisions.push(DraftRevision { professor_id, reference, timestamp: Clock::get().unwrap().unix_timestamp });
+
+        // Sign-offs of previous drafts are no longer valid
+        professor_proposal_account.sign_offs = vec![];
+
+        emit! (DraftRevisionSubmitted {professor_proposal_id: professor_proposal_account.id, professor_id, revision: professor_proposal_account.draft_revisions.len() as u8});
+
+        Ok(true)
+    }
+
+    pub fn sign_off_draft (ctx: Context<SignOffDraft>) -> Result<bool> {
+
+        let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
+        let professor_id = ctx.accounts.professor_account.id;
+
+        if !professor_proposal_account.is_assigned_to(professor_id) { return Err(error!(ErrorCode::ProfessorIsNotAssigned)) }
+        if professor_proposal_account.draft_revisions.is_empty() { return Err(error!(ErrorCode::DraftRevisionNotFound)) }
+        if professor_proposal_account.sign_offs.contains(&professor_id) { return Err(error!(ErrorCode::ProfessorHasAlreadySignedOff)) }
+
+        professor_proposal_account.sign_offs.push(professor_id);
+
+        emit! (DraftSignedOff {professor_proposal_id: professor_proposal_account.id, professor_id, draft_is_signed_off: professor_proposal_account.draft_is_signed_off()});
+
+        Ok(true)
+    }
+
+    pub fn request_extension (ctx: Context<RequestExtension>, reason_reference: ContentReference, requested_ending_timestamp: i64) -> Result<bool> {
+
+        let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
+
+        if professor_proposal_account.extensions.iter().any(|x| ExtensionState::Pending == x.state) { return Err(error!(ErrorCode::ExtensionRequestIsPending)) }
+        if professor_proposal_account.extensions.len() >= MAXIMUM_DEADLINE_EXTENSIONS { return Err(error!(ErrorCode::TooManyExtensionRequests)) }
+        if requested_ending_timestamp <= professor_proposal_account.ending_timestamp { return Err(error!(ErrorCode::IncorrectExtensionDeadline)) }
+
+        professor_proposal_account.extensions.push(DeadlineExtension {
+            professor_id: ctx.accounts.professor_account.id,
+            reason_reference,
+            previous_ending_timestamp: professor_proposal_account.ending_timestamp,
+            requested_ending_timestamp,
+            request_timestamp: Clock::get().unwrap().unix_timestamp,
+            state: ExtensionState::Pending,
+            resolved_by_high_rank_id: -1
+        });
+
+        emit! (ExtensionRequested {professor_proposal_id: professor_proposal_account.id, professor_id: ctx.accounts.professor_account.id, requested_ending_timestamp});
+
+        Ok(true)
+    }
+
+    pub fn resolve_extension (ctx: Context<ResolveExtension>, approved: bool) -> Result<bool> {
+
+        let professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
+
+        let extension = match professor_proposal_account.extensions.iter_mut().find(|x| ExtensionState::Pending == x.state) {
+            Some(extension) => extension,
+            None => return Err(error!(ErrorCode::ExtensionRequestNotFound))
+        };
+
+        extension.resolved_by_high_rank_id = ctx.accounts.high_rank.id;
+
+        if approved {
+            extension.state = ExtensionState::Approved;
+            professor_proposal_account.ending_timestamp = extension.requested_ending_timestamp;
+        } else {
+            extension.state = ExtensionState::Denied;
+        }
+
+        emit! (ExtensionResolved {professor_proposal_id: professor_proposal_account.id, approved, ending_timestamp: professor_proposal_account.ending_timestamp});
+
+        Ok(true)
+    }
+
+    pub fn update_proposal_by_high_rank (ctx: Context<UpdateProposalByHighRank>, decision: HighRankDecision, feedback_reference: ContentReference, user_type_code: String, mint_authority_bump: u8) -> Result <bool> {
 
         let proposal_account = &mut *ctx.accounts.proposal_account;
         let associated_professor_proposal_account = &mut *ctx.accounts.professor_proposal_account;
@@ -404,22 +981,77 @@ pub mod teaching_project_handler {
         let proposal_state: ProposalState;
         let professor_proposal_state: ProfessorProposalState;
 
-        if accepted {
-            proposal_state = ProposalState:: Accepted;
-            professor_proposal_state = ProfessorProposalState::Complete;
-            subject_account.teaching_project_reference = associated_professor_proposal_account.teaching_project_reference.clone();
-        } else {
-            proposal_state = ProposalState:: WaitingForTeacher;
-            professor_proposal_state = ProfessorProposalState::Pending;
+        match decision {
+            HighRankDecision::Accept => {
+                proposal_state = ProposalState:: Accepted;
+                professor_proposal_state = ProfessorProposalState::Complete;
+                record_teaching_project_version(&ctx.accounts.authority, &ctx.accounts.teaching_project_version, &ctx.accounts.system_program, subject_account, &associated_professor_proposal_account.teaching_project_reference, proposal_account.id, ctx.accounts.high_rank_account.id)?;
+                update_teaching_project_sections(subject_account, &associated_professor_proposal_account.section_references);
+                if let Some(scheme) = &proposal_account.evaluation_scheme {
+                    record_evaluation_scheme(&ctx.accounts.authority, &ctx.accounts.evaluation_scheme, &ctx.accounts.system_program, subject_account.code, scheme, proposal_account.id, ctx.accounts.high_rank_account.id)?;
+                }
+
+                let outcome = if associated_professor_proposal_account.delivery_timestamp <= associated_professor_proposal_account.ending_timestamp { ReputationOutcome::OnTimeDelivery } else { ReputationOutcome::LateDelivery };
+                record_reputation(&ctx.accounts.authority, &ctx.accounts.professor_reputation, &ctx.accounts.system_program, associated_professor_proposal_account.delivered_by, subject_account.code, outcome)?;
+
+                // Minting the reward of the professor that delivered the work (plus a bonus if it was delivered early enough)
+                let (reward_tokens, early_delivery_bonus_tokens, early_delivery_period) = professor_reward_rules(&ctx.accounts.professor_reward_config)?;
+                let delivered_early = associated_professor_proposal_account.delivery_timestamp + early_delivery_period <= associated_professor_proposal_account.ending_timestamp;
+                let total_reward_tokens = reward_tokens as u64 + if delivered_early { early_delivery_bonus_tokens as u64 } else { 0 };
+
+                // The accounts of the reward are only needed (and so only submitted) when the work is accepted
+                let (professor_account, mint, mint_authority_account, professor_token_account) = match (&mut ctx.accounts.professor_account, &ctx.accounts.mint, &ctx.accounts.mint_authority_account, &ctx.accounts.professor_token_account) {
+                    (Some(professor_account), Some(mint), Some(mint_authority_account), Some(professor_token_account)) => (professor_account, mint, mint_authority_account, professor_token_account),
+                    _ => return Err(error!(ErrorCode::IncorrectProfessorRewardAccounts))
+                };
+                if professor_token_account.mint != mint.key() { return Err(error!(ErrorCode::IncorrectProfessorRewardAccounts)) }
+
+                professor_account.rewards += 1;
+
+                if total_reward_tokens > 0 {
+                    // The mint authority is checked by the signature of its seeds
+                    let bump = &[mint_authority_bump];
+                    let mint_key = mint.key();
+                    let seeds = &[&[b"mint_authority", mint_key.as_ref(), user_type_code.as_bytes().as_ref(), bump][..]];
+
+                    let reward = tokens_to_base_units(total_reward_tokens, mint);
+                    let cpi_accounts = MintTo { mint: mint.to_account_info(), to: professor_token_account.to_account_info(), authority: mint_authority_account.to_account_info() };
+                    token::mint_to(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, seeds), reward)?;
+
+                    emit! (ProfessorRewarded {professor_proposal_id: associated_professor_proposal_account.id, professor_id: professor_account.id, reward, delivered_early});
+                }
+            },
+            HighRankDecision::RequestChanges => {
+                proposal_state = ProposalState:: WaitingForTeacher;
+                professor_proposal_state = ProfessorProposalState::Pending;
+                // Drafts start over in every round --> the limit of revisions applies per round
+                associated_professor_proposal_account.draft_revisions = vec![];
+                associated_professor_proposal_account.sign_offs = vec![];
+            },
+            HighRankDecision::RejectFinal => {
+                // Unlike the rejections by votation, the decision of the HighRank can not be appealed
+                proposal_state = ProposalState::RejectedByHighRank;
+                professor_proposal_state = ProfessorProposalState::Rejected;
+            }
         }
 
         proposal_account.state = proposal_state;
         associated_professor_proposal_account.state = professor_proposal_state;
 
+        // Every decision is kept along with its feedback so that the professor knows what must be changed
+        associated_professor_proposal_account.review_rounds.push(ReviewRound {
+            high_rank_id: ctx.accounts.high_rank_account.id,
+            decision,
+            feedback_reference,
+            timestamp: Clock::get().unwrap().unix_timestamp
+        });
+
+        emit! (ProposalReviewed {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, decision, round: associated_professor_proposal_account.review_rounds.len() as u8});
+
         Ok (true)
     }
 
-    pub fn give_credits_to_winning_student(ctx: Context<GiveCreditToWinningStudent>, user_type_code:String, _subject_code: u32, mint_authority_bump: u8) -> Result <bool> {
+    pub fn give_credits_to_winning_student<'info>(ctx: Context<'_, '_, '_, 'info, GiveCreditToWinningStudent<'info>>, user_type_code:String, subject_code: u32, mint_authority_bump: u8) -> Result <bool> {
 
         //Checking if the publicKey of the creator account passed matches the proposal's creator public key field
         let proposal_account_creator_public_key = ctx.accounts.proposal_account.creator_public_key;
@@ -430,7 +1062,27 @@ pub mod teaching_project_handler {
         let bump = &[mint_authority_bump];
         let mint_key = ctx.accounts.mint.key();
         let seeds = &[&[b"mint_authority", mint_key.as_ref(), user_type_code.as_bytes().as_ref(), bump][..]];
-        token::mint_to(ctx.accounts.get_mint_ctx().with_signer(seeds), TOKENS_RECEIVED_AS_REWARD as u64)?;
+
+        // The reward (measured in whole tokens) is split among the creator and the co-authors that signed the proposal --> the creator keeps the remainder
+        let reward = tokens_to_base_units(TOKENS_RECEIVED_AS_REWARD as u64, &ctx.accounts.mint);
+        // The 'remaining_accounts' hold the token accounts of the signed co-authors followed by their reputation accounts and their user accounts
+        let number_of_signed_co_authors = ctx.accounts.proposal_account.co_authors.iter().filter(|x| x.has_signed).count();
+        if ctx.remaining_accounts.len() != 3 * number_of_signed_co_authors { return Err(error!(ErrorCode::IncorrectCoAuthorAccounts)) }
+        let (co_authors_token_accounts, co_authors_other_accounts) = ctx.remaining_accounts.split_at(number_of_signed_co_authors);
+        let (co_authors_reputation_accounts, co_authors_user_accounts) = co_authors_other_accounts.split_at(number_of_signed_co_authors);
+
+        let co_authors_reward = mint_co_authors_rewards(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.mint_authority_account, co_authors_token_accounts, &ctx.accounts.proposal_account.co_authors, seeds, reward)?;
+        token::mint_to(ctx.accounts.get_mint_ctx().with_signer(seeds), reward - co_authors_reward)?;
+
+        // Updating the reputation of the creator and the co-authors
+        ctx.accounts.creator_account.rewards += 1;
+        record_reputation(&ctx.accounts.authority, &ctx.accounts.creator_reputation, &ctx.accounts.system_program, ctx.accounts.creator_account.authority, subject_code, ReputationOutcome::GrantedProposal)?;
+
+        let signed_co_authors: Vec<CoAuthor> = ctx.accounts.proposal_account.co_authors.iter().filter(|x| x.has_signed).copied().collect();
+        for (co_author, reputation_account_info) in signed_co_authors.iter().zip(co_authors_reputation_accounts.iter()) {
+            record_reputation(&ctx.accounts.authority, reputation_account_info, &ctx.accounts.system_program, co_author.authority, subject_code, ReputationOutcome::CoAuthorship)?;
+        }
+        reward_co_authors_user_accounts(co_authors_user_accounts, &signed_co_authors)?;
 
         // Updating the proposal state to avoid the credits being payed more than once
         let proposal_account = &mut *ctx.accounts.proposal_account;
@@ -440,7 +1092,7 @@ pub mod teaching_project_handler {
 
     }
 
-    pub fn give_credits_to_winning_professor(ctx: Context<GiveCreditToWinningProfessor>, user_type_code:String, _subject_code: u32, mint_authority_bump: u8) -> Result <bool> {
+    pub fn give_credits_to_winning_professor<'info>(ctx: Context<'_, '_, '_, 'info, GiveCreditToWinningProfessor<'info>>, user_type_code:String, subject_code: u32, mint_authority_bump: u8) -> Result <bool> {
 
         //Checking if the publicKey of the creator account passed matches the proposal's creator public key field
         let proposal_account_creator_public_key = ctx.accounts.proposal_account.creator_public_key;
@@ -451,7 +1103,27 @@ pub mod teaching_project_handler {
         let bump = &[mint_authority_bump];
         let mint_key = ctx.accounts.mint.key();
         let seeds = &[&[b"mint_authority", mint_key.as_ref(), user_type_code.as_bytes().as_ref(), bump][..]];
-        token::mint_to(ctx.accounts.get_mint_ctx().with_signer(seeds), TOKENS_RECEIVED_AS_REWARD as u64)?;
+
+        // The reward (measured in whole tokens) is split among the creator and the co-authors that signed the proposal --> the creator keeps the remainder
+        let reward = tokens_to_base_units(TOKENS_RECEIVED_AS_REWARD as u64, &ctx.accounts.mint);
+        // The 'remaining_accounts' hold the token accounts of the signed co-authors followed by their reputation accounts and their user accounts
+        let number_of_signed_co_authors = ctx.accounts.proposal_account.co_authors.iter().filter(|x| x.has_signed).count();
+        if ctx.remaining_accounts.len() != 3 * number_of_signed_co_authors { return Err(error!(ErrorCode::IncorrectCoAuthorAccounts)) }
+        let (co_authors_token_accounts, co_authors_other_accounts) = ctx.remaining_accounts.split_at(number_of_signed_co_authors);
+        let (co_authors_reputation_accounts, co_authors_user_accounts) = co_authors_other_accounts.split_at(number_of_signed_co_authors);
+
+        let co_authors_reward = mint_co_authors_rewards(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.mint_authority_account, co_authors_token_accounts, &ctx.accounts.proposal_account.co_authors, seeds, reward)?;
+        token::mint_to(ctx.accounts.get_mint_ctx().with_signer(seeds), reward - co_authors_reward)?;
+
+        // Updating the reputation of the creator and the co-authors
+        ctx.accounts.creator_account.rewards += 1;
+        record_reputation(&ctx.accounts.authority, &ctx.accounts.creator_reputation, &ctx.accounts.system_program, ctx.accounts.creator_account.authority, subject_code, ReputationOutcome::GrantedProposal)?;
+
+        let signed_co_authors: Vec<CoAuthor> = ctx.accounts.proposal_account.co_authors.iter().filter(|x| x.has_signed).copied().collect();
+        for (co_author, reputation_account_info) in signed_co_authors.iter().zip(co_authors_reputation_accounts.iter()) {
+            record_reputation(&ctx.accounts.authority, reputation_account_info, &ctx.accounts.system_program, co_author.authority, subject_code, ReputationOutcome::CoAuthorship)?;
+        }
+        reward_co_authors_user_accounts(co_authors_user_accounts, &signed_co_authors)?;
 
         // Updating the proposal state to avoid the credits being payed more than once
         let proposal_account = &mut *ctx.accounts.proposal_account;
@@ -467,560 +1139,3009 @@ pub mod teaching_project_handler {
         let proposal_account = &mut *ctx.accounts.proposal_account;
 
         let proposal_id = proposal_account.id;
-        let mut array_of_proposals = subject_account.pending_proposals.clone();
+        remove_pending_proposal(subject_account, proposal_id);
 
-        let position = array_of_proposals.iter().position(|x| *x == proposal_id).unwrap();
-        array_of_proposals.remove(position);
-        subject_account.pending_proposals = array_of_proposals;
+        // Ids of the closed accounts can be handed out again (the proposal's one only if no account seeded by it is left behind)
+        detach_proposal_from_index(&mut ctx.accounts.proposal_index, proposal_id);
+        if proposal_id_is_recyclable(proposal_account, &ctx.accounts.appeal_account) {
+            ctx.accounts.proposal_id_handler.free_id(proposal_id);
+        }
+        ctx.accounts.professor_proposal_id_handler.free_id(ctx.accounts.professor_proposal_account.id);
 
         Ok (true)
     }
    
-    pub fn initializate_new_system (ctx: Context <InitializeSystem>, _user_type_code:String) -> Result<bool> {
+    pub fn edit_proposal (ctx: Context<EditProposal>, title: String, content: String, content_reference: ContentReference, content_sha256: String, attachments: Vec<ProposalAttachment>) -> Result<bool> {
 
-        //Initializating the Subjects' Code-Id Relation
-        let code_id_subject_relation_account = &mut *ctx.accounts.code_id_subject_relation;
-        code_id_subject_relation_account.code_value = vec![];
-        code_id_subject_relation_account.key_id = vec![];
+        validate_proposal_body(&content_reference, &content_sha256, &attachments)?;
 
-        //Marking the system as initialized
-        let system_account = &mut *ctx.accounts.initialization_system_account;
-        system_account.system_is_initialized = true;
+        let proposal_account = &mut *ctx.accounts.proposal_account;
 
-        //Initializing Id's Generator for Academic Data 
-        let degree_id_generator_account = &mut *ctx.accounts.degree_id_handler;
+        // Every edition is chained to the previous ones so that the whole history can be verified from the emitted events
+        let previous_hash = proposal_account.content_hash_chain.clone();
+        proposal_account.content_hash_chain = proposal_content_hash(&previous_hash, &title, &content, &content_sha256);
+        proposal_account.title = title;
+        proposal_account.content = content;
+        proposal_account.content_reference = content_reference;
+        proposal_account.content_sha256 = content_sha256;
+        proposal_account.attachments = attachments;
+        proposal_account.version += 1;
+
+        reindex_proposal(&mut ctx.accounts.proposal_index, ctx.accounts.subject_account.code, proposal_account)?;
+
+        emit! (ProposalEdited {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, version: proposal_account.version, previous_hash, new_hash: proposal_account.content_hash_chain.clone()});
+
+        Ok(true)
+    }
+
+    pub fn withdraw_proposal (ctx: Context<WithdrawProposal>) -> Result<bool> {
+
+        let subject_account = &mut *ctx.accounts.subject_account;
+        let proposal_id = ctx.accounts.proposal_account.id;
+
+        if !votation_is_open(ctx.accounts.proposal_account.ending_timestamp) { return Err(error!(ErrorCode::VotationIsNotOpen)) }
+
+        remove_pending_proposal(subject_account, proposal_id);
+
+        // Ids of the closed accounts can be handed out again (the proposal's one only if no account seeded by it is left behind)
+        remove_proposal_from_index(&mut ctx.accounts.proposal_index, proposal_id);
+        if proposal_id_is_recyclable(&ctx.accounts.proposal_account, &ctx.accounts.appeal_account) {
+            ctx.accounts.proposal_id_handler.free_id(proposal_id);
+        }
+        ctx.accounts.professor_proposal_id_handler.free_id(ctx.accounts.professor_proposal_account.id);
+
+        // The deposit goes back to the creator along with the rent of the escrow account
+        let open_proposals_counter = &mut *ctx.accounts.open_proposals_counter;
+        open_proposals_counter.open_proposals = open_proposals_counter.open_proposals.saturating_sub(1);
+
+        emit! (ProposalWithdrawn {proposal_id, subject_id: subject_account.id});
+
+        Ok(true)
+    }
+
+    pub fn settle_proposal_deposit (ctx: Context<SettleProposalDeposit>) -> Result<bool> {
+
+        let proposal_account = &*ctx.accounts.proposal_account;
+
+        // A scheduled proposal that is never voted lapses at the end of its votation as well
+        if proposal_account.state.is_open_to_participation() && votation_is_open(proposal_account.ending_timestamp) { return Err(error!(ErrorCode::ProposalDepositCannotBeSettledYet)) }
+        if ProposalState::UnderAppeal == proposal_account.state { return Err(error!(ErrorCode::ProposalDepositCannotBeSettledYet)) }
+
+        let proposal_has_reached_quorum = proposal_has_reached_minimum_partitipation(proposal_account.supporting_votes, proposal_account.against_votes, proposal_account.expected_votes)
+            || proposal_has_reached_maximum_participation(proposal_account.supporting_votes, proposal_account.against_votes, MAXIMUM_PARTICIPATION);
+
+        // Without quorum the deposit is slashed to the treasury --> the rent of the escrow account is always returned to the depositor
+        let amount = ctx.accounts.proposal_deposit.amount;
+        if !proposal_has_reached_quorum && amount > 0 {
+            **ctx.accounts.proposal_deposit.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+            ctx.accounts.treasury.total_slashed_lamports += amount;
+        }
+
+        let open_proposals_counter = &mut *ctx.accounts.open_proposals_counter;
+        open_proposals_counter.open_proposals = open_proposals_counter.open_proposals.saturating_sub(1);
+
+        emit! (ProposalDepositSettled {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, amount, slashed: !proposal_has_reached_quorum});
+
+        Ok(true)
+    }
+
+    pub fn file_appeal (ctx: Context<FileAppeal>, reason_reference: ContentReference) -> Result<bool> {
+
+        let proposal_account = &mut *ctx.accounts.proposal_account;
+        let appeal_account = &mut *ctx.accounts.appeal_account;
+
+        let rejection_timestamp = match proposal_rejection_timestamp(proposal_account) {
+            Some(timestamp) => timestamp,
+            None => return Err(error!(ErrorCode::VotationIsNotRejected))
+        };
+
+        let current_timestamp = Clock::get().unwrap().unix_timestamp;
+        if current_timestamp > rejection_timestamp + APPEAL_WINDOW { return Err(error!(ErrorCode::AppealWindowIsClosed)) }
+
+        appeal_account.proposal_id = proposal_account.id;
+        appeal_account.subject_id = proposal_account.subject_id;
+        appeal_account.appellant = ctx.accounts.authority.key();
+        appeal_account.reason_reference = reason_reference;
+        appeal_account.filing_timestamp = current_timestamp;
+        appeal_account.resolution = AppealResolution::Pending;
+        appeal_account.resolved_by_high_rank_id = -1;
+
+        // Lapsed proposals are not marked as rejected by any vote --> the rejection is recorded in case the appeal is dismissed
+        proposal_account.rejection_timestamp = rejection_timestamp;
+        proposal_account.state = ProposalState::UnderAppeal;
+
+        emit! (AppealFiled {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id});
+
+        Ok(true)
+    }
+
+    pub fn resolve_appeal (ctx: Context<ResolveAppeal>, reopen_votation: bool, justification_reference: ContentReference, new_ending_timestamp: i64) -> Result<bool> {
+
+        let proposal_account = &mut *ctx.accounts.proposal_account;
+        let appeal_account = &mut *ctx.accounts.appeal_account;
+        let current_timestamp = Clock::get().unwrap().unix_timestamp;
+
+        if reopen_votation {
+
+            if new_ending_timestamp <= current_timestamp { return Err(error!(ErrorCode::IncorrectVotationDeadline)) }
+
+            // Once settled, the deposit (and the slot of the creator in the cap of open proposals) can not back the new votation
+            let proposal_deposit = &ctx.accounts.proposal_deposit;
+            if proposal_deposit.owner != &crate::ID || proposal_deposit.data_is_empty() { return Err(error!(ErrorCode::ProposalDepositIsSettled)) }
+
+            // The proposal is voted again from scratch
+            proposal_account.state = ProposalState::VotationInProgress;
+            proposal_account.ending_timestamp = new_ending_timestamp;
+            proposal_account.rejection_timestamp = 0;
+            proposal_account.supporting_votes = 0;
+            proposal_account.against_votes = 0;
+            proposal_account.students_that_have_voted = vec![];
+            proposal_account.professors_that_have_voted = vec![];
+
+            appeal_account.resolution = AppealResolution::VotationReopened;
+        } else {
+            proposal_account.state = ProposalState::Rejected;
+            appeal_account.resolution = AppealResolution::RejectionConfirmed;
+        }
+
+        appeal_account.justification_reference = justification_reference;
+        appeal_account.resolved_by_high_rank_id = ctx.accounts.high_rank.id;
+        appeal_account.resolution_timestamp = current_timestamp;
+
+        emit! (AppealResolved {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, resolution: appeal_account.resolution});
+
+        Ok(true)
+    }
+
+    pub fn invite_co_author (ctx: Context<InviteCoAuthor>, co_author: Pubkey, user_type: ProposalUserType, share: u8) -> Result<bool> {
+
+        let proposal_account = &mut *ctx.accounts.proposal_account;
+
+        if co_author == proposal_account.creator_public_key || proposal_account.co_authors.iter().any(|x| x.authority == co_author) { return Err(error!(ErrorCode::IncorrectCoAuthor)) }
+        if proposal_account.co_authors.len() >= MAXIMUM_CO_AUTHORS { return Err(error!(ErrorCode::TooManyCoAuthors)) }
+
+        // The invited user must be a Student or a Professor (according to the user type) of the subject of the proposal
+        let co_author_account_seed: &[u8] = match user_type { ProposalUserType::Student => b"student", ProposalUserType::Professor => b"professor" };
+        let (co_author_account_key, _bump) = Pubkey::find_program_address(&[co_author_account_seed, co_author.as_ref()], ctx.program_id);
+        if co_author_account_key != ctx.accounts.co_author_account.key() { return Err(error!(ErrorCode::IncorrectCoAuthor)) }
+
+        let co_author_subjects = match user_type {
+            ProposalUserType::Student => Account::<Student>::try_from(&ctx.accounts.co_author_account).map_err(|_| error!(ErrorCode::IncorrectCoAuthor))?.subjects.clone(),
+            ProposalUserType::Professor => Account::<Professor>::try_from(&ctx.accounts.co_author_account).map_err(|_| error!(ErrorCode::IncorrectCoAuthor))?.subjects.clone()
+        };
+        if !evaluate_if_user_belong_to_subject(co_author_subjects, ctx.accounts.subject_account.code) { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
+
+        // The creator always keeps part of the reward
+        let total_share: u32 = proposal_account.co_authors.iter().map(|x| x.share as u32).sum::<u32>() + share as u32;
+        if share == 0 || total_share >= 100 { return Err(error!(ErrorCode::IncorrectCoAuthorShare)) }
+
+        proposal_account.co_authors.push(CoAuthor { authority: co_author, user_type, share, has_signed: false });
+
+        emit! (CoAuthorInvited {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, co_author, share});
+
+        Ok(true)
+    }
+
+    pub fn co_sign_proposal (ctx: Context<CoSignProposal>) -> Result<bool> {
+
+        let proposal_account = &mut *ctx.accounts.proposal_account;
+        let co_author_key = ctx.accounts.authority.key();
+
+        // Invited users are not listed as co-authors until they sign the proposal
+        match proposal_account.co_authors.iter_mut().find(|x| x.authority == co_author_key && !x.has_signed) {
+            Some(co_author) => co_author.has_signed = true,
+            None => return Err(error!(ErrorCode::CoAuthorNotInvited))
+        }
+
+        emit! (ProposalCoSigned {proposal_id: proposal_account.id, subject_id: proposal_account.subject_id, co_author: co_author_key});
+
+        Ok(true)
+    }
+
+    pub fn comment_proposal_by_student (ctx: Context<CommentProposalByStudent>, parent_comment_id: i32, content_reference: Option<ContentReference>, text: String) -> Result<bool> {
+
+        let student_account = &*ctx.accounts.student_account;
+        if !evaluate_if_user_belong_to_subject(student_account.subjects.clone(), ctx.accounts.subject_account.code) { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
+
+        initialize_comment_account(&mut ctx.accounts.comment_account, &mut ctx.accounts.proposal_account, student_account.authority, ProposalUserType::Student, parent_comment_id, content_reference, text)?;
+
+        Ok(true)
+    }
+
+    pub fn comment_proposal_by_professor (ctx: Context<CommentProposalByProfessor>, parent_comment_id: i32, content_reference: Option<ContentReference>, text: String) -> Result<bool> {
+
+        let professor_account = &*ctx.accounts.professor_account;
+        if !evaluate_if_user_belong_to_subject(professor_account.subjects.clone(), ctx.accounts.subject_account.code) { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
+
+        initialize_comment_account(&mut ctx.accounts.comment_account, &mut ctx.accounts.proposal_account, professor_account.authority, ProposalUserType::Professor, parent_comment_id, content_reference, text)?;
+
+        Ok(true)
+    }
+
+    pub fn propose_amendment_by_student (ctx: Context<ProposeAmendmentByStudent>, title: String, content: String, content_reference: ContentReference, content_sha256: String) -> Result<bool> {
+
+        let student_account = &*ctx.accounts.student_account;
+        if !evaluate_if_user_belong_to_subject(student_account.subjects.clone(), ctx.accounts.subject_account.code) { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
+
+        let amendment_body = Amendment { title, content, content_reference, content_sha256, ..Default::default() };
+        initialize_amendment_account(&mut ctx.accounts.amendment_account, &mut ctx.accounts.proposal_account, student_account.authority, ProposalUserType::Student, amendment_body)?;
+
+        Ok(true)
+    }
+
+    pub fn propose_amendment_by_professor (ctx: Context<ProposeAmendmentByProfessor>, title: String, content: String, content_reference: ContentReference, content_sha256: String) -> Result<bool> {
+
+        let professor_account = &*ctx.accounts.professor_account;
+        if !evaluate_if_user_belong_to_subject(professor_account.subjects.clone(), ctx.accounts.subject_account.code) { return Err(error!(ErrorCode::UserDoesNotBelongToTheSubject)) }
+
+        let amendment_body = Amendment { title, content, content_reference, content_sha256, ..Default::default() };
+        initialize_amendment_account(&mut ctx.accounts.amendment_account, &mut ctx.accounts.proposal_account, professor_account.authority, ProposalUserType::Professor, amendment_body)?;
+
+        Ok(true)
+    }
+
+    pub fn accept_amendment (ctx: Context<AcceptAmendment>, reset_votes: bool) -> Result<bool> {
+
+        let proposal_account = &mut *ctx.accounts.proposal_account;
+        let amendment_account = &mut *ctx.accounts.amendment_account;
+
+        if !votation_is_open(proposal_account.ending_timestamp) { return Err(error!(ErrorCode::VotationIsNotOpen)) }
+
+        // Once voted, the proposal can only change if the votes casted over the previous version are discarded (as editions are not allowed either)
+        if proposal_account.supporting_votes + proposal_account.against_votes > 0 && !reset_votes { return Err(error!(ErrorCode::AmendmentRequiresVoteReset)) }
+
+        // The amended body is chained to the previous versions in the same way as the editions of the creator
+        let previous_hash = proposal_account.content_hash_chain.clone();
+        proposal_account.content_hash_chain = proposal_content_hash(&previous_hash, &amendment_account.title, &amendment_account.content, &amendment_account.content_sha256);
+        proposal_account.title = amendment_account.title.clone();
+        proposal_account.content = amendment_account.content.clone();
+        proposal_account.content_reference = amendment_account.content_reference.clone();
+        proposal_account.content_sha256 = amendment_account.content_sha256.clone();
+        proposal_account.version += 1;
+
+        reindex_proposal(&mut ctx.accounts.proposal_index, ctx.accounts.subject_account.code, proposal_account)?;
+
+        // Votes casted over the previous version can be discarded so that every member votes the amended proposal
+        if reset_votes {
+            proposal_account.supporting_votes = 0;
+            proposal_account.against_votes = 0;
+            proposal_account.students_that_have_voted = vec![];
+            proposal_account.professors_that_have_voted = vec![];
+        }
+
+        amendment_account.state = AmendmentState::Accepted;
+
+        emit! (AmendmentAccepted {proposal_id: proposal_account.id, amendment_id: amendment_account.id, version: proposal_account.version, votes_reset: reset_votes});
+
+        Ok(true)
+    }
+   
+    pub fn initializate_new_system (ctx: Context <InitializeSystem>, _user_type_code:String) -> Result<bool> {
+
+        //Initializating the Subjects' Code-Id Relation
+        let code_id_subject_relation_account = &mut *ctx.accounts.code_id_subject_relation;
+        code_id_subject_relation_account.code_value = vec![];
+        code_id_subject_relation_account.key_id = vec![];
+
+        //Marking the system as initialized
+        let system_account = &mut *ctx.accounts.initialization_system_account;
+        system_account.system_is_initialized = true;
+
+        //Initializing Id's Generator for Academic Data 
+        let degree_id_generator_account = &mut *ctx.accounts.degree_id_handler;
         degree_id_generator_account.smaller_id_available = 1;
 
-        let faculty_id_generator_account = &mut *ctx.accounts.faculty_id_handler;
-        faculty_id_generator_account.smaller_id_available = 1;
+        let faculty_id_generator_account = &mut *ctx.accounts.faculty_id_handler;
+        faculty_id_generator_account.smaller_id_available = 1;
+
+        let specialty_id_generator_account = &mut *ctx.accounts.specialty_id_handler;
+        specialty_id_generator_account.smaller_id_available = 1;
+
+        let subject_id_generator_account = &mut *ctx.accounts.subject_id_handler;
+        subject_id_generator_account.smaller_id_available = 1;
+
+        //Initializing the treasury that receives the slashed proposal deposits
+        let treasury_account = &mut *ctx.accounts.treasury;
+        treasury_account.total_slashed_lamports = 0;
+
+        Ok(true)
+
+    }
+
+
+    
+}
+
+                                                       // ------- AUX FUNCTIONS ------------- //
+
+// Freed ids are handed out first (in the same order they were freed) so that clients can derive the next PDA with 'next_id'
+fn general_id_generator (id_handler_account: &mut Account<IdHandler>) ->  Result<i32> {
+
+    if !id_handler_account.reused_id.is_empty() {
+        return Ok(id_handler_account.reused_id.remove(0) as i32);
+    }
+
+    let id: i32 = id_handler_account.smaller_id_available;
+    id_handler_account.smaller_id_available = match id.checked_add(1) {
+        Some(smaller_id_available) => smaller_id_available,
+        None => return Err(error!(ErrorCode::IdOverflow))
+    };
+
+    Ok(id)
+}
+
+pub fn update_internally_initializated_id_generator(id_handler_account: &mut IdHandler) {
+
+    if id_handler_account.smaller_id_available == 0 { id_handler_account.smaller_id_available += 1; }
+}
+
+
+// Every dynamic field is counted as in the rest of the contract: its default size (24 bytes) minus 20, plus its real content
+fn subject_account_space(subject_account: &Subject, extra_bytes: usize) -> usize {
+    size_of::<Subject>()
+        + subject_account.name.as_bytes().len()
+        + subject_account.teaching_project_reference.space()
+        + subject_account.pending_proposals.len() * size_of::<i32>()
+        + subject_account.cross_listings.len() * size_of::<CrossListing>()
+        + subject_account.prerequisites.len() * size_of::<u32>()
+        + subject_account.corequisites.len() * size_of::<u32>()
+        + section_references_space(&subject_account.sections)
+        + extra_bytes
+        - 7 * 20
+}
+
+// The subject is never shrunk when its teaching project is replaced, so that both the old and the new references always fit
+fn subject_account_space_for_teaching_project(subject_account: &Subject, teaching_project_reference: &ContentReference, section_references: &[SectionReference]) -> usize {
+
+    let sections_growth: usize = section_references.iter().map(|new| match subject_account.sections.iter().find(|x| x.section == new.section) {
+        Some(current) => new.reference.space().saturating_sub(current.reference.space()),
+        None => size_of::<SectionReference>() + new.reference.space()
+    }).sum();
+
+    subject_account_space(subject_account, teaching_project_reference.space().saturating_sub(subject_account.teaching_project_reference.space()) + sections_growth)
+}
+
+fn section_references_space(section_references: &[SectionReference]) -> usize {
+    section_references.iter().map(|x| size_of::<SectionReference>() + x.reference.space()).sum()
+}
+
+fn professor_proposal_account_space(professor_proposal_account: &ProfessorProposal, extra_bytes: usize) -> usize {
+    size_of::<ProfessorProposal>()
+        + professor_proposal_account.name.as_bytes().len()
+        + CONTENT_REFERENCE_RESERVED_SPACE          // Teaching project, reserved even if it has not been submitted yet
+        + professor_proposal_account.review_rounds.iter().map(|round| size_of::<ReviewRound>() + round.feedback_reference.space()).sum::<usize>()
+        + professor_proposal_account.collaborators.len() * size_of::<i32>()
+        + professor_proposal_account.extensions.iter().map(|extension| size_of::<DeadlineExtension>() + extension.reason_reference.space()).sum::<usize>()
+        + professor_proposal_account.draft_revisions.iter().map(|revision| size_of::<DraftRevision>() + revision.reference.space()).sum::<usize>()
+        + professor_proposal_account.sign_offs.len() * size_of::<i32>()
+        + section_references_space(&professor_proposal_account.section_references)
+        + extra_bytes
+        - 7 * 20
+}
+
+fn proposal_account_space(proposal_account: &Proposal, extra_bytes: usize) -> usize {
+    size_of::<Proposal>()
+        + proposal_account.students_that_have_voted.len() * size_of::<i32>()
+        + proposal_account.professors_that_have_voted.len() * size_of::<i32>()
+        + proposal_body_space(&proposal_account.title, &proposal_account.content, &proposal_account.content_reference, &proposal_account.content_sha256, &proposal_account.attachments)
+        + proposal_account.downstream_subjects.len() * size_of::<u32>()
+        + proposal_account.content_hash_chain.as_bytes().len()
+        + proposal_tags_space(&proposal_account.tags)
+        + proposal_account.co_authors.len() * size_of::<CoAuthor>()
+        + proposal_account.sections.len() * size_of::<TeachingProjectSection>()
+        + evaluation_scheme_space(&proposal_account.evaluation_scheme)
+        + extra_bytes
+        - 13 * 20
+}
+
+// Dynamic content of the fields that can be modified by the creator of the proposal (title, summary and off-chain body)
+fn proposal_body_space(title: &str, content: &str, content_reference: &ContentReference, content_sha256: &str, attachments: &[ProposalAttachment]) -> usize {
+    title.as_bytes().len()
+        + content.as_bytes().len()
+        + content_reference.space()
+        + content_sha256.as_bytes().len()
+        + attachments.iter().map(|x| size_of::<ProposalAttachment>() + x.name.as_bytes().len() + x.reference.space() + x.mime_type.as_bytes().len() - 3 * 20).sum::<usize>()
+}
+
+fn validate_subject_dependencies(code_id_relation_account: &mut CodeIdSubjectRelation, subject_dependency_graph: &SubjectDependencyGraph, code: u32, prerequisites: &[u32], corequisites: &[u32]) -> Result<()> {
+
+    let mut declared_codes: Vec<u32> = prerequisites.to_vec();
+    declared_codes.extend(corequisites.iter());
+
+    for (position, required_code) in declared_codes.iter().enumerate() {
+
+        // A subject cannot depend on itself nor be declared twice (neither as prerequisite and corequisite at the same time)
+        if *required_code == code || declared_codes[..position].contains(required_code) { return Err(error!(ErrorCode::IncorrectSubjectDependency)) }
+
+        // Only codes with an existing subject are accepted (codes registered by users have '-1' as id)
+        match code_id_relation_account.get_id_key_from_code_value(*required_code) {
+            Some(id) if id >= 1 => {},
+            _ => return Err(error!(ErrorCode::SubjectDependencyNotFound))
+        }
+    }
+
+    if subject_dependency_graph.prerequisites_form_cycle(code, prerequisites) { return Err(error!(ErrorCode::SubjectDependencyCycle)) }
+
+    if (subject_dependency_graph.dependencies.len() + declared_codes.len()) * size_of::<SubjectDependency>() + 12 > SOLANA_ACCOUNT_MAX_SIZE {
+        return Err(error!(ErrorCode::SubjectDependencyGraphIsFull))
+    }
+
+    Ok(())
+}
+
+fn initialize_subject_account(subject_account: &mut Subject, id: i32, subject_data: SubjectData, code_id_relation_account: &mut CodeIdSubjectRelation, subject_dependency_graph: &mut SubjectDependencyGraph) -> Result<()> {
+
+    // Evaluating the prerequisites and corequisites before creating the subject (they must exist and must not form any cycle)
+    validate_subject_dependencies(code_id_relation_account, subject_dependency_graph, subject_data.code, &subject_data.prerequisites, &subject_data.corequisites)?;
+    subject_dependency_graph.add_dependencies(subject_data.code, &subject_data.prerequisites, &subject_data.corequisites);
+
+    // The index of sections is seeded with the same checks applied to the sections delivered by the professors
+    let declared_sections: Vec<TeachingProjectSection> = subject_data.sections.iter().map(|x| x.section).collect();
+    validate_section_references(&declared_sections, &subject_data.sections)?;
+
+    subject_account.id = id;
+    subject_account.name = subject_data.name;
+    subject_account.degree_id = subject_data.degree_id;
+    subject_account.specialty_id = subject_data.specialty_id;
+    subject_account.course = subject_data.course;
+    subject_account.code = subject_data.code;
+    subject_account.teaching_project_reference = subject_data.teaching_project_reference;
+    subject_account.prerequisites = subject_data.prerequisites;
+    subject_account.corequisites = subject_data.corequisites;
+    subject_account.sections = subject_data.sections;
+
+    code_id_relation_account.add_key_value_subject_pair(id, subject_data.code, false, false);
+
+    Ok(())
+}
+
+// Same constraints applied by 'CreateDegree', 'CreateSpecialty' and 'CreateSubject' to a single entity
+fn validate_degree_data(degree_data: &DegreeData, faculty_smaller_id_available: i32) -> Result<()> {
+    if degree_data.name.len() > 500 { return Err(error!(ErrorCode::NameIsTooLong)) }
+    if degree_data.faculty_id < 1 || degree_data.faculty_id >= faculty_smaller_id_available { return Err(error!(ErrorCode::IncorrectFacultyId)) }
+    Ok(())
+}
+
+fn validate_specialty_data(specialty_data: &SpecialtyData, degree_smaller_id_available: i32) -> Result<()> {
+    if specialty_data.name.len() > 500 { return Err(error!(ErrorCode::NameIsTooLong)) }
+    if specialty_data.degree_id < 1 || specialty_data.degree_id >= degree_smaller_id_available { return Err(error!(ErrorCode::IncorrectDegreeId)) }
+    Ok(())
+}
+
+fn validate_subject_data(subject_data: &SubjectData, degree_smaller_id_available: i32, specialty_smaller_id_available: i32) -> Result<()> {
+    if subject_data.degree_id < 1 || subject_data.degree_id >= degree_smaller_id_available { return Err(error!(ErrorCode::IncorrectDegreeId)) }
+    if subject_data.specialty_id != -1 && (subject_data.specialty_id < 1 || subject_data.specialty_id >= specialty_smaller_id_available) { return Err(error!(ErrorCode::IncorrectSpecialtyId)) }
+    if !subject_data.teaching_project_reference.is_valid() { return Err(error!(ErrorCode::IncorrectTeachingProjectReference)) }
+    Ok(())
+}
+
+// Creates (as Anchor's 'init' does) a PDA owned by the program and serializes the given account into it
+fn create_batch_account<'info, T: AccountSerialize>(payer: &Signer<'info>, new_account: &AccountInfo<'info>, system_program: &Program<'info, System>, space: usize, seeds: &[&[u8]], account_data: &T) -> Result<()> {
+
+    let (expected_key, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    if expected_key != new_account.key() { return Err(error!(ErrorCode::IncorrectBatchAccounts)) }
+    if new_account.owner != &System::id() || !new_account.data_is_empty() { return Err(error!(ErrorCode::BatchAccountAlreadyExists)) }
+
+    let bump_seed = [bump];
+    let mut signer_seeds: Vec<&[u8]> = seeds.to_vec();
+    signer_seeds.push(&bump_seed);
+    let signer = &[&signer_seeds[..]];
+
+    let required_lamports = Rent::get()?.minimum_balance(space);
+
+    if new_account.lamports() == 0 {
+        let cpi_accounts = CreateAccount { from: payer.to_account_info(), to: new_account.clone() };
+        system_instructions::create_account(CpiContext::new_with_signer(system_program.to_account_info(), cpi_accounts, signer), required_lamports, space as u64, &crate::ID)?;
+    } else {
+        // The PDA could have received lamports before being created
+        let missing_lamports = required_lamports.saturating_sub(new_account.lamports());
+        if missing_lamports > 0 {
+            let cpi_accounts = Transfer { from: payer.to_account_info(), to: new_account.clone() };
+            system_instructions::transfer(CpiContext::new(system_program.to_account_info(), cpi_accounts), missing_lamports)?;
+        }
+        system_instructions::allocate(CpiContext::new_with_signer(system_program.to_account_info(), Allocate { account_to_allocate: new_account.clone() }, signer), space as u64)?;
+        system_instructions::assign(CpiContext::new_with_signer(system_program.to_account_info(), Assign { account_to_assign: new_account.clone() }, signer), &crate::ID)?;
+    }
+
+    let mut data = new_account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    account_data.try_serialize(&mut writer)?;
+
+    Ok(())
+}
+
+// Proposal id handlers are shared by every subject with the same code, so they may already exist (as 'init_if_needed' in 'CreateSubject')
+fn initialize_batch_id_handler<'info>(payer: &Signer<'info>, id_handler_account_info: &AccountInfo<'info>, system_program: &Program<'info, System>, seeds: &[&[u8]]) -> Result<()> {
+
+    if id_handler_account_info.owner == &crate::ID {
+
+        let (expected_key, _bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        if expected_key != id_handler_account_info.key() { return Err(error!(ErrorCode::IncorrectBatchAccounts)) }
+
+        let mut id_handler_account: Account<IdHandler> = Account::try_from(id_handler_account_info)?;
+        update_internally_initializated_id_generator(&mut id_handler_account);
+        return id_handler_account.exit(&crate::ID);
+    }
+
+    let id_handler_account = IdHandler { smaller_id_available: 1, reused_id: vec![] };
+    create_batch_account(payer, id_handler_account_info, system_program, size_of::<IdHandler>() + 140, seeds, &id_handler_account)
+}
+
+fn remove_pending_proposal(subject_account: &mut Subject, proposal_id: i32) {
+    if let Some(position) = subject_account.pending_proposals.iter().position(|x| *x == proposal_id) {
+        subject_account.pending_proposals.remove(position);
+    }
+}
+
+// Comments, amendments and appeals are seeded by the proposal id and are never closed --> reusing the id would collide with them
+fn proposal_id_is_recyclable(proposal_account: &Proposal, appeal_account: &AccountInfo) -> bool {
+    proposal_account.number_of_comments == 0 && proposal_account.number_of_amendments == 0 && appeal_account.data_is_empty()
+}
+
+// The entry keeps blocking duplicates until its cooldown ends, but it no longer refers to an id that may be handed out again
+fn detach_proposal_from_index(proposal_index_account: &mut ProposalIndex, proposal_id: i32) {
+    proposal_index_account.entries.iter_mut().filter(|x| x.proposal_id == proposal_id).for_each(|x| x.proposal_id = -1);
+}
+
+fn proposal_content_hash(previous_hash: &str, title: &str, content: &str, content_sha256: &str) -> String {
+    digest(format!("{}|{}|{}|{}", previous_hash, title, content, content_sha256))
+}
+
+// CIDv0 (base58, 46 bytes) or CIDv1 (base32, 'b' multibase prefix)
+fn is_valid_ipfs_cid(identifier: &str) -> bool {
+    let is_cid_v0 = identifier.len() == 46 && identifier.starts_with("Qm")
+        && identifier.chars().all(|x| x.is_ascii_alphanumeric() && !['0', 'O', 'I', 'l'].contains(&x));
+    let is_cid_v1 = identifier.len() >= 59 && identifier.len() <= MAXIMUM_CONTENT_IDENTIFIER_LENGTH && identifier.starts_with('b')
+        && identifier.chars().skip(1).all(|x| x.is_ascii_lowercase() || ('2'..='7').contains(&x));
+    is_cid_v0 || is_cid_v1
+}
+
+// Arweave transaction ids are 32 bytes encoded in base64url (43 bytes)
+fn is_valid_arweave_id(identifier: &str) -> bool {
+    identifier.len() == 43 && identifier.chars().all(|x| x.is_ascii_alphanumeric() || x == '-' || x == '_')
+}
+
+fn is_valid_sha256_hex(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|x| x.is_ascii_digit() || ('a'..='f').contains(&x))
+}
+
+fn validate_proposal_body(content_reference: &ContentReference, content_sha256: &str, attachments: &[ProposalAttachment]) -> Result<()> {
+
+    if !content_reference.is_valid() { return Err(error!(ErrorCode::IncorrectProposalContentReference)) }
+    if !is_valid_sha256_hex(content_sha256) { return Err(error!(ErrorCode::IncorrectProposalContentHash)) }
+
+    // The hash of the reference (if given) must be the one the content hash chain is built with
+    if content_reference.sha256.as_ref().is_some_and(|x| x != content_sha256) { return Err(error!(ErrorCode::IncorrectProposalContentHash)) }
+
+    if attachments.len() > MAXIMUM_PROPOSAL_ATTACHMENTS { return Err(error!(ErrorCode::TooManyProposalAttachments)) }
+
+    for attachment in attachments {
+        if attachment.name.is_empty() || attachment.name.len() > MAXIMUM_ATTACHMENT_FIELD_LENGTH { return Err(error!(ErrorCode::IncorrectProposalAttachment)) }
+        if !attachment.reference.is_valid() { return Err(error!(ErrorCode::IncorrectProposalAttachment)) }
+
+        // Mime types must follow the 'type/subtype' format
+        let mime_type_is_valid = attachment.mime_type.len() <= MAXIMUM_ATTACHMENT_FIELD_LENGTH
+            && attachment.mime_type.split('/').count() == 2
+            && attachment.mime_type.split('/').all(|x| !x.is_empty());
+        if !mime_type_is_valid { return Err(error!(ErrorCode::IncorrectProposalAttachment)) }
+    }
+
+    Ok(())
+}
+
+fn lock_proposal_deposit<'info>(payer: &Signer<'info>, proposal_deposit: &mut Account<'info, ProposalDeposit>, open_proposals_counter: &mut OpenProposalsCounter, proposal_deposit_config: &AccountInfo<'info>, system_program: &Program<'info, System>, proposal_id: i32, subject_account: &Subject) -> Result<()> {
+
+    // Neither deposit nor cap are applied until a HighRank configures them
+    let (deposit_lamports, maximum_open_proposals) = if proposal_deposit_config.data_is_empty() {
+        (0, u8::MAX)
+    } else {
+        let config = Account::<ProposalDepositConfig>::try_from(proposal_deposit_config)?;
+        (config.deposit_lamports, config.maximum_open_proposals_per_user)
+    };
+
+    if open_proposals_counter.open_proposals >= maximum_open_proposals { return Err(error!(ErrorCode::TooManyOpenProposals)) }
+
+    open_proposals_counter.authority = payer.key();
+    open_proposals_counter.subject_code = subject_account.code;
+    open_proposals_counter.open_proposals += 1;
+
+    if deposit_lamports > 0 {
+        let transfer_accounts = Transfer { from: payer.to_account_info(), to: proposal_deposit.to_account_info() };
+        system_instructions::transfer(CpiContext::new(system_program.to_account_info(), transfer_accounts), deposit_lamports)?;
+    }
+
+    proposal_deposit.proposal_id = proposal_id;
+    proposal_deposit.subject_id = subject_account.id;
+    proposal_deposit.depositor = payer.key();
+    proposal_deposit.amount = deposit_lamports;
+
+    Ok(())
+}
+
+// Case and whitespace differences are ignored so that re-submissions of the same text are detected
+fn normalized_proposal_hash(title: &str, content: &str, content_sha256: &str) -> String {
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase();
+    digest(format!("{}|{}|{}", normalize(title), normalize(content), content_sha256))
+}
+
+fn initialize_proposal_index(proposal_index_account: &mut ProposalIndex, subject_code: u32) {
+    if proposal_index_account.subject_code == 0 {
+        proposal_index_account.subject_code = subject_code;
+        proposal_index_account.cooldown = DEFAULT_DUPLICATE_PROPOSAL_COOLDOWN;
+    }
+}
+
+fn register_proposal_in_index(proposal_index_account: &mut ProposalIndex, subject_code: u32, proposal_account: &Proposal) -> Result<()> {
+
+    initialize_proposal_index(proposal_index_account, subject_code);
+
+    let current_timestamp = Clock::get().unwrap().unix_timestamp;
+    let cooldown = proposal_index_account.cooldown;
+
+    // Entries of proposals whose votation finished before the cooldown are no longer taken into account
+    proposal_index_account.entries.retain(|x| current_timestamp < x.ending_timestamp + cooldown);
+
+    let content_hash = normalized_proposal_hash(&proposal_account.title, &proposal_account.content, &proposal_account.content_sha256);
+    if proposal_index_account.entries.iter().any(|x| x.content_hash == content_hash) { return Err(error!(ErrorCode::DuplicatedProposal)) }
+
+    if proposal_index_account.entries.len() >= PROPOSAL_INDEX_CAPACITY {
+        proposal_index_account.entries.remove(0);
+    }
+
+    proposal_index_account.entries.push(ProposalIndexEntry { proposal_id: proposal_account.id, ending_timestamp: proposal_account.ending_timestamp, content_hash });
+
+    Ok(())
+}
+
+// The previous entry of the proposal is replaced, so the new title and content are also checked against the other proposals
+fn reindex_proposal(proposal_index_account: &mut ProposalIndex, subject_code: u32, proposal_account: &Proposal) -> Result<()> {
+    remove_proposal_from_index(proposal_index_account, proposal_account.id);
+    register_proposal_in_index(proposal_index_account, subject_code, proposal_account)
+}
+
+// A withdrawn proposal does not prevent its text from being submitted again
+fn remove_proposal_from_index(proposal_index_account: &mut ProposalIndex, proposal_id: i32) {
+    proposal_index_account.entries.retain(|x| x.proposal_id != proposal_id);
+}
+
+// Every accepted teaching project is kept as a new version of the subject's one, linked to the previous version
+fn record_teaching_project_version<'info>(payer: &Signer<'info>, version_account_info: &AccountInfo<'info>, system_program: &Program<'info, System>, subject_account: &mut Subject, teaching_project_reference: &ContentReference, proposal_id: i32, high_rank_id: i32) -> Result<()> {
+
+    let version = subject_account.teaching_project_version + 1;
+
+    let teaching_project_version = TeachingProjectVersion {
+        subject_code: subject_account.code,
+        version,
+        teaching_project_reference: teaching_project_reference.clone(),
+        previous_version: subject_account.teaching_project_version,
+        previous_reference: subject_account.teaching_project_reference.clone(),
+        proposal_id,
+        approved_by_high_rank_id: high_rank_id,
+        timestamp: Clock::get().unwrap().unix_timestamp
+    };
+
+    let space = size_of::<TeachingProjectVersion>() + teaching_project_version.teaching_project_reference.space() + teaching_project_version.previous_reference.space();
+    create_batch_account(payer, version_account_info, system_program, space, &[b"teachingProjectVersion", subject_account.code.to_le_bytes().as_ref(), version.to_le_bytes().as_ref()], &teaching_project_version)?;
+
+    subject_account.teaching_project_reference = teaching_project_version.teaching_project_reference;
+    subject_account.teaching_project_version = version;
+
+    emit! (TeachingProjectVersionRecorded {subject_code: subject_account.code, version, proposal_id, approved_by_high_rank_id: high_rank_id});
+
+    Ok(())
+}
+
+// Only the sections changed by the proposal are replaced, the rest of the index is kept
+fn update_teaching_project_sections(subject_account: &mut Subject, section_references: &[SectionReference]) {
+
+    // A replacement of the whole teaching project leaves the previous index outdated
+    if section_references.is_empty() {
+        subject_account.sections = vec![];
+        return;
+    }
+
+    for section_reference in section_references {
+        match subject_account.sections.iter_mut().find(|x| x.section == section_reference.section) {
+            Some(current) => current.reference = section_reference.reference.clone(),
+            None => subject_account.sections.push(section_reference.clone())
+        }
+    }
+
+    emit! (TeachingProjectSectionsUpdated {subject_code: subject_account.code, sections: section_references.iter().map(|x| x.section).collect()});
+}
+
+// The scheme carried by an accepted proposal is validated again before replacing the one of the subject (created if it had none)
+fn record_evaluation_scheme<'info>(payer: &Signer<'info>, evaluation_scheme_info: &AccountInfo<'info>, system_program: &Program<'info, System>, subject_code: u32, scheme: &EvaluationSchemeData, proposal_id: i32, high_rank_id: i32) -> Result<()> {
+
+    validate_evaluation_scheme(scheme)?;
+
+    let subject_code_bytes = subject_code.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"evaluationScheme", subject_code_bytes.as_ref()];
+
+    let evaluation_scheme = EvaluationScheme {
+        subject_code,
+        scheme: scheme.clone(),
+        proposal_id,
+        approved_by_high_rank_id: high_rank_id,
+        last_update_timestamp: Clock::get().unwrap().unix_timestamp
+    };
+
+    if evaluation_scheme_info.owner == &crate::ID {
+
+        let (expected_key, _bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        if expected_key != evaluation_scheme_info.key() { return Err(error!(ErrorCode::IncorrectEvaluationSchemeAccount)) }
+
+        let mut evaluation_scheme_account: Account<EvaluationScheme> = Account::try_from(evaluation_scheme_info)?;
+        evaluation_scheme_account.set_inner(evaluation_scheme);
+        evaluation_scheme_account.exit(&crate::ID)?;
+    } else {
+        create_batch_account(payer, evaluation_scheme_info, system_program, evaluation_scheme_account_space(), seeds, &evaluation_scheme)?;
+    }
+
+    emit! (EvaluationSchemeUpdated {subject_code, proposal_id});
+
+    Ok(())
+}
+
+// Reputation accounts are created the first time an outcome of the user is recorded
+fn record_reputation<'info>(payer: &Signer<'info>, reputation_account_info: &AccountInfo<'info>, system_program: &Program<'info, System>, authority: Pubkey, subject_code: u32, outcome: ReputationOutcome) -> Result<()> {
+
+    let authority_bytes = authority.to_bytes();
+    let seeds: &[&[u8]] = &[b"reputation", authority_bytes.as_ref()];
+
+    if reputation_account_info.owner == &crate::ID {
+
+        let (expected_key, _bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        if expected_key != reputation_account_info.key() { return Err(error!(ErrorCode::IncorrectReputationAccount)) }
+
+        let mut reputation_account: Account<Reputation> = Account::try_from(reputation_account_info)?;
+        reputation_account.record(subject_code, outcome);
+        return reputation_account.exit(&crate::ID);
+    }
+
+    let mut reputation_account = Reputation { authority, ..Default::default() };
+    reputation_account.record(subject_code, outcome);
+    create_batch_account(payer, reputation_account_info, system_program, size_of::<Reputation>() + MAXIMUM_REPUTATION_SUBJECTS * size_of::<SubjectReputation>(), seeds, &reputation_account)
+}
+
+// Reputation accounts that have not been created yet have no score
+fn reputation_score_of(reputation_account_info: &AccountInfo) -> Result<u32> {
+    if reputation_account_info.data_is_empty() { return Ok(0) }
+
+    let reputation_account: Account<Reputation> = Account::try_from(reputation_account_info)?;
+    Ok(reputation_account.reputation_score())
+}
+
+fn mitigate_penalty(penalty: u8, reputation_score: u32) -> u8 {
+    if reputation_score >= REPUTATION_SCORE_TO_MITIGATE_PENALTIES { penalty.saturating_sub(1) } else { penalty }
+}
+
+fn reward_co_authors_user_accounts(co_authors_user_accounts: &[AccountInfo], signed_co_authors: &[CoAuthor]) -> Result<()> {
+
+    // One Student or Professor account (in the 'remaining_accounts') per signed co-author, in the same order they were invited
+    if signed_co_authors.len() != co_authors_user_accounts.len() { return Err(error!(ErrorCode::IncorrectCoAuthorAccounts)) }
+
+    for (co_author, user_account_info) in signed_co_authors.iter().zip(co_authors_user_accounts.iter()) {
+
+        let user_account_seed: &[u8] = match co_author.user_type { ProposalUserType::Student => b"student", ProposalUserType::Professor => b"professor" };
+        let (user_account_key, _bump) = Pubkey::find_program_address(&[user_account_seed, co_author.authority.as_ref()], &crate::ID);
+        if user_account_key != user_account_info.key() { return Err(error!(ErrorCode::IncorrectCoAuthorAccounts)) }
+
+        match co_author.user_type {
+            ProposalUserType::Student => {
+                let mut student_account: Account<Student> = Account::try_from(user_account_info)?;
+                student_account.rewards += 1;
+                student_account.exit(&crate::ID)?;
+            },
+            ProposalUserType::Professor => {
+                let mut professor_account: Account<Professor> = Account::try_from(user_account_info)?;
+                professor_account.rewards += 1;
+                professor_account.exit(&crate::ID)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Rewards are configured in whole tokens, while the mint works with its base units
+fn tokens_to_base_units(tokens: u64, mint: &Mint) -> u64 {
+    tokens.saturating_mul(10_u64.pow(mint.decimals as u32))
+}
+
+fn mint_co_authors_rewards<'info>(token_program: &Program<'info, Token>, mint: &Account<'info, Mint>, mint_authority: &UncheckedAccount<'info>, co_authors_token_accounts: &[AccountInfo<'info>], co_authors: &[CoAuthor], seeds: &[&[&[u8]]], reward: u64) -> Result<u64> {
+
+    // One token account (in the 'remaining_accounts') per signed co-author, in the same order they were invited
+    let signed_co_authors: Vec<&CoAuthor> = co_authors.iter().filter(|x| x.has_signed).collect();
+    if signed_co_authors.len() != co_authors_token_accounts.len() { return Err(error!(ErrorCode::IncorrectCoAuthorAccounts)) }
+
+    let mut co_authors_reward: u64 = 0;
+
+    for (co_author, token_account_info) in signed_co_authors.iter().zip(co_authors_token_accounts.iter()) {
+
+        let token_account: Account<TokenAccount> = Account::try_from(token_account_info)?;
+        if token_account.owner != co_author.authority || token_account.mint != mint.key() { return Err(error!(ErrorCode::IncorrectCoAuthorAccounts)) }
+
+        let co_author_reward = reward * co_author.share as u64 / 100;
+        if co_author_reward > 0 {
+            let cpi_accounts = MintTo { mint: mint.to_account_info(), to: token_account_info.clone(), authority: mint_authority.to_account_info() };
+            token::mint_to(CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, seeds), co_author_reward)?;
+        }
+
+        co_authors_reward += co_author_reward;
+    }
+
+    Ok(co_authors_reward)
+}
+
+// Proposals that missed the quorum are not marked as rejected by any vote --> the end of the votation is taken as the rejection time
+fn proposal_rejection_timestamp(proposal_account: &Proposal) -> Option<i64> {
+
+    if ProposalState::Rejected == proposal_account.state { return Some(proposal_account.rejection_timestamp) }
+
+    let proposal_has_lapsed = proposal_account.state.is_open_to_participation()
+        && !votation_is_open(proposal_account.ending_timestamp)
+        && !proposal_has_reached_minimum_partitipation(proposal_account.supporting_votes, proposal_account.against_votes, proposal_account.expected_votes);
+
+    if proposal_has_lapsed { Some(proposal_account.ending_timestamp) } else { None }
+}
+
+fn validate_proposal_tags(tags: &[String]) -> Result<()> {
+
+    if tags.len() > MAXIMUM_PROPOSAL_TAGS { return Err(error!(ErrorCode::IncorrectProposalTags)) }
+
+    for (position, tag) in tags.iter().enumerate() {
+        if tag.is_empty() || tag.len() > MAXIMUM_TAG_LENGTH { return Err(error!(ErrorCode::IncorrectProposalTags)) }
+        if tags[..position].contains(tag) { return Err(error!(ErrorCode::IncorrectProposalTags)) }
+    }
+
+    Ok(())
+}
+
+fn validate_teaching_project_sections(sections: &[TeachingProjectSection]) -> Result<()> {
+    for (position, section) in sections.iter().enumerate() {
+        if sections[..position].contains(section) { return Err(error!(ErrorCode::IncorrectTeachingProjectSections)) }
+    }
+    Ok(())
+}
+
+// Exactly one reference (with the hash of the section) per section declared by the proposal
+fn validate_section_references(sections: &[TeachingProjectSection], section_references: &[SectionReference]) -> Result<()> {
+
+    if section_references.len() != sections.len() { return Err(error!(ErrorCode::IncorrectSectionReferences)) }
+
+    for (position, section_reference) in section_references.iter().enumerate() {
+        if !sections.contains(&section_reference.section) { return Err(error!(ErrorCode::IncorrectSectionReferences)) }
+        if section_references[..position].iter().any(|x| x.section == section_reference.section) { return Err(error!(ErrorCode::IncorrectSectionReferences)) }
+        if !section_reference.reference.is_valid() || section_reference.reference.sha256.is_none() { return Err(error!(ErrorCode::IncorrectSectionReferences)) }
+    }
+
+    Ok(())
+}
+
+// Components must have a unique name and weights summing up to 100 % --> grades are measured in tenths
+fn validate_evaluation_scheme(scheme: &EvaluationSchemeData) -> Result<()> {
+
+    if scheme.components.is_empty() || scheme.components.len() > MAXIMUM_EVALUATION_COMPONENTS { return Err(error!(ErrorCode::IncorrectEvaluationComponents)) }
+
+    for (position, component) in scheme.components.iter().enumerate() {
+        if component.name.is_empty() || component.name.len() > MAXIMUM_EVALUATION_COMPONENT_NAME_LENGTH { return Err(error!(ErrorCode::IncorrectEvaluationComponents)) }
+        if scheme.components[..position].iter().any(|x| x.name == component.name) { return Err(error!(ErrorCode::IncorrectEvaluationComponents)) }
+        if component.weight == 0 || component.weight > 100 { return Err(error!(ErrorCode::IncorrectEvaluationComponents)) }
+        if component.minimum_grade > MAXIMUM_GRADE { return Err(error!(ErrorCode::IncorrectEvaluationGrades)) }
+    }
+
+    if scheme.components.iter().map(|x| x.weight as u32).sum::<u32>() != 100 { return Err(error!(ErrorCode::EvaluationWeightsDoNotSumTo100)) }
+
+    // Passing the resit must still be possible
+    if scheme.minimum_passing_grade > MAXIMUM_GRADE || scheme.resit_maximum_grade > MAXIMUM_GRADE { return Err(error!(ErrorCode::IncorrectEvaluationGrades)) }
+    if scheme.components.iter().any(|x| x.resit_allowed) && scheme.resit_maximum_grade < scheme.minimum_passing_grade { return Err(error!(ErrorCode::IncorrectEvaluationGrades)) }
+
+    Ok(())
+}
+
+fn evaluation_scheme_space(evaluation_scheme: &Option<EvaluationSchemeData>) -> usize {
+    evaluation_scheme.as_ref().map_or(0, |x| x.components.iter().map(|component| size_of::<EvaluationComponent>() + component.name.as_bytes().len()).sum())
+}
+
+// Evaluation schemes are allocated with room for the biggest one, so that later proposals can always replace them
+fn evaluation_scheme_account_space() -> usize {
+    size_of::<EvaluationScheme>() + MAXIMUM_EVALUATION_COMPONENTS * (size_of::<EvaluationComponent>() + MAXIMUM_EVALUATION_COMPONENT_NAME_LENGTH)
+}
+
+fn proposal_tags_space(tags: &[String]) -> usize {
+    tags.iter().map(|x| 4 + x.as_bytes().len()).sum()
+}
+
+// Approval threshold and HighRank validation of a category --> the original rules apply if the HighRank has not configured it
+fn proposal_category_rules<'info>(proposal_category_config: &AccountInfo<'info>) -> Result<(u8, bool)> {
+    if proposal_category_config.data_is_empty() { return Ok((DEFAULT_APPROVAL_THRESHOLD, true)) }
+
+    let config = Account::<ProposalCategoryConfig>::try_from(proposal_category_config)?;
+    Ok((config.approval_threshold, config.high_rank_validation_required))
+}
+
+fn initialize_comment_account(comment_account: &mut Comment, proposal_account: &mut Proposal, author: Pubkey, author_user_type: ProposalUserType, parent_comment_id: i32, content_reference: Option<ContentReference>, text: String) -> Result<()> {
+
+    if !votation_is_open(proposal_account.ending_timestamp) { return Err(error!(ErrorCode::VotationIsNotOpen)) }
+
+    // A comment carries a short text, a reference to a longer one or both of them
+    if content_reference.is_none() && text.is_empty() { return Err(error!(ErrorCode::IncorrectCommentContent)) }
+    if content_reference.as_ref().is_some_and(|x| !x.is_valid()) { return Err(error!(ErrorCode::IncorrectCommentContent)) }
+    if text.len() > MAXIMUM_COMMENT_LENGTH { return Err(error!(ErrorCode::IncorrectCommentContent)) }
+
+    // Replies must point to an already published comment of the same proposal (-1 for top-level comments)
+    if parent_comment_id < -1 || parent_comment_id >= proposal_account.number_of_comments { return Err(error!(ErrorCode::CommentNotFound)) }
+
+    comment_account.id = proposal_account.number_of_comments;
+    comment_account.proposal_id = proposal_account.id;
+    comment_account.subject_id = proposal_account.subject_id;
+    comment_account.author = author;
+    comment_account.author_user_type = author_user_type;
+    comment_account.parent_comment_id = parent_comment_id;
+    comment_account.content_reference = content_reference;
+    comment_account.text = text;
+    comment_account.publishing_timestamp = Clock::get().unwrap().unix_timestamp;
+
+    proposal_account.number_of_comments += 1;
+
+    emit! (NewCommentPublished {proposal_id: proposal_account.id, comment_id: comment_account.id, parent_comment_id});
+
+    Ok(())
+}
+
+fn initialize_amendment_account(amendment_account: &mut Amendment, proposal_account: &mut Proposal, author: Pubkey, author_user_type: ProposalUserType, amendment_body: Amendment) -> Result<()> {
+
+    if !votation_is_open(proposal_account.ending_timestamp) { return Err(error!(ErrorCode::VotationIsNotOpen)) }
+    validate_proposal_body(&amendment_body.content_reference, &amendment_body.content_sha256, &[])?;
+
+    amendment_account.id = proposal_account.number_of_amendments;
+    amendment_account.proposal_id = proposal_account.id;
+    amendment_account.subject_id = proposal_account.subject_id;
+    amendment_account.author = author;
+    amendment_account.author_user_type = author_user_type;
+    amendment_account.title = amendment_body.title;
+    amendment_account.content = amendment_body.content;
+    amendment_account.content_reference = amendment_body.content_reference;
+    amendment_account.content_sha256 = amendment_body.content_sha256;
+    amendment_account.proposed_over_version = proposal_account.version;
+    amendment_account.publishing_timestamp = Clock::get().unwrap().unix_timestamp;
+    amendment_account.state = AmendmentState::Pending;
+
+    proposal_account.number_of_amendments += 1;
+
+    emit! (NewAmendmentProposed {proposal_id: proposal_account.id, amendment_id: amendment_account.id});
+
+    Ok(())
+}
+
+fn initialize_professor_proposal_account(professor_proposal_account: &mut ProfessorProposal, timestamp_offset: i64) {
+
+    let publishing_timestamp = Clock::get().unwrap().unix_timestamp;
+    let ending_timestamp = publishing_timestamp + (timestamp_offset/2);
+ 
+    professor_proposal_account.publishing_timestamp = publishing_timestamp;
+    professor_proposal_account.ending_timestamp = ending_timestamp;
+    professor_proposal_account.state = ProfessorProposalState::Pending;
+    professor_proposal_account.assigned_professor_id = -1;
+ 
+ }
+ 
+
+// Proposals published with a reading period stay under discussion until the votation starts
+fn schedule_proposal_votation(proposal_account: &mut Proposal, voting_starts_at: Option<i64>) -> Result<()> {
+
+    proposal_account.publishing_timestamp = Clock::get().unwrap().unix_timestamp;
+    proposal_account.voting_starts_at = proposal_account.publishing_timestamp;
+    proposal_account.state = ProposalState::VotationInProgress;
+
+    if let Some(starting_timestamp) = voting_starts_at {
+        if starting_timestamp < proposal_account.publishing_timestamp || starting_timestamp - proposal_account.publishing_timestamp > MAXIMUM_DISCUSSION_PERIOD { return Err(error!(ErrorCode::IncorrectVotingStartTime)) }
+        if starting_timestamp > proposal_account.publishing_timestamp {
+            proposal_account.voting_starts_at = starting_timestamp;
+            proposal_account.state = ProposalState::Discussion;
+        }
+    }
+
+    proposal_account.ending_timestamp = proposal_account.voting_starts_at.checked_add(ENDING_TIMESTAMP_OFFSET).ok_or(error!(ErrorCode::IncorrectVotingStartTime))?;
+
+    Ok(())
+}
+
+fn start_scheduled_votation(proposal_account: &mut Proposal) -> Result<()> {
+    if ProposalState::Discussion == proposal_account.state {
+        if Clock::get().unwrap().unix_timestamp < proposal_account.voting_starts_at { return Err(error!(ErrorCode::VotationHasNotStarted)) }
+        proposal_account.state = ProposalState::VotationInProgress;
+    }
+    Ok(())
+}
+
+fn votation_is_open (ending_timestamp_of_votation: i64) -> bool {
+    if Clock::get().unwrap().unix_timestamp < ending_timestamp_of_votation {true} else {false}
+}
+
+
+fn proposal_has_reached_minimum_partitipation(supporting_votes: u32, against_votes: u32, expected_votes: u32) -> bool {
+    return (supporting_votes + against_votes) >= expected_votes
+}
+
+fn proposal_has_reached_maximum_participation (supporting_votes: u32, against_votes: u32, max_participation: u32) -> bool {
+   return (supporting_votes + against_votes) >= max_participation as u32
+}
+
+fn proposal_has_reached_agreement(supporting_votes: u32, against_votes: u32, approval_threshold: u8) -> bool {
+    let total_votes: u64 = supporting_votes as u64 + against_votes as u64;
+
+    // The default threshold keeps the original two thirds rule (66% would already accept 33 out of 50 votes)
+    if approval_threshold == DEFAULT_APPROVAL_THRESHOLD { return (supporting_votes as u64) * 3 >= total_votes * 2 }
+
+    (supporting_votes as u64) * 100 >= (approval_threshold as u64) * total_votes
+}
+
+
+// Returns the penalty (bounded by the cap of the policy) along with the delay of the professor
+fn evaluating_professor_penalty (professor_proposal_account: &ProfessorProposal, penalty_policy: &PenaltyPolicy, current_timestamp: i64) -> (u8, i64) {
+    let delay = current_timestamp.saturating_sub(professor_proposal_account.ending_timestamp);
+    let penalized_delay = delay.saturating_sub(penalty_policy.grace_period);
+    if penalized_delay <= 0 { return (0, 0) }
+
+    // Every interval started is penalized
+    let intervals = ((penalized_delay - 1) / penalty_policy.penalty_interval + 1) as u64;
+    let penalty = intervals.saturating_mul(penalty_policy.penalty_per_interval as u64).min(penalty_policy.maximum_penalty as u64) as u8;
+
+    (penalty, delay)
+}
+
+// The config is seeds-checked so the defaults only apply while the HighRank has not configured it
+fn professor_reward_rules<'info>(professor_reward_config: &AccountInfo<'info>) -> Result<(u8, u8, i64)> {
+    if professor_reward_config.data_is_empty() { return Ok((DEFAULT_PROFESSOR_REWARD_TOKENS, DEFAULT_EARLY_DELIVERY_BONUS_TOKENS, DEFAULT_EARLY_DELIVERY_PERIOD)) }
+
+    let config = Account::<ProfessorRewardConfig>::try_from(professor_reward_config)?;
+    Ok((config.reward_tokens, config.early_delivery_bonus_tokens, config.early_delivery_period))
+}
+
+// The penalized professor signs the update --> the policy is seeds-checked so the defaults only apply while the HighRank has not configured it
+fn penalty_policy_rules<'info>(penalty_policy: &AccountInfo<'info>) -> Result<PenaltyPolicy> {
+    if penalty_policy.data_is_empty() { return Ok(default_penalty_policy()) }
+
+    let policy = Account::<PenaltyPolicy>::try_from(penalty_policy)?;
+    Ok(PenaltyPolicy::clone(&policy))
+}
+
+fn default_penalty_policy() -> PenaltyPolicy {
+    PenaltyPolicy {
+        grace_period: DEFAULT_PENALTY_GRACE_PERIOD,
+        penalty_interval: DEFAULT_PENALTY_INTERVAL,
+        penalty_per_interval: DEFAULT_PENALTY_PER_INTERVAL,
+        maximum_penalty: DEFAULT_MAXIMUM_PENALTY,
+        decay_per_term: DEFAULT_PENALTY_DECAY_PER_TERM,
+        term_duration: DEFAULT_TERM_DURATION,
+        last_update_timestamp: 0,
+        updated_by_high_rank_id: -1
+    }
+}
+
+// Punishments are forgiven little by little --> 'decay_per_term' for every term elapsed since the last decay
+fn apply_penalty_decay(professor_account: &mut Professor, penalty_policy: &PenaltyPolicy, current_timestamp: i64) {
+    if professor_account.last_penalty_decay_timestamp == 0 {
+        professor_account.last_penalty_decay_timestamp = current_timestamp;
+        return;
+    }
+
+    let terms = current_timestamp.saturating_sub(professor_account.last_penalty_decay_timestamp) / penalty_policy.term_duration;
+    if terms > 0 {
+        let decay = (terms as u64).saturating_mul(penalty_policy.decay_per_term as u64).min(u8::MAX as u64) as u8;
+        professor_account.punishments = professor_account.punishments.saturating_sub(decay);
+        professor_account.last_penalty_decay_timestamp = terms.checked_mul(penalty_policy.term_duration)
+            .and_then(|elapsed_terms| professor_account.last_penalty_decay_timestamp.checked_add(elapsed_terms))
+            .unwrap_or(current_timestamp);
+    }
+}
+
+
+// ISO 639-1 codes ("es") and BCP 47 tags ("en-GB") are accepted
+fn is_valid_language_code(language_code: &str) -> bool {
+    language_code.len() >= 2 && language_code.len() <= 10 && language_code.chars().all(|x| x.is_ascii_alphanumeric() || x == '-')
+}
+
+fn evaluate_if_user_belong_to_subject(subjects: Vec<u32>, subject_code:u32) -> bool {
+
+    let mut user_belong: bool = false;
+ 
+    for subject in subjects {
+         if subject_code == subject {
+             user_belong = true;
+         }
+     }
+ 
+    return user_belong;
+ }
+ 
+
+                          // --------- ACCOUNTS DATA STRUCTURES ('CTX' PARAM IN 'teaching_project_handler' MOD FUNCTIONS) ----- 
+
+#[derive(Accounts)]
+#[instruction(_user_type_code: String)]
+pub struct InitializeSystem <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<SystemInitialization>() + (8 as usize),
+        seeds = [b"systemInitialization"],
+        bump,
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(
+        has_one = authority,
+        seeds=[b"highRank", authority.key().as_ref()],
+        bump,
+        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank_account: Account<'info, HighRank>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SOLANA_ACCOUNT_MAX_SIZE,
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"degreeIdHandler"],
+        bump
+    )]
+    pub degree_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"facultyIdHandler"],
+        bump
+    )]
+    pub faculty_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"specialtyIdHandler"],
+        bump
+    )]
+    pub specialty_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"subjectIdHandler"],
+        bump
+    )]
+    pub subject_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<Treasury>() + 8,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: 'mint_authority' is an UncheckedAccount since it's just a PDA that references the authority of any HighRank over the tokens
+    #[account(
+        mut, 
+        seeds = [b"mint_authority",  mint.key().as_ref(), _user_type_code.as_bytes().as_ref() ],
+        bump,
+        constraint = digest(_user_type_code) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub mint_authority_account: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        mint::decimals = 1,
+        mint::authority = mint_authority_account,
+        seeds = [b"creditToken"],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+}
+
+
+#[derive(Accounts)]
+#[instruction (user_type_code: String)]
+pub struct CreateHighRank<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"highRankIdHandler"],
+        bump
+    )]
+    pub high_rank_id_handler: Account<'info,IdHandler>,
+
+    #[account(init, 
+        payer=authority, 
+        space = size_of::<HighRank>() + 12 + 100, 
+        seeds=[b"highRank", authority.key().as_ref()],
+        bump,
+        constraint = digest(user_type_code.clone()) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c")
+    ]
+    pub high_rank_account: Account<'info, HighRank>,
+
+    pub system_program: Program<'info,System>,
+
+}
+
+#[derive(Accounts)]
+#[instruction (user_type_code: String)]
+pub struct CreateProfessor<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"professorIdHandler"],
+        bump
+    )]
+    pub professor_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        constraint = high_rank_id_handler.smaller_id_available > 1  @ ErrorCode::NotAnyHighRankInitializated
+    )]
+    pub high_rank_id_handler: Account<'info,IdHandler>,
+
+    #[account(init, 
+        payer=authority, 
+        space = size_of::<Professor>() + 12 + 60 + 100 + 100, 
+        seeds=[b"professor", authority.key().as_ref()],
+        bump,
+        constraint = digest(user_type_code) == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9")
+    ]
+    pub professor_account: Account<'info, Professor>,
+
+    #[account(
+        mut,
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+
+    #[account()]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed, 
+        payer = authority, 
+        associated_token::mint = mint,
+        associated_token::authority = authority)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>, 
+}
+
+#[derive(Accounts)]
+#[instruction (user_type_code: String)]
+pub struct CreateStudent<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"studentIdHandler"],
+        bump
+    )]
+    pub student_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        constraint = high_rank_id_handler.smaller_id_available > 1  @ ErrorCode::NotAnyHighRankInitializated
+    )]
+    pub high_rank_id_handler: Account<'info,IdHandler>,
+
+    #[account(init, 
+        payer=authority, 
+        space = size_of::<Student>() + 12 + 100 + 100, 
+        seeds=[b"student", authority.key().as_ref()],
+        bump,
+        constraint = digest(user_type_code) == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69")
+    ]
+    pub student_account: Account<'info, Student>,
+
+    #[account(
+        mut,
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+
+    #[account()]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed, 
+        payer = authority, 
+        associated_token::mint = mint,
+        associated_token::authority = authority)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>
+}
+
+#[derive(Accounts)]
+#[instruction (name: String)]
+pub struct CreateFaculty<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(mut)]
+    pub faculty_id_handler: Account<'info,IdHandler>,
+
+    #[account(has_one = authority)] 
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(init, 
+        payer=authority, 
+        space = size_of::<Faculty>() + name.as_bytes().len() + 4, 
+        seeds=[b"faculty", faculty_id_handler.next_id().to_le_bytes().as_ref()], 
+        bump,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
+        constraint = name.len() <= 50
+    )]
+    pub faculty_account: Account<'info, Faculty>,
+
+    pub system_program: Program<'info,System>
+}
+
+#[derive(Accounts)]
+#[instruction (name: String, faculty_id:i32)]
+pub struct CreateDegree<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(mut)]
+    pub degree_id_handler: Account<'info,IdHandler>,
+
+    #[account(mut)]
+    pub faculty_id_handler: Account<'info, IdHandler>,
+
+    #[account(has_one = authority)] 
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(init, 
+        payer=authority, 
+        space = size_of::<Degree>() + name.as_bytes().len() + 4, 
+        seeds=[b"degree", degree_id_handler.next_id().to_le_bytes().as_ref()], 
+        bump,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
+        constraint = name.len() <= 500,
+        constraint = (faculty_id >= 1 && faculty_id < faculty_id_handler.smaller_id_available)
+    )]
+    pub degree_account: Account<'info, Degree>,
+
+    pub system_program: Program<'info,System>
+}
+
+#[derive(Accounts)]
+#[instruction (name: String, degree_id:i32)]
+pub struct CreateSpecialty <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(mut)]
+    pub specialty_id_handler: Account<'info, IdHandler>,
+
+    #[account(mut)]
+    pub degree_id_handler: Account<'info, IdHandler>,
+
+    #[account(has_one = authority)] 
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(init, 
+        payer=authority, 
+        space = size_of::<Specialty>() + name.as_bytes().len() + 4, 
+        seeds=[b"specialty", specialty_id_handler.next_id().to_le_bytes().as_ref()], 
+        bump,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
+        constraint = name.len() <= 500,
+        constraint = (degree_id >= 1 && degree_id < degree_id_handler.smaller_id_available)
+    )]
+    pub specialty_account: Account<'info, Specialty>,
+
+    pub system_program: Program<'info,System>
+}
+
+#[derive(Accounts)]
+#[instruction (name: String, degree_id: i32, specialty_id: i32, course: SubjectCourse, code: u32, teaching_project_reference: ContentReference, prerequisites: Vec<u32>, corequisites: Vec<u32>, sections: Vec<SectionReference>)]
+pub struct CreateSubject<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+    
+    #[account(mut, has_one = authority)]      // Se comprueba que la cuenta de "Alto cargo" pasada pertenece a quien firma la transacción (autenticación)
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(mut)]
+    pub subject_id_handler: Account<'info,IdHandler>,
+
+    #[account()]
+    pub degree_id_handler: Account<'info,IdHandler>,
+
+    #[account()]
+    pub specialty_id_handler: Account<'info,IdHandler>,
+
+
+    #[account(init, 
+        payer=authority, 
+        space = size_of::<Subject>() + name.as_bytes().len() + teaching_project_reference.space() + (prerequisites.len() + corequisites.len()) * size_of::<u32>() + section_references_space(&sections), 
+        seeds=[b"subject", subject_id_handler.next_id().to_le_bytes().as_ref()], 
+        bump,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
+        constraint = (degree_id >= 1) && (degree_id < degree_id_handler.smaller_id_available),
+        constraint = (specialty_id == -1) || (specialty_id >= 1 && specialty_id < specialty_id_handler.smaller_id_available),
+        constraint = teaching_project_reference.is_valid() @ ErrorCode::IncorrectTeachingProjectReference
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"proposalIdHandler", code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<IdHandler>() + 140,
+        seeds = [b"professorProposalIdHandler", code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub professor_proposal_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation_account: Account<'info, CodeIdSubjectRelation>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SOLANA_ACCOUNT_MAX_SIZE,
+        seeds = [b"subjectDependencyGraph"],
+        bump
+    )]
+    pub subject_dependency_graph: Account<'info, SubjectDependencyGraph>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CreateDegreesBatch<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        mut,
+        seeds = [b"degreeIdHandler"],
+        bump
+    )]
+    pub degree_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        seeds = [b"facultyIdHandler"],
+        bump
+    )]
+    pub faculty_id_handler: Account<'info, IdHandler>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CreateSpecialtiesBatch<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        mut,
+        seeds = [b"specialtyIdHandler"],
+        bump
+    )]
+    pub specialty_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        seeds = [b"degreeIdHandler"],
+        bump
+    )]
+    pub degree_id_handler: Account<'info, IdHandler>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CreateSubjectsBatch<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        mut,
+        seeds = [b"subjectIdHandler"],
+        bump
+    )]
+    pub subject_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        seeds = [b"degreeIdHandler"],
+        bump
+    )]
+    pub degree_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        seeds = [b"specialtyIdHandler"],
+        bump
+    )]
+    pub specialty_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation_account: Account<'info, CodeIdSubjectRelation>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SOLANA_ACCOUNT_MAX_SIZE,
+        seeds = [b"subjectDependencyGraph"],
+        bump
+    )]
+    pub subject_dependency_graph: Account<'info, SubjectDependencyGraph>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (degree_id: i32, specialty_id: i32)]
+pub struct CrossListSubject<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        seeds = [b"degreeIdHandler"],
+        bump
+    )]
+    pub degree_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        seeds = [b"specialtyIdHandler"],
+        bump
+    )]
+    pub specialty_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = (degree_id >= 1) && (degree_id < degree_id_handler.smaller_id_available) @ ErrorCode::IncorrectDegreeId,
+        constraint = (specialty_id == -1) || (specialty_id >= 1 && specialty_id < specialty_id_handler.smaller_id_available) @ ErrorCode::IncorrectSpecialtyId,
+        realloc = subject_account_space(&subject_account, size_of::<CrossListing>()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct RemoveSubjectCrossListing<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        mut,
+        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
+}
+
+#[derive(Accounts)]
+#[instruction (ects_credits: u16, semester: SubjectSemester, teaching_language: String, modality: SubjectModality, coordinator_professor_id: i32, course_number: u8)]
+pub struct UpdateSubjectMetadata<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        seeds = [b"professorIdHandler"],
+        bump
+    )]
+    pub professor_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    // Only submitted when a coordinator is assigned ('coordinator_professor_id' != -1)
+    #[account(
+        constraint = coordinator_professor.id == coordinator_professor_id @ ErrorCode::IncorrectProfessorId,
+        constraint = evaluate_if_user_belong_to_subject(coordinator_professor.subjects.clone(), subject_account.code) @ ErrorCode::UserDoesNotBelongToTheSubject
+    )]
+    pub coordinator_professor: Option<Account<'info, Professor>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<SubjectMetadata>() + 8 + SUBJECT_METADATA_RESERVED_SPACE,
+        seeds = [b"subjectMetadata", subject_account.id.to_le_bytes().as_ref()],
+        bump,
+        constraint = ects_credits > 0 && ects_credits <= MAXIMUM_ECTS_CREDITS @ ErrorCode::IncorrectEctsCredits,
+        constraint = course_number >= 1 @ ErrorCode::IncorrectCourseNumber,
+        constraint = (coordinator_professor_id == -1) || (coordinator_professor_id >= 1 && coordinator_professor_id < professor_id_handler.smaller_id_available) @ ErrorCode::IncorrectProfessorId,
+        constraint = (coordinator_professor_id == -1) == coordinator_professor.is_none() @ ErrorCode::IncorrectProfessorId
+    )]
+    pub subject_metadata_account: Account<'info, SubjectMetadata>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CreateEvaluationScheme<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    // Once created, the scheme can only be replaced through an accepted proposal
+    #[account(
+        init,
+        payer = authority,
+        space = evaluation_scheme_account_space(),
+        seeds = [b"evaluationScheme", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub evaluation_scheme: Account<'info, EvaluationScheme>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (category: ProposalCategory, approval_threshold: u8, high_rank_validation_required: bool)]
+pub struct UpdateProposalCategoryConfig<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<ProposalCategoryConfig>() + 8,
+        seeds = [b"proposalCategoryConfig", (category as u8).to_le_bytes().as_ref()],
+        bump,
+        constraint = approval_threshold > 50 && approval_threshold <= 100 @ ErrorCode::IncorrectApprovalThreshold
+    )]
+    pub proposal_category_config: Account<'info, ProposalCategoryConfig>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (deposit_lamports: u64, maximum_open_proposals_per_user: u8)]
+pub struct UpdateProposalDepositConfig<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<ProposalDepositConfig>() + 8,
+        seeds = [b"proposalDepositConfig"],
+        bump,
+        constraint = maximum_open_proposals_per_user >= 1 @ ErrorCode::IncorrectMaximumOpenProposals
+    )]
+    pub proposal_deposit_config: Account<'info, ProposalDepositConfig>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (grace_period: i64, penalty_interval: i64, penalty_per_interval: u8, maximum_penalty: u8, decay_per_term: u8, term_duration: i64)]
+pub struct UpdatePenaltyPolicy<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<PenaltyPolicy>() + 8,
+        seeds = [b"penaltyPolicy"],
+        bump,
+        constraint = grace_period >= 0 && penalty_interval > 0 && term_duration > 0 @ ErrorCode::IncorrectPenaltyPolicy,
+        constraint = grace_period <= MAXIMUM_PENALTY_POLICY_PERIOD && penalty_interval <= MAXIMUM_PENALTY_POLICY_PERIOD && term_duration <= MAXIMUM_PENALTY_POLICY_PERIOD @ ErrorCode::IncorrectPenaltyPolicy,
+        constraint = penalty_per_interval <= maximum_penalty @ ErrorCode::IncorrectPenaltyPolicy
+    )]
+    pub penalty_policy: Account<'info, PenaltyPolicy>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (reward_tokens: u8, early_delivery_bonus_tokens: u8, early_delivery_period: i64)]
+pub struct UpdateProfessorRewardConfig<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<ProfessorRewardConfig>() + 8,
+        seeds = [b"professorRewardConfig"],
+        bump,
+        constraint = early_delivery_period >= 0 @ ErrorCode::IncorrectProfessorRewardConfig
+    )]
+    pub professor_reward_config: Account<'info, ProfessorRewardConfig>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (void_reference: ContentReference)]
+pub struct VoidPenalty<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        mut,
+        constraint = professor_account.id == penalty_record.professor_id
+    )]
+    pub professor_account: Account<'info, Professor>,
+
+    #[account(
+        mut,
+        constraint = !penalty_record.voided @ ErrorCode::PenaltyIsAlreadyVoided,
+        constraint = void_reference.is_valid() @ ErrorCode::IncorrectPenaltyVoidReference
+    )]
+    pub penalty_record: Account<'info, PenaltyRecord>
+}
+
+#[derive(Accounts)]
+#[instruction (cooldown: i64)]
+pub struct UpdateDuplicateProposalCooldown<'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub high_rank: Account<'info, HighRank>,
+
+    #[account(
+        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<ProposalIndex>() + PROPOSAL_INDEX_CAPACITY * (size_of::<ProposalIndexEntry>() + 64 - 20),
+        seeds = [b"proposalIndex", subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = cooldown >= 0 @ ErrorCode::IncorrectDuplicateProposalCooldown
+    )]
+    pub proposal_index: Account<'info, ProposalIndex>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (title: String, content: String, content_reference: ContentReference, content_sha256: String, attachments: Vec<ProposalAttachment>, category: ProposalCategory, tags: Vec<String>, voting_starts_at: Option<i64>, sections: Vec<TeachingProjectSection>, evaluation_scheme: Option<EvaluationSchemeData>)]
+pub struct CreateProposalByStudent <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+    
+    #[account(mut, has_one = authority)]      
+    pub student_creator: Account<'info, Student>,
+
+    #[account(
+        mut,
+        seeds = [b"proposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"professorProposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub professor_proposal_id_handler: Account<'info, IdHandler>,
+    
+    #[account()]
+    pub subject_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        init, 
+        payer=authority, 
+        space = size_of::<Proposal>() + proposal_body_space(&title, &content, &content_reference, &content_sha256, &attachments) + proposal_tags_space(&tags) + sections.len() * size_of::<TeachingProjectSection>() + evaluation_scheme_space(&evaluation_scheme) + 40 + 64 + subject_dependency_graph.dependent_subjects(subject_account.code).len() * size_of::<u32>(), 
+        seeds=[b"proposal", proposal_id_handler.next_id().to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        bump,
+        constraint = student_creator.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69",
+        constraint = title.len() <= 100 && content.len() <= MAXIMUM_PROPOSAL_SUMMARY_LENGTH
+    )]
+    pub proposal_account: Account<'info, Proposal>,
+
+    #[account(init,
+        payer = authority,
+        space = size_of::<ProfessorProposal>() + title.len() + 20 + 60,
+        seeds = [b"professorProposal", professor_proposal_id_handler.next_id().to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        bump,
+        realloc = subject_account_space(&subject_account, size_of::<i32>()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    #[account(
+        mut,
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+
+    #[account(
+        seeds = [b"subjectDependencyGraph"],
+        bump
+    )]
+    pub subject_dependency_graph: Account<'info, SubjectDependencyGraph>,
+
+    /// CHECK: config of the category, deserialized only if the HighRank has already created it
+    #[account(
+        seeds = [b"proposalCategoryConfig", (category as u8).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_category_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<ProposalDeposit>() + 8,
+        seeds = [b"proposalDeposit", proposal_id_handler.next_id().to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_deposit: Account<'info, ProposalDeposit>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<OpenProposalsCounter>() + 8,
+        seeds = [b"openProposals", authority.key().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub open_proposals_counter: Account<'info, OpenProposalsCounter>,
+
+    /// CHECK: deposit config, deserialized only if the HighRank has already created it
+    #[account(
+        seeds = [b"proposalDepositConfig"],
+        bump
+    )]
+    pub proposal_deposit_config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<ProposalIndex>() + PROPOSAL_INDEX_CAPACITY * (size_of::<ProposalIndexEntry>() + 64 - 20),
+        seeds = [b"proposalIndex", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_index: Account<'info, ProposalIndex>,
+
+    pub system_program: Program<'info, System>
+
+}
+
+#[derive(Accounts)]
+#[instruction (title: String, content: String, content_reference: ContentReference, content_sha256: String, attachments: Vec<ProposalAttachment>, category: ProposalCategory, tags: Vec<String>, voting_starts_at: Option<i64>, sections: Vec<TeachingProjectSection>, evaluation_scheme: Option<EvaluationSchemeData>)]
+pub struct CreateProposalByProfessor <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"systemInitialization"],
+        bump,
+        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+    )]
+    pub initialization_system_account: Account<'info, SystemInitialization>,
+    
+    #[account(mut, has_one = authority)]      
+    pub professor_creator: Account<'info, Professor>,
+
+     #[account(
+        mut,
+        seeds = [b"proposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"professorProposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub professor_proposal_id_handler: Account<'info, IdHandler>,
+    
+    #[account()]
+    pub subject_id_handler: Account<'info,IdHandler>,
+
+    #[account(
+        init, 
+        payer=authority, 
+        space = size_of::<Proposal>() + proposal_body_space(&title, &content, &content_reference, &content_sha256, &attachments) + proposal_tags_space(&tags) + sections.len() * size_of::<TeachingProjectSection>() + evaluation_scheme_space(&evaluation_scheme) + 40 + 64 + subject_dependency_graph.dependent_subjects(subject_account.code).len() * size_of::<u32>(), 
+        seeds=[b"proposal", proposal_id_handler.next_id().to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        bump,
+        constraint = professor_creator.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
+        constraint = title.len() <= 100 && content.len() <= MAXIMUM_PROPOSAL_SUMMARY_LENGTH
+    )]
+    pub proposal_account: Account<'info, Proposal>,
+
+    #[account(init,
+        payer = authority,
+        space = size_of::<ProfessorProposal>() + title.len() + 20,
+        seeds = [b"professorProposal", professor_proposal_id_handler.next_id().to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+
+
+    #[account(
+        mut,
+        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        bump,
+        realloc = subject_account_space(&subject_account, size_of::<i32>()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    #[account(
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+
+    #[account(
+        seeds = [b"subjectDependencyGraph"],
+        bump
+    )]
+    pub subject_dependency_graph: Account<'info, SubjectDependencyGraph>,
+
+    /// CHECK: config of the category, deserialized only if the HighRank has already created it
+    #[account(
+        seeds = [b"proposalCategoryConfig", (category as u8).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_category_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<ProposalDeposit>() + 8,
+        seeds = [b"proposalDeposit", proposal_id_handler.next_id().to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_deposit: Account<'info, ProposalDeposit>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<OpenProposalsCounter>() + 8,
+        seeds = [b"openProposals", authority.key().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub open_proposals_counter: Account<'info, OpenProposalsCounter>,
+
+    /// CHECK: deposit config, deserialized only if the HighRank has already created it
+    #[account(
+        seeds = [b"proposalDepositConfig"],
+        bump
+    )]
+    pub proposal_deposit_config: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<ProposalIndex>() + PROPOSAL_INDEX_CAPACITY * (size_of::<ProposalIndexEntry>() + 64 - 20),
+        seeds = [b"proposalIndex", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_index: Account<'info, ProposalIndex>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct VoteProposalByStudent <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut, has_one = authority)]      
+    pub voting_student: Account<'info, Student>,
+    
+    #[account()]
+    pub subject_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        bump,
+        constraint = voting_student.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69",
+        constraint = proposal_account.state.is_open_to_participation() @ErrorCode::VotationIsNotOpen,                                     
+        realloc = proposal_account_space(&proposal_account, size_of::<i32>()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub proposal_account: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump,
+        constraint =  proposal_account.subject_id < subject_id_handler.smaller_id_available
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    #[account(mut)]
+    pub professor_proposal_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
+        constraint = professor_proposal_account.id < professor_proposal_id_handler.smaller_id_available
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct VoteProposalByProfessor <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut, has_one = authority)]      
+    pub voting_professor: Account<'info, Professor>,
+    
+    #[account()]
+    pub subject_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        bump,
+        constraint = voting_professor.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen,
+        realloc = proposal_account_space(&proposal_account, size_of::<i32>()),
+        realloc::payer = authority,
+        realloc::zero = false                                           
+    )]
+    pub proposal_account: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal_account.subject_id < subject_id_handler.smaller_id_available
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    #[account(mut)]
+    pub professor_proposal_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
+        constraint = professor_proposal_account.id < professor_proposal_id_handler.smaller_id_available
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (teaching_project_reference: ContentReference, section_references: Vec<SectionReference>)]
+pub struct UpdateProposalByProfessor <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut, has_one = authority)]      
+    pub professor_account: Account<'info, Professor>,
+    
+    #[account(
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        bump,
+        constraint = professor_account.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state  @  ErrorCode::VotationIsNotWaitingForTeacher                                           
+    )]
+    pub proposal_account: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        constraint = teaching_project_reference.is_valid() @ ErrorCode::IncorrectTeachingProjectReference,
+        realloc = professor_proposal_account_space(&professor_proposal_account, section_references_space(&section_references)),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump,
+        realloc = subject_account_space_for_teaching_project(&subject_account, &teaching_project_reference, &section_references),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub subject_account: Account<'info, Subject>,
 
-        let specialty_id_generator_account = &mut *ctx.accounts.specialty_id_handler;
-        specialty_id_generator_account.smaller_id_available = 1;
+    /// CHECK: penalty policy, deserialized only if the HighRank has already created it
+    #[account(
+        seeds = [b"penaltyPolicy"],
+        bump
+    )]
+    pub penalty_policy: UncheckedAccount<'info>,
 
-        let subject_id_generator_account = &mut *ctx.accounts.subject_id_handler;
-        subject_id_generator_account.smaller_id_available = 1;
+    /// CHECK: Only created if the professor is penalized (its seeds are checked by 'create_batch_account')
+    #[account(mut)]
+    pub penalty_record: UncheckedAccount<'info>,
 
-        Ok(true)
+    /// CHECK: Created on the first outcome of the professor (only updated if the teaching project is accepted without HighRank validation)
+    #[account(
+        mut,
+        seeds = [b"reputation", professor_account.authority.as_ref()],
+        bump
+    )]
+    pub professor_reputation: UncheckedAccount<'info>,
 
-    }
+    /// CHECK: Only created if the teaching project is accepted without HighRank validation (its seeds are checked by 'create_batch_account')
+    #[account(mut)]
+    pub teaching_project_version: UncheckedAccount<'info>,
 
+    /// CHECK: Only written if the proposal carries a new evaluation scheme (its seeds are checked by 'record_evaluation_scheme')
+    #[account(mut)]
+    pub evaluation_scheme: UncheckedAccount<'info>,
 
-    
+    pub system_program: Program<'info, System>
 }
 
-                                                       // ------- AUX FUNCTIONS ------------- //
+#[derive(Accounts)]
+pub struct AssignProfessorProposal <'info> {
 
-fn general_id_generator (id_handler_account: &mut Account<IdHandler>) ->  i32 {
-    let id: i32 = id_handler_account.smaller_id_available;
-    id_handler_account.smaller_id_available = id_handler_account.smaller_id_available + 1;
-    return id;
-}
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-pub fn update_internally_initializated_id_generator(id_handler_account: &mut IdHandler) {
+    #[account(
+        has_one = authority,
+        constraint = subject_metadata_account.coordinator_professor_id == coordinator_account.id @ ErrorCode::UserIsNotTheSubjectCoordinator
+    )]
+    pub coordinator_account: Account<'info, Professor>,
 
-    if id_handler_account.smaller_id_available == 0 { id_handler_account.smaller_id_available += 1; }
-}
+    #[account(
+        constraint = evaluate_if_user_belong_to_subject(assigned_professor.subjects.clone(), subject_account.code) @ ErrorCode::UserDoesNotBelongToTheSubject
+    )]
+    pub assigned_professor: Account<'info, Professor>,
 
+    #[account(
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state @ ErrorCode::VotationIsNotWaitingForTeacher
+    )]
+    pub proposal_account: Account<'info, Proposal>,
 
-fn initialize_professor_proposal_account(professor_proposal_account: &mut ProfessorProposal, timestamp_offset: i64) {
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
 
-    let publishing_timestamp = Clock::get().unwrap().unix_timestamp;
-    let ending_timestamp = publishing_timestamp + (timestamp_offset/2);
- 
-    professor_proposal_account.publishing_timestamp = publishing_timestamp;
-    professor_proposal_account.ending_timestamp = ending_timestamp;
-    professor_proposal_account.state = ProfessorProposalState::Pending;
- 
- }
- 
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
 
-fn votation_is_open (ending_timestamp_of_votation: i64) -> bool {
-    if Clock::get().unwrap().unix_timestamp < ending_timestamp_of_votation {true} else {false}
+    #[account(
+        seeds = [b"subjectMetadata", subject_account.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_metadata_account: Account<'info, SubjectMetadata>
 }
 
+#[derive(Accounts)]
+pub struct AssignProfessorProposalByRoundRobin <'info> {
 
-fn proposal_has_reached_minimum_partitipation(supporting_votes: u32, against_votes: u32, expected_votes: u32) -> bool {
-    return (supporting_votes + against_votes) >= expected_votes
-}
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-fn proposal_has_reached_maximum_participation (supporting_votes: u32, against_votes: u32, max_participation: u32) -> bool {
-   return (supporting_votes + against_votes) >= max_participation as u32
-}
+    #[account(
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state @ ErrorCode::VotationIsNotWaitingForTeacher
+    )]
+    pub proposal_account: Account<'info, Proposal>,
 
-fn proposal_has_reached_agreement(supporting_votes: u32, against_votes: u32) -> bool {
-    let total_votes: f32 = supporting_votes as f32 + against_votes as f32;
-    return (supporting_votes as f32) / (total_votes) as f32 >= (2_f32/3_f32 as f32) 
-}
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        constraint = professor_proposal_account.assigned_professor_id == -1 @ ErrorCode::ProfessorIsAlreadyAssigned
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
 
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
 
-fn evaluating_professor_penalty (professor_proposal_account: &mut ProfessorProposal, timestamp_offset: i64) -> u8 {
-    let current_timestamp = Clock::get().unwrap().unix_timestamp;
-    let mut penalty_counter: u8 = 0;
-    while penalty_counter as i64 * (timestamp_offset/2) + current_timestamp > professor_proposal_account.ending_timestamp {
-        penalty_counter = penalty_counter + 1;
-    }
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = size_of::<ProfessorAssignment>() + 8,
+        seeds = [b"professorAssignment", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub professor_assignment_account: Account<'info, ProfessorAssignment>,
 
-    return penalty_counter;
-        
+    #[account(
+        seeds = [b"codeIdSubjectRelation"],
+        bump
+    )]
+    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+
+    pub system_program: Program<'info, System>
 }
 
+#[derive(Accounts)]
+#[instruction (reference: ContentReference)]
+pub struct SubmitDraftRevision <'info> {
 
-fn evaluate_if_user_belong_to_subject(subjects: Vec<u32>, subject_code:u32) -> bool {
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-    let mut user_belong: bool = false;
- 
-    for subject in subjects {
-         if subject_code == subject {
-             user_belong = true;
-         }
-     }
- 
-    return user_belong;
- }
- 
+    #[account(has_one = authority)]
+    pub professor_account: Account<'info, Professor>,
 
-                          // --------- ACCOUNTS DATA STRUCTURES ('CTX' PARAM IN 'teaching_project_handler' MOD FUNCTIONS) ----- 
+    #[account(
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state @ ErrorCode::VotationIsNotWaitingForTeacher
+    )]
+    pub proposal_account: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        constraint = reference.is_valid() @ ErrorCode::IncorrectTeachingProjectReference,
+        realloc = professor_proposal_account_space(&professor_proposal_account, size_of::<DraftRevision>() + reference.space()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
+
+    pub system_program: Program<'info, System>
+}
 
 #[derive(Accounts)]
-#[instruction(_user_type_code: String)]
-pub struct InitializeSystem <'info> {
+pub struct SignOffDraft <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(has_one = authority)]
+    pub professor_account: Account<'info, Professor>,
+
     #[account(
-        init,
-        payer = authority,
-        space = size_of::<SystemInitialization>() + (8 as usize),
-        seeds = [b"systemInitialization"],
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state @ ErrorCode::VotationIsNotWaitingForTeacher
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
+    pub proposal_account: Account<'info, Proposal>,
 
     #[account(
-        has_one = authority,
-        seeds=[b"highRank", authority.key().as_ref()],
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        realloc = professor_proposal_account_space(&professor_proposal_account, size_of::<i32>()),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
-    pub high_rank_account: Account<'info, HighRank>,
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
 
     #[account(
-        init,
-        payer = authority,
-        space = SOLANA_ACCOUNT_MAX_SIZE,
-        seeds = [b"codeIdSubjectRelation"],
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+    pub subject_account: Account<'info, Subject>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct AddProfessorProposalCollaborator <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
     #[account(
-        init,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"degreeIdHandler"],
-        bump
+        has_one = authority,
+        constraint = professor_proposal_account.assigned_professor_id == professor_account.id @ ErrorCode::ProfessorIsNotAssigned
     )]
-    pub degree_id_handler: Account<'info,IdHandler>,
+    pub professor_account: Account<'info, Professor>,
 
     #[account(
-        init,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"facultyIdHandler"],
-        bump
+        constraint = evaluate_if_user_belong_to_subject(collaborator_account.subjects.clone(), subject_account.code) @ ErrorCode::UserDoesNotBelongToTheSubject
     )]
-    pub faculty_id_handler: Account<'info,IdHandler>,
+    pub collaborator_account: Account<'info, Professor>,
 
     #[account(
-        init,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"specialtyIdHandler"],
-        bump
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state @ ErrorCode::VotationIsNotWaitingForTeacher
     )]
-    pub specialty_id_handler: Account<'info,IdHandler>,
+    pub proposal_account: Account<'info, Proposal>,
 
     #[account(
-        init,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"subjectIdHandler"],
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        realloc = professor_proposal_account_space(&professor_proposal_account, size_of::<i32>()),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub subject_id_handler: Account<'info,IdHandler>,
+    pub subject_account: Account<'info, Subject>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+#[instruction (reason_reference: ContentReference)]
+pub struct RequestExtension <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        constraint = professor_proposal_account.assigned_professor_id == professor_account.id @ ErrorCode::ProfessorIsNotAssigned
+    )]
+    pub professor_account: Account<'info, Professor>,
+
+    #[account(
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state @ ErrorCode::VotationIsNotWaitingForTeacher
+    )]
+    pub proposal_account: Account<'info, Proposal>,
 
-    /// CHECK: 'mint_authority' is an UncheckedAccount since it's just a PDA that references the authority of any HighRank over the tokens
     #[account(
-        mut, 
-        seeds = [b"mint_authority",  mint.key().as_ref(), _user_type_code.as_bytes().as_ref() ],
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = digest(_user_type_code) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        constraint = reason_reference.is_valid() @ ErrorCode::IncorrectExtensionReference,
+        realloc = professor_proposal_account_space(&professor_proposal_account, size_of::<DeadlineExtension>() + reason_reference.space()),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
-    pub mint_authority_account: UncheckedAccount<'info>,
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
 
     #[account(
-        init_if_needed,
-        payer = authority,
-        mint::decimals = 1,
-        mint::authority = mint_authority_account,
-        seeds = [b"creditToken"],
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub mint: Account<'info, Mint>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub subject_account: Account<'info, Subject>,
 
+    pub system_program: Program<'info, System>
 }
 
-
 #[derive(Accounts)]
-#[instruction (user_type_code: String)]
-pub struct CreateHighRank<'info> {
+pub struct ResolveExtension <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
-        init_if_needed,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"highRankIdHandler"],
-        bump
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
     )]
-    pub high_rank_id_handler: Account<'info,IdHandler>,
+    pub high_rank: Account<'info, HighRank>,
 
-    #[account(init, 
-        payer=authority, 
-        space = size_of::<HighRank>() + 12 + 100, 
-        seeds=[b"highRank", authority.key().as_ref()],
+    #[account(
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = digest(user_type_code.clone()) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c")
-    ]
-    pub high_rank_account: Account<'info, HighRank>,
+        constraint = ProposalState::WaitingForTeacher == proposal_account.state @ ErrorCode::VotationIsNotWaitingForTeacher
+    )]
+    pub proposal_account: Account<'info, Proposal>,
 
-    pub system_program: Program<'info,System>,
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id
+    )]
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
 
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>
 }
 
 #[derive(Accounts)]
-#[instruction (user_type_code: String)]
-pub struct CreateProfessor<'info> {
+#[instruction (decision: HighRankDecision, feedback_reference: ContentReference, user_type_code: String)]
+pub struct UpdateProposalByHighRank <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
+    
+    #[account(mut, has_one = authority)]      
+    pub high_rank_account: Account<'info, HighRank>,
 
     #[account(
-        seeds = [b"systemInitialization"],
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref() ], 
         bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
+        constraint = ProposalState::WaitingForHighRank == proposal_account.state  @  ErrorCode::VotationIsNotWaitingForHighRank,                                       
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
+    pub proposal_account: Account<'info, Proposal>,
 
     #[account(
-        init_if_needed,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"professorIdHandler"],
-        bump
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        constraint = feedback_reference.is_valid() @ ErrorCode::IncorrectFeedbackReference,
+        realloc = professor_proposal_account_space(&professor_proposal_account, size_of::<ReviewRound>() + feedback_reference.space()),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
-    pub professor_id_handler: Account<'info,IdHandler>,
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
 
     #[account(
-        constraint = high_rank_id_handler.smaller_id_available > 1  @ ErrorCode::NotAnyHighRankInitializated
+        mut,
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump,
+        realloc = subject_account_space_for_teaching_project(&subject_account, &professor_proposal_account.teaching_project_reference, &professor_proposal_account.section_references),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
-    pub high_rank_id_handler: Account<'info,IdHandler>,
+    pub subject_account: Account<'info, Subject>,
 
-    #[account(init, 
-        payer=authority, 
-        space = size_of::<Professor>() + 12 + 60 + 100 + 100, 
-        seeds=[b"professor", authority.key().as_ref()],
-        bump,
-        constraint = digest(user_type_code) == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9")
-    ]
-    pub professor_account: Account<'info, Professor>,
+    /// CHECK: Reputation of the professor that delivered the work (its seeds are checked by 'record_reputation')
+    #[account(mut)]
+    pub professor_reputation: UncheckedAccount<'info>,
+
+    /// CHECK: Only created if the teaching project is accepted (its seeds are checked by 'create_batch_account')
+    #[account(mut)]
+    pub teaching_project_version: UncheckedAccount<'info>,
 
+    /// CHECK: Only written if the proposal carries a new evaluation scheme (its seeds are checked by 'record_evaluation_scheme')
+    #[account(mut)]
+    pub evaluation_scheme: UncheckedAccount<'info>,
+
+    // The professor and the accounts of the reward are only required if the work is accepted
     #[account(
         mut,
-        seeds = [b"codeIdSubjectRelation"],
+        seeds = [b"professor", professor_proposal_account.delivered_by.as_ref()],
         bump
     )]
-    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+    pub professor_account: Option<Account<'info, Professor>>,
 
-    #[account()]
-    pub mint: Account<'info, Mint>,
+    /// CHECK: reward config, deserialized only if the HighRank has already created it
+    #[account(
+        seeds = [b"professorRewardConfig"],
+        bump
+    )]
+    pub professor_reward_config: UncheckedAccount<'info>,
 
+    /// CHECK: 'mint_authority' is an UncheckedAccount since it's just a PDA that references the authority of any HighRank over the tokens (its seeds are signed when minting)
     #[account(
-        init_if_needed, 
-        payer = authority, 
-        associated_token::mint = mint,
-        associated_token::authority = authority)]
-    pub token_account: Account<'info, TokenAccount>,
+        mut,
+        constraint = digest(user_type_code) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+    )]
+    pub mint_authority_account: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = professor_token_account.owner == professor_proposal_account.delivered_by
+    )]
+    pub professor_token_account: Option<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>, 
+    pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-#[instruction (user_type_code: String)]
-pub struct CreateStudent<'info> {
+#[instruction (user_type_code: String, _subject_code: u32)]
+pub struct GiveCreditToWinningStudent <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = authority
+    )]      
+    pub high_rank_account: Account<'info, HighRank>,
 
     #[account(
-        seeds = [b"systemInitialization"],
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), _subject_code.to_le_bytes().as_ref()], 
         bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
+        constraint = ProposalState::Accepted == proposal_account.state  @  ErrorCode::VotationIsNotAccepted,                                       
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
+    pub proposal_account: Account<'info, Proposal>,
 
-    #[account(
-        init_if_needed,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"studentIdHandler"],
-        bump
-    )]
-    pub student_id_handler: Account<'info,IdHandler>,
 
     #[account(
-        constraint = high_rank_id_handler.smaller_id_available > 1  @ ErrorCode::NotAnyHighRankInitializated
-    )]
-    pub high_rank_id_handler: Account<'info,IdHandler>,
-
-    #[account(init, 
-        payer=authority, 
-        space = size_of::<Student>() + 12 + 100 + 100, 
-        seeds=[b"student", authority.key().as_ref()],
+        mut,
+        seeds=[b"student", proposal_account.creator_public_key.as_ref()], 
         bump,
-        constraint = digest(user_type_code) == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69")
-    ]
-    pub student_account: Account<'info, Student>,
+        constraint = creator_account.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69",
+        constraint = creator_account.id == proposal_account.creator_id                             
+    )]
+    pub creator_account: Account<'info, Student>,
 
+    /// CHECK: 'mint_authority' is an UncheckedAccount since it's just a PDA that references the authority of any HighRank over the tokens
     #[account(
-        mut,
-        seeds = [b"codeIdSubjectRelation"],
-        bump
+        mut, 
+        seeds = [b"mint_authority", mint.key().as_ref(), user_type_code.as_bytes().as_ref()],
+        bump,
+        constraint = digest(user_type_code) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
     )]
-    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+    pub mint_authority_account: UncheckedAccount<'info>,
 
-    #[account()]
+    #[account(mut)]
     pub mint: Account<'info, Mint>,
 
-    #[account(
-        init_if_needed, 
-        payer = authority, 
-        associated_token::mint = mint,
-        associated_token::authority = authority)]
+    #[account(mut)]
     pub token_account: Account<'info, TokenAccount>,
 
+    /// CHECK: Created on the first outcome of the user (its seeds are checked by 'record_reputation')
+    #[account(mut)]
+    pub creator_reputation: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>
+    pub rent: Sysvar<'info, Rent>, 
+}
+
+impl<'info> GiveCreditToWinningStudent <'info> {
+
+    pub fn get_mint_ctx(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+
+        let cpi_program =  self.token_program.to_account_info();
+        let cpi_accounts = MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.mint_authority_account.to_account_info(),
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
 }
 
 #[derive(Accounts)]
-#[instruction (name: String)]
-pub struct CreateFaculty<'info> {
+#[instruction (user_type_code: String, _subject_code: u32)]
+pub struct GiveCreditToWinningProfessor <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        has_one = authority
+    )]      
+    pub high_rank_account: Account<'info, HighRank>,
 
     #[account(
-        seeds = [b"systemInitialization"],
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), _subject_code.to_le_bytes().as_ref()], 
         bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
+        constraint = ProposalState::Accepted == proposal_account.state  @  ErrorCode::VotationIsNotAccepted,                                       
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
-
-    #[account(mut)]
-    pub faculty_id_handler: Account<'info,IdHandler>,
+    pub proposal_account: Account<'info, Proposal>,
 
-    #[account(has_one = authority)] 
-    pub high_rank: Account<'info, HighRank>,
 
-    #[account(init, 
-        payer=authority, 
-        space = size_of::<Faculty>() + name.as_bytes().len() + 4, 
-        seeds=[b"faculty", faculty_id_handler.smaller_id_available.to_le_bytes().as_ref()], 
+    #[account(
+        mut,
+        seeds=[b"professor", proposal_account.creator_public_key.to_bytes().as_ref()], 
         bump,
-        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = name.len() <= 50
+        constraint = creator_account.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
+        constraint = creator_account.id == proposal_account.creator_id                             
     )]
-    pub faculty_account: Account<'info, Faculty>,
-
-    pub system_program: Program<'info,System>
-}
-
-#[derive(Accounts)]
-#[instruction (name: String, faculty_id:i32)]
-pub struct CreateDegree<'info> {
+    pub creator_account: Account<'info, Professor>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
 
-    #[account(
-        seeds = [b"systemInitialization"],
-        bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+   /// CHECK: 'mint_authority' is an UncheckedAccount since it's just a PDA that references the authority of any HighRank over the tokens
+   #[account(
+    mut, 
+    seeds = [b"mint_authority", mint.key().as_ref(), user_type_code.as_bytes().as_ref()],
+    bump,
+    constraint = digest(user_type_code) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
+    pub mint_authority_account: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub degree_id_handler: Account<'info,IdHandler>,
+    pub mint: Account<'info, Mint>,
 
     #[account(mut)]
-    pub faculty_id_handler: Account<'info, IdHandler>,
+    pub token_account: Account<'info, TokenAccount>,
 
-    #[account(has_one = authority)] 
-    pub high_rank: Account<'info, HighRank>,
+    /// CHECK: Created on the first outcome of the user (its seeds are checked by 'record_reputation')
+    #[account(mut)]
+    pub creator_reputation: UncheckedAccount<'info>,
 
-    #[account(init, 
-        payer=authority, 
-        space = size_of::<Degree>() + name.as_bytes().len() + 4, 
-        seeds=[b"degree", degree_id_handler.smaller_id_available.to_le_bytes().as_ref()], 
-        bump,
-        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = name.len() <= 500,
-        constraint = (faculty_id >= 1 && faculty_id < faculty_id_handler.smaller_id_available)
-    )]
-    pub degree_account: Account<'info, Degree>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>, 
+}
 
-    pub system_program: Program<'info,System>
+impl<'info> GiveCreditToWinningProfessor <'info> {
+
+    pub fn get_mint_ctx(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+
+        let cpi_program =  self.token_program.to_account_info();
+        let cpi_accounts = MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.token_account.to_account_info(),
+            authority: self.mint_authority_account.to_account_info(),
+        };
+
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
 }
 
 #[derive(Accounts)]
-#[instruction (name: String, degree_id:i32)]
-pub struct CreateSpecialty <'info> {
+#[instruction (title: String, content: String, content_reference: ContentReference, content_sha256: String, attachments: Vec<ProposalAttachment>)]
+pub struct EditProposal <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
-        seeds = [b"systemInitialization"],
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+        constraint = proposal_account.creator_public_key == authority.key() @ ErrorCode::UserIsNotTheProposalCreator,
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen,
+        constraint = proposal_account.supporting_votes + proposal_account.against_votes == 0 @ ErrorCode::ProposalHasAlreadyBeenVoted,
+        constraint = title.len() <= 100 && content.len() <= MAXIMUM_PROPOSAL_SUMMARY_LENGTH,
+        realloc = proposal_account_space(&proposal_account, proposal_body_space(&title, &content, &content_reference, &content_sha256, &attachments))
+            - proposal_body_space(&proposal_account.title, &proposal_account.content, &proposal_account.content_reference, &proposal_account.content_sha256, &proposal_account.attachments),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
-
-    #[account(mut)]
-    pub specialty_id_handler: Account<'info, IdHandler>,
-
-    #[account(mut)]
-    pub degree_id_handler: Account<'info, IdHandler>,
+    pub proposal_account: Account<'info, Proposal>,
 
-    #[account(has_one = authority)] 
-    pub high_rank: Account<'info, HighRank>,
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
 
-    #[account(init, 
-        payer=authority, 
-        space = size_of::<Specialty>() + name.as_bytes().len() + 4, 
-        seeds=[b"specialty", specialty_id_handler.smaller_id_available.to_le_bytes().as_ref()], 
-        bump,
-        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = name.len() <= 500,
-        constraint = (degree_id >= 1 && degree_id < degree_id_handler.smaller_id_available)
+    #[account(
+        mut,
+        seeds = [b"proposalIndex", subject_account.code.to_le_bytes().as_ref()],
+        bump
     )]
-    pub specialty_account: Account<'info, Specialty>,
+    pub proposal_index: Account<'info, ProposalIndex>,
 
-    pub system_program: Program<'info,System>
+    pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-#[instruction (name: String, degree_id: i32, specialty_id: i32, course: SubjectCourse, code: u32, teaching_project_reference: String)]
-pub struct CreateSubject<'info> {
+pub struct WithdrawProposal <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
-        seeds = [b"systemInitialization"],
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+        constraint = proposal_account.creator_public_key == authority.key() @ ErrorCode::UserIsNotTheProposalCreator,
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen,
+        close = authority
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
-    
-    #[account(mut, has_one = authority)]      // Se comprueba que la cuenta de "Alto cargo" pasada pertenece a quien firma la transacción (autenticación)
-    pub high_rank: Account<'info, HighRank>,
-
-    #[account(mut)]
-    pub subject_id_handler: Account<'info,IdHandler>,
-
-    #[account()]
-    pub degree_id_handler: Account<'info,IdHandler>,
-
-    #[account()]
-    pub specialty_id_handler: Account<'info,IdHandler>,
-
+    pub proposal_account: Account<'info, Proposal>,
 
-    #[account(init, 
-        payer=authority, 
-        space = size_of::<Subject>() + name.as_bytes().len() + teaching_project_reference.as_bytes().len(), 
-        seeds=[b"subject", subject_id_handler.smaller_id_available.to_le_bytes().as_ref()], 
+    #[account(
+        mut,
+        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = (degree_id >= 1) && (degree_id < degree_id_handler.smaller_id_available),
-        constraint = (specialty_id == -1) || (specialty_id >= 1 && specialty_id < specialty_id_handler.smaller_id_available),
-        constraint = teaching_project_reference.len() == 46 @ ErrorCode::IncorrectTeachingProjectReference
+        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
+        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        close = authority
     )]
-    pub subject_account: Account<'info, Subject>,
+    pub professor_proposal_account: Account<'info, ProfessorProposal>,
 
     #[account(
-        init_if_needed,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"proposalIdHandler", code.to_le_bytes().as_ref()],
-        bump
+        mut,
+        seeds = [b"proposalDeposit", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        close = authority
     )]
-    pub proposal_id_handler: Account<'info,IdHandler>,
+    pub proposal_deposit: Account<'info, ProposalDeposit>,
 
     #[account(
-        init_if_needed,
-        payer = authority,
-        space = size_of::<IdHandler>() + 140,
-        seeds = [b"professorProposalIdHandler", code.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"openProposals", authority.key().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump
     )]
-    pub professor_proposal_id_handler: Account<'info, IdHandler>,
+    pub open_proposals_counter: Account<'info, OpenProposalsCounter>,
 
     #[account(
         mut,
-        seeds = [b"codeIdSubjectRelation"],
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub code_id_subject_relation_account: Account<'info, CodeIdSubjectRelation>,
-
-    pub system_program: Program<'info, System>
-}
-
-#[derive(Accounts)]
-#[instruction (title: String, content: String)]
-pub struct CreateProposalByStudent <'info> {
-
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    #[account(
-        seeds = [b"systemInitialization"],
-        bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
-    )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
-    
-    #[account(mut, has_one = authority)]      
-    pub student_creator: Account<'info, Student>,
+    pub subject_account: Account<'info, Subject>,
 
     #[account(
         mut,
         seeds = [b"proposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
         bump
     )]
-    pub proposal_id_handler: Account<'info,IdHandler>,
+    pub proposal_id_handler: Account<'info, IdHandler>,
 
     #[account(
         mut,
@@ -1028,438 +4149,395 @@ pub struct CreateProposalByStudent <'info> {
         bump
     )]
     pub professor_proposal_id_handler: Account<'info, IdHandler>,
-    
-    #[account()]
-    pub subject_id_handler: Account<'info,IdHandler>,
 
+    /// CHECK: only used to know whether an appeal was filed against the proposal
     #[account(
-        init, 
-        payer=authority, 
-        space = size_of::<Proposal>() + title.as_bytes().len() + content.as_bytes().len() + 40, 
-        seeds=[b"proposal", proposal_id_handler.smaller_id_available.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
-        bump,
-        constraint = student_creator.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69",
-        constraint = title.len() <= 100 && content.len() <= 2500       
+        seeds = [b"appeal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
     )]
-    pub proposal_account: Account<'info, Proposal>,
+    pub appeal_account: UncheckedAccount<'info>,
 
-    #[account(init,
-        payer = authority,
-        space = size_of::<ProfessorProposal>() + title.len() + 20 + 60,
-        seeds = [b"professorProposal", professor_proposal_id_handler.smaller_id_available.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
-        bump,
+    #[account(
+        mut,
+        seeds = [b"proposalIndex", subject_account.code.to_le_bytes().as_ref()],
+        bump
     )]
-    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+    pub proposal_index: Account<'info, ProposalIndex>,
+}
+
+#[derive(Accounts)]
+#[instruction (reason_reference: ContentReference)]
+pub struct FileAppeal <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        realloc = 
-            size_of::<Subject>() + 
-            (subject_account.name.as_bytes().len() as usize) - (20 as usize) + 
-            (subject_account.teaching_project_reference.as_bytes().len() as usize) - (20 as usize) +
-            (subject_account.pending_proposals.len() as u16 * 4_u16 + 8_u16) as usize - (20 as usize),
-        realloc::payer = authority,
-        realloc::zero = false
-        
+        constraint = proposal_account.creator_public_key == authority.key() @ ErrorCode::UserIsNotTheProposalCreator
     )]
-    pub subject_account: Account<'info, Subject>,
+    pub proposal_account: Account<'info, Proposal>,
 
     #[account(
-        mut,
-        seeds = [b"codeIdSubjectRelation"],
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
+    pub subject_account: Account<'info, Subject>,
 
-    pub system_program: Program<'info, System>
+    // A proposal can only be appealed once: the appeal is kept after its resolution, so a reopened votation that is rejected again is final
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<Appeal>() + reason_reference.space() + CONTENT_REFERENCE_RESERVED_SPACE,
+        seeds = [b"appeal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = reason_reference.is_valid() @ ErrorCode::IncorrectAppealReference
+    )]
+    pub appeal_account: Account<'info, Appeal>,
 
+    pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-#[instruction (title: String, content: String)]
-pub struct CreateProposalByProfessor <'info> {
+#[instruction (reopen_votation: bool, justification_reference: ContentReference)]
+pub struct ResolveAppeal <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
-        seeds = [b"systemInitialization"],
-        bump,
-        constraint = initialization_system_account.system_is_initialized == true @ ErrorCode::SystemIsNotInitializated
+        has_one = authority,
+        constraint = high_rank.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
     )]
-    pub initialization_system_account: Account<'info, SystemInitialization>,
-    
-    #[account(mut, has_one = authority)]      
-    pub professor_creator: Account<'info, Professor>,
+    pub high_rank: Account<'info, HighRank>,
 
-     #[account(
+    #[account(
         mut,
-        seeds = [b"proposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = ProposalState::UnderAppeal == proposal_account.state @ ErrorCode::ProposalIsNotUnderAppeal
+    )]
+    pub proposal_account: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub proposal_id_handler: Account<'info,IdHandler>,
+    pub subject_account: Account<'info, Subject>,
 
     #[account(
         mut,
-        seeds = [b"professorProposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        seeds = [b"appeal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = AppealResolution::Pending == appeal_account.resolution @ ErrorCode::ProposalIsNotUnderAppeal,
+        constraint = justification_reference.is_valid() @ ErrorCode::IncorrectAppealReference
+    )]
+    pub appeal_account: Account<'info, Appeal>,
+
+    /// CHECK: only checked to be still open (not closed by 'settle_proposal_deposit') when the votation is reopened
+    #[account(
+        seeds = [b"proposalDeposit", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump
     )]
-    pub professor_proposal_id_handler: Account<'info, IdHandler>,
-    
-    #[account()]
-    pub subject_id_handler: Account<'info,IdHandler>,
+    pub proposal_deposit: UncheckedAccount<'info>
+}
+
+#[derive(Accounts)]
+pub struct InviteCoAuthor <'info> {
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
     #[account(
-        init, 
-        payer=authority, 
-        space = size_of::<Proposal>() + title.as_bytes().len() + content.as_bytes().len() + 40, 
-        seeds=[b"proposal", proposal_id_handler.smaller_id_available.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        mut,
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = professor_creator.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
-        constraint = title.len() <= 100 && content.len() <= 2500       
+        constraint = proposal_account.creator_public_key == authority.key() @ ErrorCode::UserIsNotTheProposalCreator,
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen,
+        realloc = proposal_account_space(&proposal_account, size_of::<CoAuthor>()),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
     pub proposal_account: Account<'info, Proposal>,
 
-    #[account(init,
-        payer = authority,
-        space = size_of::<ProfessorProposal>() + title.len() + 20,
-        seeds = [b"professorProposal", professor_proposal_id_handler.smaller_id_available.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
-        bump,
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
     )]
-    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+    pub subject_account: Account<'info, Subject>,
+
+    /// CHECK: Student or Professor account of the invited user, validated in the instruction according to the user type
+    pub co_author_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CoSignProposal <'info> {
 
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"subject", subject_account.id.to_le_bytes().as_ref()],
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        realloc = 
-            size_of::<Subject>() + 
-            (subject_account.name.as_bytes().len() as usize) - (20 as usize) + 
-            (subject_account.teaching_project_reference.as_bytes().len() as usize) - (20 as usize) +
-            (subject_account.pending_proposals.len() as u16 * 4_16 + 8_u16) as usize - (20 as usize),
-        realloc::payer = authority,
-        realloc::zero = false
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen
     )]
-    pub subject_account: Account<'info, Subject>,
+    pub proposal_account: Account<'info, Proposal>,
 
     #[account(
-        seeds = [b"codeIdSubjectRelation"],
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub code_id_subject_relation: Account<'info, CodeIdSubjectRelation>,
-
-    pub system_program: Program<'info, System>
+    pub subject_account: Account<'info, Subject>
 }
 
 #[derive(Accounts)]
-pub struct VoteProposalByStudent <'info> {
+#[instruction (parent_comment_id: i32, content_reference: Option<ContentReference>, text: String)]
+pub struct CommentProposalByStudent <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
-    
-    #[account(mut, has_one = authority)]      
-    pub voting_student: Account<'info, Student>,
-    
-    #[account()]
-    pub subject_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        has_one = authority,
+        constraint = student_account.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69"
+    )]
+    pub student_account: Account<'info, Student>,
 
     #[account(
         mut,
-        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = voting_student.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69",
-        constraint = ProposalState::VotationInProgress == proposal_account.state @ErrorCode::VotationIsNotOpen,                                     
-        realloc = size_of::<Proposal>() + 
-                proposal_account.title.as_bytes().len() - (20 as usize) +
-                proposal_account.content.as_bytes().len() - (20 as usize) +
-                proposal_account.students_that_have_voted.len() * 4 + 4 - (20 as usize),
-        realloc::payer = authority,
-        realloc::zero = false
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen
     )]
     pub proposal_account: Account<'info, Proposal>,
 
     #[account(
         seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
-        bump,
-        constraint =  proposal_account.subject_id < subject_id_handler.smaller_id_available
+        bump
     )]
     pub subject_account: Account<'info, Subject>,
 
-    #[account(mut)]
-    pub professor_proposal_id_handler: Account<'info, IdHandler>,
-
     #[account(
-        mut,
-        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
-        bump,
-        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
-        constraint = professor_proposal_account.id < professor_proposal_id_handler.smaller_id_available
+        init,
+        payer = authority,
+        space = size_of::<Comment>() + content_reference.as_ref().map_or(0, |x| x.space()) + text.as_bytes().len(),
+        seeds = [b"comment", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref(), proposal_account.number_of_comments.to_le_bytes().as_ref()],
+        bump
     )]
-    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+    pub comment_account: Account<'info, Comment>,
 
     pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-pub struct VoteProposalByProfessor <'info> {
+#[instruction (parent_comment_id: i32, content_reference: Option<ContentReference>, text: String)]
+pub struct CommentProposalByProfessor <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
-    
-    #[account(mut, has_one = authority)]      
-    pub voting_professor: Account<'info, Professor>,
-    
-    #[account()]
-    pub subject_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        has_one = authority,
+        constraint = professor_account.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9"
+    )]
+    pub professor_account: Account<'info, Professor>,
 
     #[account(
         mut,
-        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = voting_professor.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
-        constraint = ProposalState::VotationInProgress == proposal_account.state @ ErrorCode::VotationIsNotOpen,
-        realloc = size_of::<Proposal>() + 
-                proposal_account.title.as_bytes().len() - (20 as usize) +
-                proposal_account.content.as_bytes().len() - (20 as usize) +
-                proposal_account.students_that_have_voted.len() * 4 + 4 - (20 as usize),
-        realloc::payer = authority,
-        realloc::zero = false                                           
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen
     )]
     pub proposal_account: Account<'info, Proposal>,
 
     #[account(
         seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
-        bump,
-        constraint = proposal_account.subject_id < subject_id_handler.smaller_id_available
+        bump
     )]
     pub subject_account: Account<'info, Subject>,
 
-    #[account(mut)]
-    pub professor_proposal_id_handler: Account<'info, IdHandler>,
-
     #[account(
-        mut,
-        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
-        bump,
-        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
-        constraint = professor_proposal_account.id < professor_proposal_id_handler.smaller_id_available
+        init,
+        payer = authority,
+        space = size_of::<Comment>() + content_reference.as_ref().map_or(0, |x| x.space()) + text.as_bytes().len(),
+        seeds = [b"comment", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref(), proposal_account.number_of_comments.to_le_bytes().as_ref()],
+        bump
     )]
-    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+    pub comment_account: Account<'info, Comment>,
 
     pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-#[instruction (teaching_project_reference: String)]
-pub struct UpdateProposalByProfessor <'info> {
+#[instruction (title: String, content: String, content_reference: ContentReference, content_sha256: String)]
+pub struct ProposeAmendmentByStudent <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
-    
-    #[account(mut, has_one = authority)]      
-    pub professor_account: Account<'info, Professor>,
-    
+
     #[account(
-        mut,
-        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()], 
-        bump,
-        constraint = professor_account.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
-        constraint = ProposalState::WaitingForTeacher == proposal_account.state  @  ErrorCode::VotationIsNotWaitingForTeacher                                           
+        has_one = authority,
+        constraint = student_account.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69"
     )]
-    pub proposal_account: Account<'info, Proposal>,
+    pub student_account: Account<'info, Student>,
 
     #[account(
         mut,
-        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
-        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
-        constraint = teaching_project_reference.len() == 46 @ ErrorCode::IncorrectTeachingProjectReference
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen,
+        constraint = title.len() <= 100 && content.len() <= MAXIMUM_PROPOSAL_SUMMARY_LENGTH
     )]
-    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+    pub proposal_account: Account<'info, Proposal>,
 
     #[account(
         seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
-        bump,
+        bump
     )]
     pub subject_account: Account<'info, Subject>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<Amendment>() + title.as_bytes().len() + content.as_bytes().len() + content_reference.space() + content_sha256.as_bytes().len(),
+        seeds = [b"amendment", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref(), proposal_account.number_of_amendments.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub amendment_account: Account<'info, Amendment>,
+
+    pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-pub struct UpdateProposalByHighRank <'info> {
+#[instruction (title: String, content: String, content_reference: ContentReference, content_sha256: String)]
+pub struct ProposeAmendmentByProfessor <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
-    
-    #[account(mut, has_one = authority)]      
-    pub high_rank_account: Account<'info, HighRank>,
 
     #[account(
-        mut,
-        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref() ], 
-        bump,
-        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = ProposalState::WaitingForHighRank == proposal_account.state  @  ErrorCode::VotationIsNotWaitingForHighRank,                                       
+        has_one = authority,
+        constraint = professor_account.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9"
     )]
-    pub proposal_account: Account<'info, Proposal>,
+    pub professor_account: Account<'info, Professor>,
 
     #[account(
         mut,
-        seeds = [b"professorProposal", professor_proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = professor_proposal_account.original_proposal_id == proposal_account.id,
-        constraint = proposal_account.associated_professor_proposal_id == professor_proposal_account.id,
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen,
+        constraint = title.len() <= 100 && content.len() <= MAXIMUM_PROPOSAL_SUMMARY_LENGTH
     )]
-    pub professor_proposal_account: Account<'info, ProfessorProposal>,
+    pub proposal_account: Account<'info, Proposal>,
 
     #[account(
-        mut,
         seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
-        bump,
+        bump
     )]
     pub subject_account: Account<'info, Subject>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = size_of::<Amendment>() + title.as_bytes().len() + content.as_bytes().len() + content_reference.space() + content_sha256.as_bytes().len(),
+        seeds = [b"amendment", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref(), proposal_account.number_of_amendments.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub amendment_account: Account<'info, Amendment>,
+
+    pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-#[instruction (user_type_code: String, _subject_code: u32)]
-pub struct GiveCreditToWinningStudent <'info> {
+pub struct AcceptAmendment <'info> {
 
     #[account(mut)]
     pub authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        has_one = authority
-    )]      
-    pub high_rank_account: Account<'info, HighRank>,
 
     #[account(
         mut,
-        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), _subject_code.to_le_bytes().as_ref()], 
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = ProposalState::Accepted == proposal_account.state  @  ErrorCode::VotationIsNotAccepted,                                       
+        constraint = proposal_account.creator_public_key == authority.key() @ ErrorCode::UserIsNotTheProposalCreator,
+        constraint = proposal_account.state.is_open_to_participation() @ ErrorCode::VotationIsNotOpen,
+        realloc = proposal_account_space(&proposal_account, proposal_body_space(&amendment_account.title, &amendment_account.content, &amendment_account.content_reference, &amendment_account.content_sha256, &proposal_account.attachments))
+            - proposal_body_space(&proposal_account.title, &proposal_account.content, &proposal_account.content_reference, &proposal_account.content_sha256, &proposal_account.attachments),
+        realloc::payer = authority,
+        realloc::zero = false
     )]
     pub proposal_account: Account<'info, Proposal>,
 
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
 
     #[account(
         mut,
-        seeds=[b"student", proposal_account.creator_public_key.as_ref()], 
+        seeds = [b"amendment", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref(), amendment_account.id.to_le_bytes().as_ref()],
         bump,
-        constraint = creator_account.identifier_code_hash == "318aee3fed8c9d040d35a7fc1fa776fb31303833aa2de885354ddf3d44d8fb69",
-        constraint = creator_account.id == proposal_account.creator_id                             
+        constraint = AmendmentState::Pending == amendment_account.state @ ErrorCode::AmendmentIsNotPending,
+        constraint = amendment_account.proposed_over_version == proposal_account.version @ ErrorCode::AmendmentIsOutdated
     )]
-    pub creator_account: Account<'info, Student>,
+    pub amendment_account: Account<'info, Amendment>,
 
-    /// CHECK: 'mint_authority' is an UncheckedAccount since it's just a PDA that references the authority of any HighRank over the tokens
     #[account(
-        mut, 
-        seeds = [b"mint_authority", mint.key().as_ref(), user_type_code.as_bytes().as_ref()],
-        bump,
-        constraint = digest(user_type_code) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+        mut,
+        seeds = [b"proposalIndex", subject_account.code.to_le_bytes().as_ref()],
+        bump
     )]
-    pub mint_authority_account: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-
-    #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>, 
-}
-
-impl<'info> GiveCreditToWinningStudent <'info> {
-
-    pub fn get_mint_ctx(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+    pub proposal_index: Account<'info, ProposalIndex>,
 
-        let cpi_program =  self.token_program.to_account_info();
-        let cpi_accounts = MintTo {
-            mint: self.mint.to_account_info(),
-            to: self.token_account.to_account_info(),
-            authority: self.mint_authority_account.to_account_info(),
-        };
-
-        CpiContext::new(cpi_program, cpi_accounts)
-    }
+    pub system_program: Program<'info, System>
 }
 
 #[derive(Accounts)]
-#[instruction (user_type_code: String, _subject_code: u32)]
-pub struct GiveCreditToWinningProfessor <'info> {
+pub struct SettleProposalDeposit <'info> {
 
-    #[account(mut)]
     pub authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        has_one = authority
-    )]      
-    pub high_rank_account: Account<'info, HighRank>,
 
     #[account(
-        mut,
-        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), _subject_code.to_le_bytes().as_ref()], 
-        bump,
-        constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = ProposalState::Accepted == proposal_account.state  @  ErrorCode::VotationIsNotAccepted,                                       
+        seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
     )]
     pub proposal_account: Account<'info, Proposal>,
 
+    #[account(
+        seeds = [b"subject", proposal_account.subject_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subject_account: Account<'info, Subject>,
 
     #[account(
         mut,
-        seeds=[b"professor", proposal_account.creator_public_key.to_bytes().as_ref()], 
+        seeds = [b"proposalDeposit", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
-        constraint = creator_account.identifier_code_hash == "edee29f882543b956620b26d0ee0e7e950399b1c4222f5de05e06425b4c995e9",
-        constraint = creator_account.id == proposal_account.creator_id                             
-    )]
-    pub creator_account: Account<'info, Professor>,
-
-
-   /// CHECK: 'mint_authority' is an UncheckedAccount since it's just a PDA that references the authority of any HighRank over the tokens
-   #[account(
-    mut, 
-    seeds = [b"mint_authority", mint.key().as_ref(), user_type_code.as_bytes().as_ref()],
-    bump,
-    constraint = digest(user_type_code) == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c"
+        has_one = depositor,
+        close = depositor
     )]
-    pub mint_authority_account: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub proposal_deposit: Account<'info, ProposalDeposit>,
 
     #[account(mut)]
-    pub token_account: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>, 
-}
-
-impl<'info> GiveCreditToWinningProfessor <'info> {
-
-    pub fn get_mint_ctx(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
+    pub depositor: SystemAccount<'info>,
 
-        let cpi_program =  self.token_program.to_account_info();
-        let cpi_accounts = MintTo {
-            mint: self.mint.to_account_info(),
-            to: self.token_account.to_account_info(),
-            authority: self.mint_authority_account.to_account_info(),
-        };
+    #[account(
+        mut,
+        seeds = [b"openProposals", depositor.key().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub open_proposals_counter: Account<'info, OpenProposalsCounter>,
 
-        CpiContext::new(cpi_program, cpi_accounts)
-    }
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>
 }
 
 #[derive(Accounts)]
@@ -1476,7 +4554,7 @@ pub struct DeleteRejectedProposal <'info> {
         seeds=[b"proposal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
         bump,
         constraint = high_rank_account.identifier_code_hash == "0ffe1abd1a08215353c233d6e009613e95eec4253832a761af28ff37ac5a150c",
-        constraint = ProposalState::Rejected == proposal_account.state  @  ErrorCode::VotationIsNotRejected,
+        constraint = ProposalState::Rejected == proposal_account.state || ProposalState::RejectedByHighRank == proposal_account.state  @  ErrorCode::VotationIsNotRejected,
         close = authority                                            
     )]
     pub proposal_account: Account<'info, Proposal>,
@@ -1498,6 +4576,42 @@ pub struct DeleteRejectedProposal <'info> {
         constraint = proposal_account.subject_id == subject_account.id
     )]
     pub subject_account: Account<'info, Subject>,
+
+    #[account(
+        mut,
+        seeds = [b"proposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_id_handler: Account<'info, IdHandler>,
+
+    #[account(
+        mut,
+        seeds = [b"professorProposalIdHandler", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub professor_proposal_id_handler: Account<'info, IdHandler>,
+
+    /// CHECK: the deposit of the proposal must have been settled (and its escrow account closed) before deleting the proposal
+    #[account(
+        seeds = [b"proposalDeposit", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal_deposit.data_is_empty() @ ErrorCode::ProposalDepositIsNotSettled
+    )]
+    pub proposal_deposit: UncheckedAccount<'info>,
+
+    /// CHECK: only used to know whether an appeal was filed against the proposal
+    #[account(
+        seeds = [b"appeal", proposal_account.id.to_le_bytes().as_ref(), subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub appeal_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposalIndex", subject_account.code.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal_index: Account<'info, ProposalIndex>,
 }
 
 
@@ -1522,6 +4636,23 @@ pub struct IdHandler {
     reused_id: Vec<i64>                     // Suponiendo 20 id's: 20*8 bytes (160 bytes + 4 alineación) || Tamaño por defecto: 24 (20 + 4 alineación) --> dif = + 140 bytes
 }
 
+impl IdHandler {
+
+    fn next_id(&self) -> i32 {
+        match self.reused_id.first() {
+            Some(id) => *id as i32,
+            None => self.smaller_id_available
+        }
+    }
+
+    // Once the reserved space is full, the freed id is simply not recycled
+    fn free_id(&mut self, id: i32) {
+        if self.reused_id.len() < REUSED_ID_CAPACITY && !self.reused_id.contains(&(id as i64)) {
+            self.reused_id.push(id as i64);
+        }
+    }
+}
+
 #[account]
 #[derive(Default)]
 pub struct Professor {
@@ -1533,6 +4664,8 @@ pub struct Professor {
     pendent_votation_proposals: Vec<i64>,         // Suponiendo 15 propuestas: 15*8 bytes (120 bytes + 4 alineación) || Tamaño por defecto: 24 (20 + 4 alineación) --> dif = + 100 bytes
     punishments: u8,                              // 1 byte
     rewards: u32,                                 // 4 bytes
+    number_of_penalties: u32,                     // 4 bytes --> índice del próximo 'PenaltyRecord' del profesor
+    last_penalty_decay_timestamp: i64,            // 8 bytes
 }
     
 #[account]
@@ -1581,9 +4714,118 @@ pub struct Subject {
     code: u32,
     degree_id: i32,
     specialty_id: i32,
-    teaching_project_reference: String,
+    teaching_project_reference: ContentReference,
+    course: SubjectCourse,
+    pending_proposals: Vec<i32>,
+    cross_listings: Vec<CrossListing>,          // Degrees (and specialties) sharing the subject apart from 'degree_id' / 'specialty_id'
+    prerequisites: Vec<u32>,                    // Codes of the subjects that must be passed before this one
+    corequisites: Vec<u32>,                     // Codes of the subjects that must be taken together with this one
+    teaching_project_version: u32,              // Last version of the teaching project (0 --> the one given when the subject was created)
+    sections: Vec<SectionReference>             // Index of the sections of the teaching project (one entry per section at most)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct SectionReference {
+    section: TeachingProjectSection,
+    reference: ContentReference                 // sha256 obligatorio
+}
+
+// Reference to a document stored off-chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq)]
+pub struct ContentReference {
+    backend: StorageBackend,
+    identifier: String,                         // CIDv0 (46 bytes), CIDv1 en base32 (máx. 100 bytes) o id de transacción de Arweave (43 bytes)
+    sha256: Option<String>                      // sha256 opcional del documento --> 64 bytes
+}
+
+impl ContentReference {
+
+    fn is_valid(&self) -> bool {
+        let identifier_is_valid = match self.backend {
+            StorageBackend::Ipfs => is_valid_ipfs_cid(&self.identifier),
+            StorageBackend::Arweave => is_valid_arweave_id(&self.identifier)
+        };
+        identifier_is_valid && self.sha256.as_ref().map_or(true, |x| is_valid_sha256_hex(x))
+    }
+
+    // Dynamic content of the reference (the rest is already counted by the 'size_of' of the account that holds it)
+    fn space(&self) -> usize {
+        self.identifier.as_bytes().len() + self.sha256.as_ref().map_or(0, |x| x.as_bytes().len())
+    }
+}
+
+// Instruction data for the creation of academic entities (one per entity in the batch instructions)
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DegreeData {
+    name: String,
+    faculty_id: i32
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SpecialtyData {
+    name: String,
+    degree_id: i32
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubjectData {
+    name: String,
+    degree_id: i32,
+    specialty_id: i32,
     course: SubjectCourse,
-    pending_proposals: Vec<i32>
+    code: u32,
+    teaching_project_reference: ContentReference,
+    prerequisites: Vec<u32>,
+    corequisites: Vec<u32>,
+    sections: Vec<SectionReference>
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub struct CrossListing {
+    degree_id: i32,
+    specialty_id: i32
+}
+
+#[account]
+#[derive(Default)]
+pub struct EvaluationScheme {
+    subject_code: u32,
+    scheme: EvaluationSchemeData,
+    proposal_id: i32,                           // -1 si fue creado directamente por un HighRank
+    approved_by_high_rank_id: i32,              // -1 si la categoría de la propuesta no requiere validación
+    last_update_timestamp: i64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq)]
+pub struct EvaluationSchemeData {
+    components: Vec<EvaluationComponent>,       // Máx. 10 componentes cuyos pesos suman 100
+    minimum_passing_grade: u8,                  // Décimas (ej: 50 --> 5,0)
+    resit_maximum_grade: u8                     // Nota máxima en la recuperación (décimas)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct EvaluationComponent {
+    name: String,                               // Máx. 32 bytes
+    weight: u8,                                 // Porcentaje sobre la nota final
+    minimum_grade: u8,                          // Nota mínima del componente para aprobar (décimas, 0 si no la hay)
+    resit_allowed: bool
+}
+
+// Versioned extension of the Subject account (the version is increased whenever new fields are added)
+#[account]
+#[derive(Default)]
+pub struct SubjectMetadata {
+    version: u8,
+    subject_id: i32,
+    ects_credits: u16,                  // Décimas de crédito (ej: 45 --> 4,5 ECTS)
+    semester: SubjectSemester,
+    teaching_language: String,          // Código ISO 639-1 / BCP 47 (máx. 10 caracteres) --> cabe en el tamaño por defecto
+    modality: SubjectModality,
+    coordinator_professor_id: i32,      // -1 si no hay coordinador asignado
+    course_number: u8,                  // Sin el límite de 'SubjectCourse' (Nineth)
+    last_update_timestamp: i64,
+    updated_by_high_rank_id: i32
 }
 
 #[account]
@@ -1593,7 +4835,10 @@ pub struct Proposal {
     professors_that_have_voted: Vec<i32>,
     id: i32,                                     
     title: String,
-    content: String,
+    content: String,                             // Resumen opcional (máx. 280 bytes) --> el cuerpo completo se guarda fuera de la cadena
+    content_reference: ContentReference,         // Cuerpo de la propuesta (IPFS o Arweave)
+    content_sha256: String,                      // sha256 del cuerpo de la propuesta --> 64 bytes
+    attachments: Vec<ProposalAttachment>,        // Máx. 10 adjuntos
     creator_id: i32,
     creator_public_key: Pubkey,
     user_type: ProposalUserType,                  
@@ -1606,7 +4851,245 @@ pub struct Proposal {
     updated_by_teacher: bool,
     high_rank_validation: bool,
     state: ProposalState,
-    associated_professor_proposal_id: i32
+    associated_professor_proposal_id: i32,
+    downstream_subjects: Vec<u32>,              // Codes of the subjects depending on the proposal's one (their professors can also vote)
+    version: u16,                               // Increased on every edition of the title or the content
+    content_hash_chain: String,                 // sha256(hash anterior | título | resumen | hash del cuerpo) --> 64 bytes
+    number_of_comments: i32,                    // Usado como id (y semilla) del siguiente comentario
+    number_of_amendments: i32,                  // Usado como id (y semilla) de la siguiente enmienda
+    category: ProposalCategory,
+    tags: Vec<String>,                          // Máx. 5 etiquetas de 20 bytes
+    approval_threshold: u8,                     // Copiado de la configuración de la categoría al crear la propuesta
+    high_rank_validation_required: bool,        // Copiado de la configuración de la categoría al crear la propuesta
+    co_authors: Vec<CoAuthor>,                  // Máx. 5 coautores (sólo los que han firmado reciben recompensa)
+    rejection_timestamp: i64,                   // 0 mientras la propuesta no haya sido rechazada (por votación o por falta de quórum)
+    voting_starts_at: i64,                      // Igual a publishing_timestamp si no hay periodo de lectura
+    sections: Vec<TeachingProjectSection>,      // Secciones del proyecto docente afectadas (vacío --> el proyecto completo)
+    evaluation_scheme: Option<EvaluationSchemeData>     // Nuevo esquema de evaluación (None si la propuesta no lo modifica)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ProposalAttachment {
+    name: String,                               // Máx. 64 bytes
+    reference: ContentReference,
+    mime_type: String                           // Formato 'tipo/subtipo' (máx. 64 bytes)
+}
+
+#[account]
+#[derive(Default)]
+pub struct Appeal {
+    proposal_id: i32,
+    subject_id: i32,
+    appellant: Pubkey,
+    reason_reference: ContentReference,         // Motivos del recurso
+    filing_timestamp: i64,
+    resolution: AppealResolution,
+    justification_reference: ContentReference,  // Justificación de la resolución
+    resolved_by_high_rank_id: i32,              // -1 mientras no se haya resuelto
+    resolution_timestamp: i64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone)]
+pub struct CoAuthor {
+    authority: Pubkey,
+    user_type: ProposalUserType,
+    share: u8,                                  // Porcentaje de la recompensa (el resto es para el creador)
+    has_signed: bool
+}
+
+#[account]
+#[derive(Default)]
+pub struct ProposalCategoryConfig {
+    category: ProposalCategory,
+    approval_threshold: u8,                     // Porcentaje de votos a favor necesario (51 - 100)
+    high_rank_validation_required: bool,
+    last_update_timestamp: i64,
+    updated_by_high_rank_id: i32
+}
+
+#[account]
+#[derive(Default)]
+pub struct Reputation {
+    authority: Pubkey,
+    granted_proposals: u32,
+    co_authorships: u32,
+    on_time_deliveries: u32,
+    late_deliveries: u32,
+    score: u32,
+    subjects: Vec<SubjectReputation>            // Máx. 20 asignaturas
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Default)]
+pub struct SubjectReputation {
+    subject_code: u32,
+    granted_proposals: u16,
+    co_authorships: u16,
+    on_time_deliveries: u16,
+    late_deliveries: u16
+}
+
+impl Reputation {
+
+    fn record(&mut self, subject_code: u32, outcome: ReputationOutcome) {
+
+        let position = match self.subjects.iter().position(|x| x.subject_code == subject_code) {
+            Some(position) => Some(position),
+            None if self.subjects.len() < MAXIMUM_REPUTATION_SUBJECTS => {
+                self.subjects.push(SubjectReputation { subject_code, ..Default::default() });
+                Some(self.subjects.len() - 1)
+            },
+            None => None
+        };
+
+        match outcome {
+            ReputationOutcome::GrantedProposal => self.granted_proposals += 1,
+            ReputationOutcome::CoAuthorship => self.co_authorships += 1,
+            ReputationOutcome::OnTimeDelivery => self.on_time_deliveries += 1,
+            ReputationOutcome::LateDelivery => self.late_deliveries += 1
+        }
+
+        if let Some(position) = position {
+            let subject_reputation = &mut self.subjects[position];
+            match outcome {
+                ReputationOutcome::GrantedProposal => subject_reputation.granted_proposals += 1,
+                ReputationOutcome::CoAuthorship => subject_reputation.co_authorships += 1,
+                ReputationOutcome::OnTimeDelivery => subject_reputation.on_time_deliveries += 1,
+                ReputationOutcome::LateDelivery => subject_reputation.late_deliveries += 1
+            }
+        }
+
+        self.score = self.reputation_score();
+    }
+
+    pub fn reputation_score(&self) -> u32 {
+        (self.granted_proposals * REPUTATION_POINTS_PER_GRANTED_PROPOSAL
+            + self.co_authorships * REPUTATION_POINTS_PER_CO_AUTHORSHIP
+            + self.on_time_deliveries * REPUTATION_POINTS_PER_ON_TIME_DELIVERY)
+            .saturating_sub(self.late_deliveries * REPUTATION_POINTS_PER_LATE_DELIVERY)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct ProfessorRewardConfig {
+    reward_tokens: u8,                          // Tokens (enteros) por cada proyecto docente aceptado
+    early_delivery_bonus_tokens: u8,
+    early_delivery_period: i64,                 // Antelación mínima sobre el 'ending_timestamp' para recibir el bonus
+    last_update_timestamp: i64,
+    updated_by_high_rank_id: i32
+}
+
+#[account]
+#[derive(Default)]
+pub struct PenaltyPolicy {
+    grace_period: i64,                          // Retraso (en segundos) que no se penaliza
+    penalty_interval: i64,                      // Cada intervalo de retraso empezado suma 'penalty_per_interval'
+    penalty_per_interval: u8,
+    maximum_penalty: u8,                        // Máximo por cada retraso
+    decay_per_term: u8,                         // Penalizaciones perdonadas por cada periodo transcurrido
+    term_duration: i64,
+    last_update_timestamp: i64,
+    updated_by_high_rank_id: i32
+}
+
+#[account]
+#[derive(Default)]
+pub struct TeachingProjectVersion {
+    subject_code: u32,
+    version: u32,
+    teaching_project_reference: ContentReference,
+    previous_version: u32,                      // 0 --> proyecto docente con el que se creó la asignatura
+    previous_reference: ContentReference,       // Proyecto docente sustituido
+    proposal_id: i32,                           // Propuesta que dio lugar a la nueva versión
+    approved_by_high_rank_id: i32,              // -1 si la categoría de la propuesta no requiere validación
+    timestamp: i64
+}
+
+#[account]
+#[derive(Default)]
+pub struct PenaltyRecord {
+    professor_id: i32,
+    professor_proposal_id: i32,
+    subject_code: u32,
+    penalty: u8,
+    delay: i64,                                 // Segundos de retraso sobre el 'ending_timestamp' de la propuesta
+    cause: PenaltyCause,
+    timestamp: i64,
+    voided: bool,
+    void_reference: ContentReference,           // Resolución del recurso
+    voided_by_high_rank_id: i32                 // -1 mientras no se haya anulado
+}
+
+#[account]
+#[derive(Default)]
+pub struct ProposalDepositConfig {
+    deposit_lamports: u64,                      // Bloqueados en el escrow de cada propuesta nueva
+    maximum_open_proposals_per_user: u8,        // Propuestas abiertas simultáneamente por usuario y asignatura
+    last_update_timestamp: i64,
+    updated_by_high_rank_id: i32
+}
+
+#[account]
+#[derive(Default)]
+pub struct ProposalDeposit {
+    proposal_id: i32,
+    subject_id: i32,
+    depositor: Pubkey,
+    amount: u64                                 // Lamports bloqueados además del rent del escrow
+}
+
+#[account]
+#[derive(Default)]
+pub struct ProposalIndex {
+    subject_code: u32,
+    cooldown: i64,                              // Segundos tras el fin de la votación durante los que se rechazan duplicados
+    entries: Vec<ProposalIndexEntry>            // Máx. 50 entradas de 80 bytes (hash de 64 + id + timestamp) --> 4 KB
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ProposalIndexEntry {
+    proposal_id: i32,                           // -1 una vez eliminada o retirada la propuesta
+    ending_timestamp: i64,
+    content_hash: String                        // sha256(título normalizado | resumen normalizado | hash del cuerpo)
+}
+
+#[account]
+#[derive(Default)]
+pub struct OpenProposalsCounter {
+    authority: Pubkey,
+    subject_code: u32,
+    open_proposals: u8
+}
+
+#[account]
+#[derive(Default)]
+pub struct Comment {
+    id: i32,
+    proposal_id: i32,
+    subject_id: i32,
+    author: Pubkey,
+    author_user_type: ProposalUserType,
+    parent_comment_id: i32,                     // -1 si es un comentario de primer nivel
+    content_reference: Option<ContentReference>,    // Vacío si sólo tiene texto
+    text: String,                               // Máx. 280 bytes
+    publishing_timestamp: i64
+}
+
+#[account]
+#[derive(Default)]
+pub struct Amendment {
+    id: i32,
+    proposal_id: i32,
+    subject_id: i32,
+    author: Pubkey,
+    author_user_type: ProposalUserType,
+    title: String,
+    content: String,
+    content_reference: ContentReference,
+    content_sha256: String,
+    proposed_over_version: u16,                 // Versión de la propuesta sobre la que se redactó la enmienda
+    publishing_timestamp: i64,
+    state: AmendmentState
 }
 
 #[account]
@@ -1617,8 +5100,62 @@ pub struct ProfessorProposal {
     name: String,
     publishing_timestamp: i64,
     ending_timestamp: i64,
-    teaching_project_reference: String,
-    state: ProfessorProposalState
+    teaching_project_reference: ContentReference,
+    state: ProfessorProposalState,
+    review_rounds: Vec<ReviewRound>,            // Historial de decisiones del HighRank (una por cada revisión)
+    assigned_professor_id: i32,                 // -1 mientras no se haya asignado ningún profesor
+    collaborators: Vec<i32>,                    // Máx. 5 profesores añadidos por el profesor asignado
+    extensions: Vec<DeadlineExtension>,         // Máx. 3 solicitudes de prórroga (sólo una pendiente a la vez)
+    delivered_by: Pubkey,                       // Profesor que entregó el último proyecto docente
+    delivery_timestamp: i64,
+    draft_revisions: Vec<DraftRevision>,        // Máx. 10 borradores por ronda de revisión (enviados por los profesores asignados)
+    sign_offs: Vec<i32>,                        // Profesores que han aprobado el último borrador
+    section_references: Vec<SectionReference>   // Una por cada sección declarada por la propuesta
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DraftRevision {
+    professor_id: i32,
+    reference: ContentReference,
+    timestamp: i64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DeadlineExtension {
+    professor_id: i32,
+    reason_reference: ContentReference,         // Motivo (baja médica, periodo de exámenes...)
+    previous_ending_timestamp: i64,
+    requested_ending_timestamp: i64,
+    request_timestamp: i64,
+    state: ExtensionState,
+    resolved_by_high_rank_id: i32               // -1 mientras no se haya resuelto
+}
+
+impl ProfessorProposal {
+    fn is_assigned_to(&self, professor_id: i32) -> bool {
+        self.assigned_professor_id == professor_id || self.collaborators.contains(&professor_id)
+    }
+
+    // The assigned professor and every collaborator must sign off the last draft
+    fn draft_is_signed_off(&self) -> bool {
+        self.sign_offs.contains(&self.assigned_professor_id) && self.collaborators.iter().all(|x| self.sign_offs.contains(x))
+    }
+}
+
+// Round-robin state for the assignment of the professor proposals of a subject
+#[account]
+#[derive(Default)]
+pub struct ProfessorAssignment {
+    subject_code: u32,
+    last_assigned_professor_id: i32
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReviewRound {
+    high_rank_id: i32,
+    decision: HighRankDecision,
+    feedback_reference: ContentReference,       // Justificación de la decisión
+    timestamp: i64
 }
 
 
@@ -1632,6 +5169,72 @@ pub struct CodeIdSubjectRelation {
     code_value: Vec<AdditionalSubjectInfo>
 }
 
+#[account]
+#[derive(Default)]
+pub struct SubjectDependencyGraph {
+    dependencies: Vec<SubjectDependency>
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub struct SubjectDependency {
+    subject_code: u32,
+    required_code: u32,
+    kind: SubjectDependencyKind
+}
+
+impl SubjectDependencyGraph {
+
+    fn add_dependencies(&mut self, subject_code: u32, prerequisites: &[u32], corequisites: &[u32]) {
+
+        for required_code in prerequisites {
+            self.dependencies.push(SubjectDependency { subject_code, required_code: *required_code, kind: SubjectDependencyKind::Prerequisite });
+        }
+
+        for required_code in corequisites {
+            self.dependencies.push(SubjectDependency { subject_code, required_code: *required_code, kind: SubjectDependencyKind::Corequisite });
+        }
+    }
+
+    fn dependent_subjects(&self, required_code: u32) -> Vec<u32> {
+
+        let mut dependent_subjects: Vec<u32> = vec![];
+
+        for dependency in self.dependencies.iter().filter(|x| x.required_code == required_code) {
+            if !dependent_subjects.contains(&dependency.subject_code) {
+                dependent_subjects.push(dependency.subject_code);
+            }
+        }
+
+        dependent_subjects
+    }
+
+    // Depth-first search through the prerequisites' chain looking for the subject itself
+    fn prerequisites_form_cycle(&self, subject_code: u32, prerequisites: &[u32]) -> bool {
+
+        let mut pending_codes: Vec<u32> = prerequisites.to_vec();
+        let mut visited_codes: Vec<u32> = vec![];
+
+        while let Some(code) = pending_codes.pop() {
+
+            if code == subject_code { return true }
+            if visited_codes.contains(&code) { continue }
+            visited_codes.push(code);
+
+            for dependency in self.dependencies.iter().filter(|x| x.subject_code == code && x.kind == SubjectDependencyKind::Prerequisite) {
+                pending_codes.push(dependency.required_code);
+            }
+        }
+
+        false
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct Treasury {
+    total_slashed_lamports: u64
+}
+
 #[account]
 #[derive(Default)]
 pub struct SystemInitialization {
@@ -1758,28 +5361,117 @@ pub enum ProposalState {
     WaitingForHighRank,
     Rejected,
     Accepted,
-    AcceptedAndTokensGranted
+    AcceptedAndTokensGranted,
+    UnderAppeal,
+    Discussion,
+    RejectedByHighRank
+}
+
+impl fmt::Display for ProposalState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProposalState::Accepted => write!(f, "Accepted"),
+            ProposalState::Rejected => write!(f, "Rejected"),
+            ProposalState::WaitingForTeacher=> write!(f, "WaitingForTeacher"),
+            ProposalState::WaitingForHighRank => write!(f, "WaitingForHighRank"),
+            ProposalState::VotationInProgress=> write!(f, "VotationInProgress"),
+            ProposalState::AcceptedAndTokensGranted=> write!(f, "AcceptedAndTokensGranted"),
+            ProposalState::UnderAppeal => write!(f, "UnderAppeal"),
+            ProposalState::Discussion => write!(f, "Discussion"),
+            ProposalState::RejectedByHighRank => write!(f, "RejectedByHighRank")
+        }
+    }
+}
+
+impl ProposalState {
+    // Proposals can be commented, amended or voted while they are under discussion or being voted
+    pub fn is_open_to_participation(&self) -> bool {
+        ProposalState::Discussion == *self || ProposalState::VotationInProgress == *self
+    }
+}
+
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone)]
+pub enum ProfessorProposalState { 
+    #[default]
+    Pending,
+    Complete,
+    Rejected
+}
+
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum ReputationOutcome {
+    GrantedProposal,
+    CoAuthorship,
+    OnTimeDelivery,
+    LateDelivery
+}
+
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum PenaltyCause {
+    #[default]
+    LateTeachingProjectUpdate
+}
+
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum ExtensionState {
+    Pending,
+    Approved,
+    Denied
+}
+
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum HighRankDecision {
+    Accept,
+    RequestChanges,
+    RejectFinal
+}
+
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum ProposalCategory {
+    EvaluationCriteria,
+    Bibliography,
+    Schedule,
+    Competences,
+    #[default]
+    Other
+}
+
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum TeachingProjectSection {
+    Objectives,
+    Contents,
+    Methodology,
+    Evaluation,
+    Bibliography,
+    Schedule
+}
+
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum StorageBackend {
+    #[default]
+    Ipfs,
+    Arweave
 }
 
-impl fmt::Display for ProposalState {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ProposalState::Accepted => write!(f, "Accepted"),
-            ProposalState::Rejected => write!(f, "Rejected"),
-            ProposalState::WaitingForTeacher=> write!(f, "WaitingForTeacher"),
-            ProposalState::WaitingForHighRank => write!(f, "WaitingForHighRank"),
-            ProposalState::VotationInProgress=> write!(f, "VotationInProgress"),
-            ProposalState::AcceptedAndTokensGranted=> write!(f, "AcceptedAndTokensGranted")
-        }
-    }
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum AppealResolution {
+    #[default]
+    Pending,
+    VotationReopened,
+    RejectionConfirmed
 }
 
 #[derive(Default)]
-#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone)]
-pub enum ProfessorProposalState { 
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum AmendmentState {
     #[default]
     Pending,
-    Complete
+    Accepted
 }
 
 #[derive(Default)]
@@ -1806,6 +5498,40 @@ pub enum SubjectCourse {
     Nineth
 }
 
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone)]
+pub enum AcademicEntity {
+    Degree,
+    Specialty,
+    Subject
+}
+
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone, PartialEq)]
+pub enum SubjectDependencyKind {
+    #[default]
+    Prerequisite,
+    Corequisite
+}
+
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone)]
+pub enum SubjectSemester {
+    #[default]
+    NotDefined,
+    First,
+    Second,
+    Annual
+}
+
+#[derive(Default)]
+#[derive(AnchorSerialize,AnchorDeserialize,Copy,Clone)]
+pub enum SubjectModality {
+    #[default]
+    InPerson,
+    Online,
+    Hybrid
+}
+
 
 //----------------Errors-----------------//
 #[error_code]
@@ -1847,8 +5573,239 @@ pub enum ErrorCode {
     #[msg("System has not been initializated by a HighRank yet")]
     SystemIsNotInitializated,
     
-    #[msg("Incorrect Teaching Project reference for IPFS")]
-    IncorrectTeachingProjectReference
+    #[msg("Incorrect Teaching Project reference")]
+    IncorrectTeachingProjectReference,
+
+    #[msg("Incorrect degree's id submitted")]
+    IncorrectDegreeId,
+
+    #[msg("Incorrect specialty's id submitted")]
+    IncorrectSpecialtyId,
+
+    #[msg("Subject is already offered in the degree and specialty submitted")]
+    SubjectAlreadyOfferedInDegree,
+
+    #[msg("Subject is not cross-listed in the degree and specialty submitted")]
+    CrossListingNotFound,
+
+    #[msg("ECTS credits must be greater than 0 and not exceed the maximum allowed")]
+    IncorrectEctsCredits,
+
+    #[msg("Incorrect course number submitted")]
+    IncorrectCourseNumber,
+
+    #[msg("Incorrect teaching language code submitted")]
+    IncorrectTeachingLanguage,
+
+    #[msg("A subject cannot depend on itself nor declare the same dependency twice")]
+    IncorrectSubjectDependency,
+
+    #[msg("Prerequisite or corequisite subject not found")]
+    SubjectDependencyNotFound,
+
+    #[msg("Prerequisites submitted would form a cycle")]
+    SubjectDependencyCycle,
+
+    #[msg("Subjects' dependency graph is full")]
+    SubjectDependencyGraphIsFull,
+
+    #[msg("No more ids available in the IdHandler")]
+    IdOverflow,
+
+    #[msg("Batch must contain between 1 and the maximum number of entities allowed")]
+    IncorrectBatchSize,
+
+    #[msg("Accounts passed do not match the entities of the batch")]
+    IncorrectBatchAccounts,
+
+    #[msg("One of the accounts of the batch already exists")]
+    BatchAccountAlreadyExists,
+
+    #[msg("Incorrect faculty's id submitted")]
+    IncorrectFacultyId,
+
+    #[msg("Name exceeds the maximum length allowed")]
+    NameIsTooLong,
+
+    #[msg("Only the creator of the proposal can carry out this action")]
+    UserIsNotTheProposalCreator,
+
+    #[msg("Proposal has already been voted")]
+    ProposalHasAlreadyBeenVoted,
+
+    #[msg("Incorrect reference for the proposal's content")]
+    IncorrectProposalContentReference,
+
+    #[msg("Proposal's content hash must be a SHA-256 hexadecimal digest")]
+    IncorrectProposalContentHash,
+
+    #[msg("Incorrect proposal's attachment")]
+    IncorrectProposalAttachment,
+
+    #[msg("Proposal exceeds the maximum number of attachments")]
+    TooManyProposalAttachments,
+
+    #[msg("Comments must contain a short text or a valid content reference")]
+    IncorrectCommentContent,
+
+    #[msg("Parent comment not found in the proposal")]
+    CommentNotFound,
+
+    #[msg("Amendment is not pending")]
+    AmendmentIsNotPending,
+
+    #[msg("Proposal's tags must be unique and not empty")]
+    IncorrectProposalTags,
+
+    #[msg("Approval threshold must be a percentage greater than 50")]
+    IncorrectApprovalThreshold,
+
+    #[msg("At least one open proposal per user must be allowed")]
+    IncorrectMaximumOpenProposals,
+
+    #[msg("User has reached the maximum number of open proposals in the subject")]
+    TooManyOpenProposals,
+
+    #[msg("Proposal's deposit cannot be settled while the votation is open")]
+    ProposalDepositCannotBeSettledYet,
+
+    #[msg("Proposal's deposit must be settled before deleting the proposal")]
+    ProposalDepositIsNotSettled,
+
+    #[msg("An identical proposal is open or was closed within the cooldown period")]
+    DuplicatedProposal,
+
+    #[msg("Duplicate proposal cooldown cannot be negative")]
+    IncorrectDuplicateProposalCooldown,
+
+    #[msg("User cannot be invited as co-author of the proposal")]
+    IncorrectCoAuthor,
+
+    #[msg("Proposal has reached the maximum number of co-authors")]
+    TooManyCoAuthors,
+
+    #[msg("Co-authors' shares must be positive and leave part of the reward to the creator")]
+    IncorrectCoAuthorShare,
+
+    #[msg("User has not been invited as co-author or has already signed the proposal")]
+    CoAuthorNotInvited,
+
+    #[msg("A token account must be submitted for every co-author that signed the proposal")]
+    IncorrectCoAuthorAccounts,
+
+    #[msg("The appeal window of the proposal is closed")]
+    AppealWindowIsClosed,
+
+    #[msg("Incorrect reference for the appeal")]
+    IncorrectAppealReference,
+
+    #[msg("Proposal is not under appeal")]
+    ProposalIsNotUnderAppeal,
+
+    #[msg("The new deadline of the votation must be in the future")]
+    IncorrectVotationDeadline,
+
+    #[msg("Incorrect reference for the feedback of the review")]
+    IncorrectFeedbackReference,
+
+    #[msg("The votation must start between the publication of the proposal and the end of the maximum discussion period")]
+    IncorrectVotingStartTime,
+
+    #[msg("The votation of the proposal has not started yet")]
+    VotationHasNotStarted,
+
+    #[msg("Professor is not assigned to the work of the proposal")]
+    ProfessorIsNotAssigned,
+
+    #[msg("A professor has already been assigned to the work of the proposal")]
+    ProfessorIsAlreadyAssigned,
+
+    #[msg("User is not the coordinator of the subject")]
+    UserIsNotTheSubjectCoordinator,
+
+    #[msg("Every professor of the subject must be submitted (only once) to assign the work by round-robin")]
+    IncorrectSubjectProfessorAccounts,
+
+    #[msg("Incorrect collaborator for the work of the proposal")]
+    IncorrectCollaborator,
+
+    #[msg("Maximum number of collaborators reached")]
+    TooManyCollaborators,
+
+    #[msg("Incorrect reference for the reason of the extension")]
+    IncorrectExtensionReference,
+
+    #[msg("The requested deadline must be later than the current one")]
+    IncorrectExtensionDeadline,
+
+    #[msg("There is already a pending extension request for the proposal")]
+    ExtensionRequestIsPending,
+
+    #[msg("Maximum number of extension requests reached")]
+    TooManyExtensionRequests,
+
+    #[msg("There is no pending extension request for the proposal")]
+    ExtensionRequestNotFound,
+
+    #[msg("Incorrect penalty policy")]
+    IncorrectPenaltyPolicy,
+
+    #[msg("The penalty has already been voided")]
+    PenaltyIsAlreadyVoided,
+
+    #[msg("Incorrect reference for the resolution of the appeal against the penalty")]
+    IncorrectPenaltyVoidReference,
+
+    #[msg("Incorrect reputation account for the user")]
+    IncorrectReputationAccount,
+
+    #[msg("Incorrect professor reward config")]
+    IncorrectProfessorRewardConfig,
+
+    #[msg("No draft of the teaching project has been submitted")]
+    DraftRevisionNotFound,
+
+    #[msg("Maximum number of draft revisions reached")]
+    TooManyDraftRevisions,
+
+    #[msg("Professor has already signed off the draft")]
+    ProfessorHasAlreadySignedOff,
+
+    #[msg("The draft must be signed off by every professor assigned to the work")]
+    MissingDraftSignOffs,
+
+    #[msg("Sections of the teaching project can not be repeated")]
+    IncorrectTeachingProjectSections,
+
+    #[msg("Every section declared by the proposal needs exactly one valid reference with its sha256")]
+    IncorrectSectionReferences,
+
+    #[msg("Evaluation schemes need from 1 to 10 components with a unique name (max. 32 bytes) and a weight between 1 and 100")]
+    IncorrectEvaluationComponents,
+
+    #[msg("The weights of the evaluation components must sum to 100")]
+    EvaluationWeightsDoNotSumTo100,
+
+    #[msg("Grades must be between 0 and 100 tenths and the resit can not be capped below the passing grade")]
+    IncorrectEvaluationGrades,
+
+    #[msg("Incorrect evaluation scheme account")]
+    IncorrectEvaluationSchemeAccount,
+
+    #[msg("The same subject code appears more than once in the batch")]
+    DuplicatedSubjectCodeInBatch,
+
+    #[msg("The proposal has already been voted, so the votes must be reset to accept an amendment")]
+    AmendmentRequiresVoteReset,
+
+    #[msg("The amendment was drafted over a previous version of the proposal")]
+    AmendmentIsOutdated,
+
+    #[msg("The professor, the mint and the token account are required to accept the teaching project")]
+    IncorrectProfessorRewardAccounts,
+
+    #[msg("The deposit of the proposal has already been settled, so its votation can not be reopened")]
+    ProposalDepositIsSettled
 }
 
 
@@ -1865,3 +5822,324 @@ pub struct NewProfessorProposalCreated {
     pub proposal_id: i32,
     pub professor_proposal_id: i32
 }
+
+#[event]
+pub struct SubjectCrossListed {
+    pub subject_id: i32,
+    pub subject_code: u32,
+    pub degree_id: i32,
+    pub specialty_id: i32
+}
+
+#[event]
+pub struct SubjectMetadataUpdated {
+    pub subject_id: i32,
+    pub version: u8
+}
+
+#[event]
+pub struct ProposalAffectsDownstreamSubjects {
+    pub proposal_id: i32,
+    pub subject_id: i32,
+    pub downstream_subjects: Vec<u32>
+}
+
+#[event]
+pub struct AcademicCatalogueBatchCreated {
+    pub entity: AcademicEntity,
+    pub ids: Vec<i32>
+}
+
+#[event]
+pub struct ProposalEdited {
+    pub proposal_id: i32,
+    pub subject_id: i32,
+    pub version: u16,
+    pub previous_hash: String,
+    pub new_hash: String
+}
+
+#[event]
+pub struct ProposalWithdrawn {
+    pub proposal_id: i32,
+    pub subject_id: i32
+}
+
+#[event]
+pub struct NewCommentPublished {
+    pub proposal_id: i32,
+    pub comment_id: i32,
+    pub parent_comment_id: i32
+}
+
+#[event]
+pub struct NewAmendmentProposed {
+    pub proposal_id: i32,
+    pub amendment_id: i32
+}
+
+#[event]
+pub struct AmendmentAccepted {
+    pub proposal_id: i32,
+    pub amendment_id: i32,
+    pub version: u16,
+    pub votes_reset: bool
+}
+
+#[event]
+pub struct ProposalCategoryConfigUpdated {
+    pub category: ProposalCategory,
+    pub approval_threshold: u8,
+    pub high_rank_validation_required: bool
+}
+
+#[event]
+pub struct ProposalDepositConfigUpdated {
+    pub deposit_lamports: u64,
+    pub maximum_open_proposals_per_user: u8
+}
+
+#[event]
+pub struct ProposalDepositSettled {
+    pub proposal_id: i32,
+    pub subject_id: i32,
+    pub amount: u64,
+    pub slashed: bool
+}
+
+#[event]
+pub struct CoAuthorInvited {
+    pub proposal_id: i32,
+    pub subject_id: i32,
+    pub co_author: Pubkey,
+    pub share: u8
+}
+
+#[event]
+pub struct ProposalCoSigned {
+    pub proposal_id: i32,
+    pub subject_id: i32,
+    pub co_author: Pubkey
+}
+
+#[event]
+pub struct AppealFiled {
+    pub proposal_id: i32,
+    pub subject_id: i32
+}
+
+#[event]
+pub struct AppealResolved {
+    pub proposal_id: i32,
+    pub subject_id: i32,
+    pub resolution: AppealResolution
+}
+
+#[event]
+pub struct ProposalReviewed {
+    pub proposal_id: i32,
+    pub subject_id: i32,
+    pub decision: HighRankDecision,
+    pub round: u8
+}
+
+#[event]
+pub struct ProfessorAssigned {
+    pub proposal_id: i32,
+    pub professor_proposal_id: i32,
+    pub professor_id: i32
+}
+
+#[event]
+pub struct ExtensionRequested {
+    pub professor_proposal_id: i32,
+    pub professor_id: i32,
+    pub requested_ending_timestamp: i64
+}
+
+#[event]
+pub struct ExtensionResolved {
+    pub professor_proposal_id: i32,
+    pub approved: bool,
+    pub ending_timestamp: i64
+}
+
+#[event]
+pub struct PenaltyPolicyUpdated {
+    pub grace_period: i64,
+    pub penalty_interval: i64,
+    pub penalty_per_interval: u8,
+    pub maximum_penalty: u8,
+    pub decay_per_term: u8,
+    pub term_duration: i64
+}
+
+#[event]
+pub struct ProfessorPenalized {
+    pub professor_id: i32,
+    pub professor_proposal_id: i32,
+    pub penalty: u8
+}
+
+#[event]
+pub struct PenaltyVoided {
+    pub professor_id: i32,
+    pub professor_proposal_id: i32,
+    pub penalty: u8
+}
+
+#[event]
+pub struct ProfessorRewardConfigUpdated {
+    pub reward_tokens: u8,
+    pub early_delivery_bonus_tokens: u8,
+    pub early_delivery_period: i64
+}
+
+#[event]
+pub struct ProfessorRewarded {
+    pub professor_proposal_id: i32,
+    pub professor_id: i32,
+    pub reward: u64,
+    pub delivered_early: bool
+}
+
+#[event]
+pub struct DraftRevisionSubmitted {
+    pub professor_proposal_id: i32,
+    pub professor_id: i32,
+    pub revision: u8
+}
+
+#[event]
+pub struct DraftSignedOff {
+    pub professor_proposal_id: i32,
+    pub professor_id: i32,
+    pub draft_is_signed_off: bool
+}
+
+#[event]
+pub struct TeachingProjectVersionRecorded {
+    pub subject_code: u32,
+    pub version: u32,
+    pub proposal_id: i32,
+    pub approved_by_high_rank_id: i32
+}
+
+#[event]
+pub struct TeachingProjectSectionsUpdated {
+    pub subject_code: u32,
+    pub sections: Vec<TeachingProjectSection>
+}
+
+#[event]
+pub struct EvaluationSchemeUpdated {
+    pub subject_code: u32,
+    pub proposal_id: i32
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const DEADLINE: i64 = 1_700_000_000;
+    const WEEK: i64 = 604800;
+
+    fn penalty_policy(grace_period: i64, penalty_interval: i64, penalty_per_interval: u8, maximum_penalty: u8, decay_per_term: u8, term_duration: i64) -> PenaltyPolicy {
+        PenaltyPolicy { grace_period, penalty_interval, penalty_per_interval, maximum_penalty, decay_per_term, term_duration, last_update_timestamp: 0, updated_by_high_rank_id: -1 }
+    }
+
+    fn professor_proposal_with_deadline(ending_timestamp: i64) -> ProfessorProposal {
+        ProfessorProposal { ending_timestamp, ..ProfessorProposal::default() }
+    }
+
+    #[test]
+    fn delivery_within_the_grace_period_is_not_penalized() {
+        let policy = penalty_policy(WEEK, WEEK, 1, 4, 1, 26 * WEEK);
+        let professor_proposal = professor_proposal_with_deadline(DEADLINE);
+
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, DEADLINE - 1), (0, 0));
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, DEADLINE + WEEK), (0, 0));
+    }
+
+    #[test]
+    fn every_started_interval_of_delay_is_penalized() {
+        let policy = penalty_policy(WEEK, WEEK, 2, 10, 1, 26 * WEEK);
+        let professor_proposal = professor_proposal_with_deadline(DEADLINE);
+
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, DEADLINE + WEEK + 1), (2, WEEK + 1));
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, DEADLINE + 2 * WEEK), (2, 2 * WEEK));
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, DEADLINE + 2 * WEEK + 1), (4, 2 * WEEK + 1));
+    }
+
+    #[test]
+    fn penalty_of_a_single_delay_is_capped() {
+        let policy = penalty_policy(0, WEEK, 1, 4, 1, 26 * WEEK);
+        let professor_proposal = professor_proposal_with_deadline(DEADLINE);
+
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, DEADLINE + 52 * WEEK).0, 4);
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, i64::MAX).0, 4);
+    }
+
+    #[test]
+    fn default_policy_penalizes_every_started_half_of_the_votation_period() {
+        let professor_proposal = professor_proposal_with_deadline(DEADLINE);
+
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &default_penalty_policy(), DEADLINE + 1).0, DEFAULT_PENALTY_PER_INTERVAL);
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &default_penalty_policy(), DEADLINE + DEFAULT_PENALTY_INTERVAL + 1).0, 2 * DEFAULT_PENALTY_PER_INTERVAL);
+    }
+
+    #[test]
+    fn punishments_decay_for_every_elapsed_term() {
+        let policy = penalty_policy(0, WEEK, 1, 4, 1, 26 * WEEK);
+        let mut professor = Professor { punishments: 5, ..Professor::default() };
+
+        // The first evaluation only starts counting the terms
+        apply_penalty_decay(&mut professor, &policy, DEADLINE);
+        assert_eq!((professor.punishments, professor.last_penalty_decay_timestamp), (5, DEADLINE));
+
+        apply_penalty_decay(&mut professor, &policy, DEADLINE + 26 * WEEK - 1);
+        assert_eq!(professor.punishments, 5);
+
+        // The part of the term that has not elapsed yet is kept for the next evaluation
+        apply_penalty_decay(&mut professor, &policy, DEADLINE + 52 * WEEK + 1);
+        assert_eq!((professor.punishments, professor.last_penalty_decay_timestamp), (3, DEADLINE + 52 * WEEK));
+
+        apply_penalty_decay(&mut professor, &policy, DEADLINE + 520 * WEEK);
+        assert_eq!(professor.punishments, 0);
+    }
+
+    #[test]
+    fn extreme_policy_periods_do_not_overflow() {
+        let professor_proposal = professor_proposal_with_deadline(DEADLINE);
+
+        // A single interval (or term) covers any delay
+        let policy = penalty_policy(0, i64::MAX, 1, 4, 1, i64::MAX);
+        assert_eq!(evaluating_professor_penalty(&professor_proposal, &policy, i64::MAX), (1, i64::MAX - DEADLINE));
+
+        let mut professor = Professor { punishments: 5, last_penalty_decay_timestamp: DEADLINE, ..Professor::default() };
+        apply_penalty_decay(&mut professor, &policy, i64::MAX);
+        assert_eq!((professor.punishments, professor.last_penalty_decay_timestamp), (5, DEADLINE));
+
+        // A grace period longer than any delay is never penalized
+        let policy = penalty_policy(i64::MAX, WEEK, 1, 4, 1, WEEK);
+        assert_eq!(evaluating_professor_penalty(&professor_proposal_with_deadline(i64::MIN), &policy, i64::MAX), (0, 0));
+
+        // The longest delay is capped by the policy
+        let policy = penalty_policy(0, 1, 1, 4, 1, 1);
+        assert_eq!(evaluating_professor_penalty(&professor_proposal_with_deadline(i64::MIN), &policy, i64::MAX).0, 4);
+
+        apply_penalty_decay(&mut professor, &policy, i64::MAX);
+        assert_eq!((professor.punishments, professor.last_penalty_decay_timestamp), (0, i64::MAX));
+    }
+
+    #[test]
+    fn reputation_score_mitigates_penalties() {
+        let reputation = Reputation { granted_proposals: 3, on_time_deliveries: 3, late_deliveries: 2, ..Reputation::default() };
+        assert_eq!(reputation.reputation_score(), 50);
+
+        assert_eq!(mitigate_penalty(3, reputation.reputation_score()), 2);
+        assert_eq!(mitigate_penalty(0, reputation.reputation_score()), 0);
+        assert_eq!(mitigate_penalty(3, reputation.reputation_score() - 1), 3);
+    }
+}
@@ -17,6 +17,12 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddres
 chai.use(chaiAsPromised);
 const confirmOptions: ConfirmOptions = { commitment: "confirmed" };
 
+// IPFS reference of the full body of the proposals (the on-chain content is just a short summary)
+const DEFAULT_PROPOSAL_CONTENT_REFERENCE = "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs";
+
+// Off-chain documents can be referenced by their IPFS CID or by a typed reference (IPFS / Arweave + optional sha256)
+type ContentReferenceLike = string | { backend: any, identifier: string, sha256: string | null };
+
 // helper functions
 const createWallet = async (connection: anchor.web3.Connection, funds: number): Promise<anchor.web3.Keypair> => {
     const wallet = anchor.web3.Keypair.generate();
@@ -140,6 +146,7 @@ const initializeStudent = async (program: Program<TeachingProjectHandler>, autho
 //     const subject_id_generator_pda = await findPDAforIdGenerator(program.programId, "subject")
 //     const code_id_relation_account = await findPDAforCodeIdRelation(program.programId)
 //     const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
+    const treasury_pda = await findPDAforTreasury(program.programId)
 
 //     const result = await program.methods.initializateNewSystem()
 //         .accounts({
@@ -151,6 +158,7 @@ const initializeStudent = async (program: Program<TeachingProjectHandler>, autho
 //             facultyIdHandler: faculty_id_generator_pda,
 //             specialtyIdHandler: specialty_id_generator_pda,
 //             subjectIdHandler: subject_id_generator_pda,
+            treasury: treasury_pda,
 //             systemProgram: anchor.web3.SystemProgram.programId,
 //         })
 //         .signers([authority])
@@ -232,7 +240,7 @@ const initializeSpecialty = async (program: Program<TeachingProjectHandler>, aut
     return result;
 }
 
-const initializeSubject = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, name: string, degree_id: number, specialty_id: number, course: any, code: number, reference: string): Promise<String> => {
+const initializeSubject = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, name: string, degree_id: number, specialty_id: number, course: any, code: number, reference: ContentReferenceLike, prerequisites: Array<number> = [], corequisites: Array<number> = [], section_references: Array<{ section: any, reference: ContentReferenceLike }> = []): Promise<String> => {
 
     const pda = await findPDAforSubject(program.programId, id)
     const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
@@ -243,8 +251,9 @@ const initializeSubject = async (program: Program<TeachingProjectHandler>, autho
     const systemInitialization = await findPDAforSystemInitialization(program.programId)
     const proposalIdHandlerForSubject = await findPDAforProposalIdGenerator(program.programId, false, code)
     const professorProposalIdHandlerForSubject = await findPDAforProposalIdGenerator(program.programId, true, code)
+    const subject_dependency_graph_pda = await findPDAforSubjectDependencyGraph(program.programId)
 
-    const result = await program.methods.createSubject(name, degree_id, specialty_id, course, code, reference)
+    const result = await program.methods.createSubject(name, degree_id, specialty_id, course, code, toContentReference(reference), prerequisites, corequisites, section_references.map((x) => ({ section: x.section, reference: toContentReference(x.reference) })))
         .accounts({
             authority: authority.publicKey,
             initializationSystemAccount: systemInitialization,
@@ -256,6 +265,7 @@ const initializeSubject = async (program: Program<TeachingProjectHandler>, autho
             codeIdSubjectRelationAccount: code_id_relation_pda,
             proposalIdHandler: proposalIdHandlerForSubject,
             professorProposalIdHandler: professorProposalIdHandlerForSubject,
+            subjectDependencyGraph: subject_dependency_graph_pda,
             systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -264,7 +274,7 @@ const initializeSubject = async (program: Program<TeachingProjectHandler>, autho
     return result;
 }
 
-const initializeProposalByStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number): Promise<String> => {
+const initializeProposalByStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number, content_reference: ContentReferenceLike = DEFAULT_PROPOSAL_CONTENT_REFERENCE, attachments: Array<{ name: string, reference: ContentReferenceLike, mimeType: string }> = [], category: any = { other: {} }, tags: Array<string> = [], voting_starts_at: number = null, sections: Array<any> = [], evaluation_scheme: any = null): Promise<String> => {
 
     const pda = await findPDAforProposal(program.programId, id, subject_code)
     const student_pda = await findPDAforStudent(program.programId, authority.publicKey)
@@ -274,9 +284,16 @@ const initializeProposalByStudent = async (program: Program<TeachingProjectHandl
     const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
     const professor_proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, true, subject_code)
     const code_id_relation_account = await findPDAforCodeIdRelation(program.programId)
+    const subject_dependency_graph_pda = await findPDAforSubjectDependencyGraph(program.programId)
     const systemInitialization = await findPDAforSystemInitialization(program.programId)
 
-    const result = await program.methods.createProposalByStudent(title, content)
+    const proposal_category_config_pda = await findPDAforProposalCategoryConfig(program.programId, category)
+    const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, id, subject_code)
+    const open_proposals_counter_pda = await findPDAforOpenProposalsCounter(program.programId, authority.publicKey, subject_code)
+    const proposal_deposit_config_pda = await findPDAforProposalDepositConfig(program.programId)
+    const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+
+    const result = await program.methods.createProposalByStudent(title, content, toContentReference(content_reference), CryptoJS.SHA256(content).toString(), toAttachments(attachments), category, tags, voting_starts_at === null ? null : new anchor.BN(voting_starts_at), sections, evaluation_scheme)
         .accounts({
             authority: authority.publicKey,
             initializationSystemAccount: systemInitialization,
@@ -288,6 +305,12 @@ const initializeProposalByStudent = async (program: Program<TeachingProjectHandl
             professorProposalAccount: professor_proposal_pda,
             subjectAccount: subject_pda,
             codeIdSubjectRelation: code_id_relation_account,
+            subjectDependencyGraph: subject_dependency_graph_pda,
+            proposalCategoryConfig: proposal_category_config_pda,
+            proposalDeposit: proposal_deposit_pda,
+            openProposalsCounter: open_proposals_counter_pda,
+            proposalDepositConfig: proposal_deposit_config_pda,
+            proposalIndex: proposal_index_pda,
             systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -296,7 +319,7 @@ const initializeProposalByStudent = async (program: Program<TeachingProjectHandl
     return result;
 }
 
-const initializeProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number): Promise<String> => {
+const initializeProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number, content_reference: ContentReferenceLike = DEFAULT_PROPOSAL_CONTENT_REFERENCE, attachments: Array<{ name: string, reference: ContentReferenceLike, mimeType: string }> = [], category: any = { other: {} }, tags: Array<string> = [], voting_starts_at: number = null, sections: Array<any> = [], evaluation_scheme: any = null): Promise<String> => {
 
     const pda = await findPDAforProposal(program.programId, id, subject_code)
     const professor_pda = await findPDAforProfessor(program.programId, authority.publicKey)
@@ -306,11 +329,18 @@ const initializeProposalByProfessor = async (program: Program<TeachingProjectHan
     const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
     const professor_proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, true, subject_code)
     const code_id_relation_account = await findPDAforCodeIdRelation(program.programId)
+    const subject_dependency_graph_pda = await findPDAforSubjectDependencyGraph(program.programId)
     const systemInitialization = await findPDAforSystemInitialization(program.programId)
 
 
 
-    const result = await program.methods.createProposalByProfessor(title, content)
+    const proposal_category_config_pda = await findPDAforProposalCategoryConfig(program.programId, category)
+    const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, id, subject_code)
+    const open_proposals_counter_pda = await findPDAforOpenProposalsCounter(program.programId, authority.publicKey, subject_code)
+    const proposal_deposit_config_pda = await findPDAforProposalDepositConfig(program.programId)
+    const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+
+    const result = await program.methods.createProposalByProfessor(title, content, toContentReference(content_reference), CryptoJS.SHA256(content).toString(), toAttachments(attachments), category, tags, voting_starts_at === null ? null : new anchor.BN(voting_starts_at), sections, evaluation_scheme)
         .accounts({
             authority: authority.publicKey,
             initializationSystemAccount: systemInitialization,
@@ -322,6 +352,12 @@ const initializeProposalByProfessor = async (program: Program<TeachingProjectHan
             professorProposalAccount: professor_proposal_pda,
             subjectAccount: subject_pda,
             codeIdSubjectRelation: code_id_relation_account,
+            subjectDependencyGraph: subject_dependency_graph_pda,
+            proposalCategoryConfig: proposal_category_config_pda,
+            proposalDeposit: proposal_deposit_pda,
+            openProposalsCounter: open_proposals_counter_pda,
+            proposalDepositConfig: proposal_deposit_config_pda,
+            proposalIndex: proposal_index_pda,
             systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -355,21 +391,35 @@ const voteProposalByStudent = async (program: Program<TeachingProjectHandler>, a
     return result;
 }
 
-const updateProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, reference: string): Promise<String> => {
+const updateProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, reference: ContentReferenceLike, section_references: Array<{ section: any, reference: ContentReferenceLike }> = []): Promise<String> => {
 
     const professor_account = await findPDAforProfessor(program.programId, authority.publicKey)
     const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
     const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
     const subject_pda = await findPDAforSubject(program.programId, subject_id)
 
+    // The penalty record is only created if the professor is late (its index is the number of penalties they already have)
+    const professor = await program.account.professor.fetch(professor_account)
+    const penalty_record_pda = await findPDAforPenaltyRecord(program.programId, professor.id, professor.numberOfPenalties)
+    const penalty_policy_pda = await findPDAforPenaltyPolicy(program.programId)
 
-    const result = await program.methods.updateProposalByProfessor(reference)
+    // A new version of the teaching project is recorded if the proposal does not require HighRank validation
+    const subject = await program.account.subject.fetch(subject_pda)
+    const teaching_project_version_pda = await findPDAforTeachingProjectVersion(program.programId, subject_code, subject.teachingProjectVersion + 1)
+
+    const result = await program.methods.updateProposalByProfessor(toContentReference(reference), section_references.map((x) => ({ section: x.section, reference: toContentReference(x.reference) })))
         .accounts({
             authority: authority.publicKey,
             professorAccount: professor_account,
             proposalAccount: proposal_pda,
             professorProposalAccount: professor_proposal_pda,
-            subjectAccount: subject_pda
+            subjectAccount: subject_pda,
+            penaltyPolicy: penalty_policy_pda,
+            penaltyRecord: penalty_record_pda,
+            professorReputation: await findPDAforReputation(program.programId, authority.publicKey),
+            teachingProjectVersion: teaching_project_version_pda,
+            evaluationScheme: await findPDAforEvaluationScheme(program.programId, subject_code),
+            systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([authority])
         .rpc(confirmOptions);
@@ -377,21 +427,42 @@ const updateProposalByProfessor = async (program: Program<TeachingProjectHandler
     return result;
 }
 
-const updateProposalByHighRank = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number): Promise<String> => {
+const updateProposalByHighRank = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, decision: any = { accept: {} }, feedback_reference: ContentReferenceLike = DEFAULT_PROPOSAL_CONTENT_REFERENCE): Promise<String> => {
 
     const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
     const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
     const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
     const subject_pda = await findPDAforSubject(program.programId, subject_id)
+    const professor_proposal = await program.account.professorProposal.fetch(professor_proposal_pda)
+    const professor_reputation_pda = await findPDAforReputation(program.programId, professor_proposal.deliveredBy)
 
+    // The professor that delivered the work is rewarded through the same mint authority used for the creators (only if the work is accepted)
+    const is_accepted = decision.accept !== undefined
+    const mint = await findPDAforMint(program.programId)
+    const [mint_authority_pda, mint_authority_bump] = await findPDAforMintAuthority(program.programId, mint, "1111")
+    const professor_token_account = await getAssociatedTokenAddress(mint, professor_proposal.deliveredBy, false)
+    const professor_reward_config_pda = await findPDAforProfessorRewardConfig(program.programId)
 
-    const result = await program.methods.updateProposalByHighRank(true)
+    const subject = await program.account.subject.fetch(subject_pda)
+    const teaching_project_version_pda = await findPDAforTeachingProjectVersion(program.programId, subject_code, subject.teachingProjectVersion + 1)
+
+    const result = await program.methods.updateProposalByHighRank(decision, toContentReference(feedback_reference), "1111", mint_authority_bump)
         .accounts({
             authority: authority.publicKey,
             highRankAccount: high_rank_account,
             proposalAccount: proposal_pda,
             professorProposalAccount: professor_proposal_pda,
-            subjectAccount: subject_pda
+            subjectAccount: subject_pda,
+            professorReputation: professor_reputation_pda,
+            teachingProjectVersion: teaching_project_version_pda,
+            evaluationScheme: await findPDAforEvaluationScheme(program.programId, subject_code),
+            professorAccount: is_accepted ? await findPDAforProfessor(program.programId, professor_proposal.deliveredBy) : null,
+            professorRewardConfig: professor_reward_config_pda,
+            mintAuthorityAccount: is_accepted ? mint_authority_pda : null,
+            mint: is_accepted ? mint : null,
+            professorTokenAccount: is_accepted ? professor_token_account : null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([authority])
         .rpc(confirmOptions);
@@ -399,7 +470,7 @@ const updateProposalByHighRank = async (program: Program<TeachingProjectHandler>
     return result;
 }
 
-const giveCreditToStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, student_creator_public_key: anchor.web3.PublicKey, identifier_code: string, subject_code: number, subject_id: number): Promise<String> => {
+const giveCreditToStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, student_creator_public_key: anchor.web3.PublicKey, identifier_code: string, subject_code: number, subject_id: number, co_authors: Array<anchor.web3.PublicKey> = []): Promise<String> => {
 
     const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
     const proposal_account_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
@@ -411,6 +482,21 @@ const giveCreditToStudent = async (program: Program<TeachingProjectHandler>, aut
     let mintAuthority: { pda: anchor.web3.PublicKey, bump: number };
     mintAuthority = { pda: pda, bump: bump };
 
+    // Token accounts of the signed co-authors followed by their reputation accounts and their user accounts (in the same order they were invited)
+    const co_authors_accounts = []
+    for (const co_author of co_authors) {
+        co_authors_accounts.push({ pubkey: await getAssociatedTokenAddress(mint, co_author, false), isWritable: true, isSigner: false })
+    }
+    for (const co_author of co_authors) {
+        co_authors_accounts.push({ pubkey: await findPDAforReputation(program.programId, co_author), isWritable: true, isSigner: false })
+    }
+    const proposal = await program.account.proposal.fetch(proposal_account_pda)
+    for (const co_author of co_authors) {
+        const is_professor = proposal.coAuthors.find((x) => x.authority.equals(co_author)).userType.professor !== undefined
+        const user_account_pda = is_professor ? await findPDAforProfessor(program.programId, co_author) : await findPDAforStudent(program.programId, co_author)
+        co_authors_accounts.push({ pubkey: user_account_pda, isWritable: true, isSigner: false })
+    }
+
     const associatedTokenAccount = await getAssociatedTokenAddress(mint, creator_account_pda, true);
 
     const result = await program.methods.giveCreditsToWinningStudent("1111", subject_code, mintAuthority.bump)
@@ -420,6 +506,7 @@ const giveCreditToStudent = async (program: Program<TeachingProjectHandler>, aut
             proposalAccount: proposal_account_pda,
             creatorAccount: creator_account_pda,
             tokenAccount: associatedTokenAccount,
+            creatorReputation: await findPDAforReputation(program.programId, student_creator_public_key),
             mintAuthorityAccount: mintAuthority.pda,
             mint: mint,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -428,6 +515,7 @@ const giveCreditToStudent = async (program: Program<TeachingProjectHandler>, aut
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
 
         })
+        .remainingAccounts(co_authors_accounts)
         .signers([authority])
         .rpc();
 
@@ -441,6 +529,11 @@ const deleteRejectedProposalByHighRank = async (program: Program<TeachingProject
     const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
     const subject_pda = await findPDAforSubject(program.programId, subject_id)
     const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
+    const proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, false, subject_code)
+    const professor_proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, true, subject_code)
+    const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, proposal_id, subject_code)
+    const appeal_pda = await findPDAforAppeal(program.programId, proposal_id, subject_code)
+    const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
 
     const result = await program.methods.deleteRejectedProposalAccount()
         .accounts({
@@ -449,6 +542,11 @@ const deleteRejectedProposalByHighRank = async (program: Program<TeachingProject
             proposalAccount: proposal_pda,
             professorProposalAccount: professor_proposal_pda,
             subjectAccount: subject_pda,
+            proposalIdHandler: proposal_id_handler,
+            professorProposalIdHandler: professor_proposal_id_handler,
+            proposalDeposit: proposal_deposit_pda,
+            appealAccount: appeal_pda,
+            proposalIndex: proposal_index_pda,
         })
         .signers([authority])
         .rpc();
@@ -568,6 +666,121 @@ const findPDAforCodeIdRelation = async (programId: anchor.web3.PublicKey): Promi
     return pda;
 }
 
+const findPDAforSubjectDependencyGraph = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("subjectDependencyGraph")],
+        programId
+    );
+    return pda;
+}
+
+// Seed of the category config --> index of the variant in the 'ProposalCategory' enum
+const PROPOSAL_CATEGORIES = ["evaluationCriteria", "bibliography", "schedule", "competences", "other"];
+
+const findPDAforProposalCategoryConfig = async (programId: anchor.web3.PublicKey, category: any): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("proposalCategoryConfig"), Buffer.from([PROPOSAL_CATEGORIES.indexOf(Object.keys(category)[0])])],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforTreasury = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("treasury")],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforReputation = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("reputation"), authority.toBytes()],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforProfessorRewardConfig = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("professorRewardConfig")],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforPenaltyPolicy = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("penaltyPolicy")],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforPenaltyRecord = async (programId: anchor.web3.PublicKey, professor_id: number, penalty_index: number): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("penaltyRecord"), numberToLEBytes(professor_id), numberToLEBytes(penalty_index)],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforTeachingProjectVersion = async (programId: anchor.web3.PublicKey, subject_code: number, version: number): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("teachingProjectVersion"), numberToLEBytes(subject_code), numberToLEBytes(version)],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforEvaluationScheme = async (programId: anchor.web3.PublicKey, subject_code: number): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("evaluationScheme"), numberToLEBytes(subject_code)],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforProposalDepositConfig = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("proposalDepositConfig")],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforProposalDeposit = async (programId: anchor.web3.PublicKey, proposal_id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("proposalDeposit"), numberToLEBytes(proposal_id), numberToLEBytes(subject_code)],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforOpenProposalsCounter = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey, subject_code: number): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("openProposals"), authority.toBytes(), numberToLEBytes(subject_code)],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforAppeal = async (programId: anchor.web3.PublicKey, proposal_id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("appeal"), numberToLEBytes(proposal_id), numberToLEBytes(subject_code)],
+        programId
+    );
+    return pda;
+}
+
+const findPDAforProposalIndex = async (programId: anchor.web3.PublicKey, subject_code: number): Promise<anchor.web3.PublicKey> => {
+    const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [utf8.encode("proposalIndex"), numberToLEBytes(subject_code)],
+        programId
+    );
+    return pda;
+}
+
 const findPDAforSystemInitialization = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
     const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
         [utf8.encode("systemInitialization")],
@@ -649,6 +862,16 @@ const getReturnLog = (confirmedTransaction) => {
 };
 
 
+// Plain strings are taken as IPFS CIDs without a sha256 of the document
+function toContentReference(reference: ContentReferenceLike) {
+    if (reference === null || typeof reference !== "string") { return reference; }
+    return { backend: { ipfs: {} }, identifier: reference, sha256: null };
+}
+
+function toAttachments(attachments: Array<{ name: string, reference: ContentReferenceLike, mimeType: string }>) {
+    return attachments.map((x) => ({ ...x, reference: toContentReference(x.reference) }));
+}
+
 function numberToLEBytes(number) {
     const buffer = new ArrayBuffer(4);
     const view = new DataView(buffer);
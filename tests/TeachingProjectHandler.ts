@@ -17,6 +17,12 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddres
 chai.use(chaiAsPromised);
 const confirmOptions: ConfirmOptions = { commitment: "confirmed" };
 
+// IPFS reference of the full body of the proposals (the on-chain content is just a short summary)
+const DEFAULT_PROPOSAL_CONTENT_REFERENCE = "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs";
+
+// Off-chain documents can be referenced by their IPFS CID or by a typed reference (IPFS / Arweave + optional sha256)
+type ContentReferenceLike = string | { backend: any, identifier: string, sha256: string | null };
+
 // helper functions
 const createWallet = async (connection: anchor.web3.Connection, funds: number): Promise<anchor.web3.Keypair> => {
   const wallet = anchor.web3.Keypair.generate();
@@ -148,6 +154,7 @@ const initializeSystem = async (program: Program<TeachingProjectHandler>, author
   const subject_id_generator_pda = await findPDAforIdGenerator(program.programId, "subject")
   const code_id_relation_account = await findPDAforCodeIdRelation(program.programId)
   const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
+  const treasury_pda = await findPDAforTreasury(program.programId)
 
 
   const mint = await findPDAforMint(program.programId)
@@ -163,6 +170,7 @@ const initializeSystem = async (program: Program<TeachingProjectHandler>, author
       facultyIdHandler: faculty_id_generator_pda,
       specialtyIdHandler: specialty_id_generator_pda,
       subjectIdHandler: subject_id_generator_pda,
+      treasury: treasury_pda,
       mintAuthorityAccount: mint_auth_pda,
       mint: mint,
       tokenProgram: TOKEN_PROGRAM_ID,
@@ -249,7 +257,7 @@ const initializeSpecialty = async (program: Program<TeachingProjectHandler>, aut
   return result;
 }
 
-const initializeSubject = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, name: string, degree_id: number, specialty_id: number, course: any, code: number, reference: string): Promise<String> => {
+const initializeSubject = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, name: string, degree_id: number, specialty_id: number, course: any, code: number, reference: ContentReferenceLike, prerequisites: Array<number> = [], corequisites: Array<number> = [], section_references: Array<{ section: any, reference: ContentReferenceLike }> = []): Promise<String> => {
 
   const pda = await findPDAforSubject(program.programId, id)
   const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
@@ -260,8 +268,9 @@ const initializeSubject = async (program: Program<TeachingProjectHandler>, autho
   const systemInitialization = await findPDAforSystemInitialization(program.programId)
   const proposalIdHandlerForSubject = await findPDAforProposalIdGenerator(program.programId, false, code)
   const professorProposalIdHandlerForSubject = await findPDAforProposalIdGenerator(program.programId, true, code)
+  const subject_dependency_graph_pda = await findPDAforSubjectDependencyGraph(program.programId)
 
-  const result = await program.methods.createSubject(name, degree_id, specialty_id, course, code, reference)
+  const result = await program.methods.createSubject(name, degree_id, specialty_id, course, code, toContentReference(reference), prerequisites, corequisites, section_references.map((x) => ({ section: x.section, reference: toContentReference(x.reference) })))
     .accounts({
       authority: authority.publicKey,
       initializationSystemAccount: systemInitialization,
@@ -273,6 +282,7 @@ const initializeSubject = async (program: Program<TeachingProjectHandler>, autho
       codeIdSubjectRelationAccount: code_id_relation_pda,
       proposalIdHandler: proposalIdHandlerForSubject,
       professorProposalIdHandler: professorProposalIdHandlerForSubject,
+      subjectDependencyGraph: subject_dependency_graph_pda,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([authority])
@@ -281,7 +291,138 @@ const initializeSubject = async (program: Program<TeachingProjectHandler>, autho
   return result;
 }
 
-const initializeProposalByStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number): Promise<String> => {
+const createDegreesBatch = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, first_id: number, degrees: Array<{ name: string, facultyId: number }>): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const id_generator_pda = await findPDAforIdGenerator(program.programId, "degree")
+  const faculty_id_generator_pda = await findPDAforIdGenerator(program.programId, "faculty")
+  const systemInitialization = await findPDAforSystemInitialization(program.programId)
+
+  // Every degree is created in its own PDA, passed in the same order as the degrees
+  const degree_pdas = []
+  for (var i = 0; i < degrees.length; i++) {
+    degree_pdas.push({ pubkey: await findPDAforDegree(program.programId, first_id + i), isWritable: true, isSigner: false })
+  }
+
+  const result = await program.methods.createDegreesBatch(degrees)
+    .accounts({
+      authority: authority.publicKey,
+      initializationSystemAccount: systemInitialization,
+      highRank: high_rank_pda,
+      degreeIdHandler: id_generator_pda,
+      facultyIdHandler: faculty_id_generator_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .remainingAccounts(degree_pdas)
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const createSubjectsBatch = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, first_id: number, subjects: Array<any>): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const id_generator_pda = await findPDAforIdGenerator(program.programId, "subject")
+  const degree_id_generator_pda = await findPDAforIdGenerator(program.programId, "degree")
+  const specialty_id_generator_pda = await findPDAforIdGenerator(program.programId, "specialty")
+  const code_id_relation_pda = await findPDAforCodeIdRelation(program.programId)
+  const systemInitialization = await findPDAforSystemInitialization(program.programId)
+  const subject_dependency_graph_pda = await findPDAforSubjectDependencyGraph(program.programId)
+
+  // Every subject needs its own PDA and both proposal id handlers, passed in the same order as the subjects
+  const subject_pdas = []
+  for (var i = 0; i < subjects.length; i++) {
+    subject_pdas.push({ pubkey: await findPDAforSubject(program.programId, first_id + i), isWritable: true, isSigner: false })
+    subject_pdas.push({ pubkey: await findPDAforProposalIdGenerator(program.programId, false, subjects[i].code), isWritable: true, isSigner: false })
+    subject_pdas.push({ pubkey: await findPDAforProposalIdGenerator(program.programId, true, subjects[i].code), isWritable: true, isSigner: false })
+  }
+
+  const result = await program.methods.createSubjectsBatch(subjects.map((x) => ({ ...x, teachingProjectReference: toContentReference(x.teachingProjectReference), sections: (x.sections ?? []).map((y) => ({ section: y.section, reference: toContentReference(y.reference) })) })))
+    .accounts({
+      authority: authority.publicKey,
+      initializationSystemAccount: systemInitialization,
+      highRank: high_rank_pda,
+      subjectIdHandler: id_generator_pda,
+      degreeIdHandler: degree_id_generator_pda,
+      specialtyIdHandler: specialty_id_generator_pda,
+      codeIdSubjectRelationAccount: code_id_relation_pda,
+      subjectDependencyGraph: subject_dependency_graph_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .remainingAccounts(subject_pdas)
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const crossListSubject = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, subject_id: number, degree_id: number, specialty_id: number): Promise<String> => {
+
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const degree_id_generator_pda = await findPDAforIdGenerator(program.programId, "degree")
+  const specialty_id_generator_pda = await findPDAforIdGenerator(program.programId, "specialty")
+
+  const result = await program.methods.crossListSubject(degree_id, specialty_id)
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      degreeIdHandler: degree_id_generator_pda,
+      specialtyIdHandler: specialty_id_generator_pda,
+      subjectAccount: subject_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const updateSubjectMetadata = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, subject_id: number, ects_credits: number, semester: any, language: string, modality: any, coordinator_professor_id: number, course_number: number, coordinator_authority: anchor.web3.PublicKey = null): Promise<String> => {
+
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const subject_metadata_pda = await findPDAforSubjectMetadata(program.programId, subject_id)
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const professor_id_generator_pda = await findPDAforIdGenerator(program.programId, "professor")
+  // The program id stands for the optional coordinator account when no coordinator is assigned
+  const coordinator_professor_pda = coordinator_authority ? await findPDAforProfessor(program.programId, coordinator_authority) : program.programId
+
+  const result = await program.methods.updateSubjectMetadata(ects_credits, semester, language, modality, coordinator_professor_id, course_number)
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      professorIdHandler: professor_id_generator_pda,
+      subjectAccount: subject_pda,
+      coordinatorProfessor: coordinator_professor_pda,
+      subjectMetadataAccount: subject_metadata_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const updateProposalCategoryConfig = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, category: any, approval_threshold: number, high_rank_validation_required: boolean): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const proposal_category_config_pda = await findPDAforProposalCategoryConfig(program.programId, category)
+
+  const result = await program.methods.updateProposalCategoryConfig(category, approval_threshold, high_rank_validation_required)
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      proposalCategoryConfig: proposal_category_config_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const initializeProposalByStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number, content_reference: ContentReferenceLike = DEFAULT_PROPOSAL_CONTENT_REFERENCE, attachments: Array<{ name: string, reference: ContentReferenceLike, mimeType: string }> = [], category: any = { other: {} }, tags: Array<string> = [], voting_starts_at: number = null, sections: Array<any> = [], evaluation_scheme: any = null): Promise<String> => {
 
   const pda = await findPDAforProposal(program.programId, id, subject_code)
   const student_pda = await findPDAforStudent(program.programId, authority.publicKey)
@@ -291,9 +432,16 @@ const initializeProposalByStudent = async (program: Program<TeachingProjectHandl
   const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
   const professor_proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, true, subject_code)
   const code_id_relation_account = await findPDAforCodeIdRelation(program.programId)
+  const subject_dependency_graph_pda = await findPDAforSubjectDependencyGraph(program.programId)
   const systemInitialization = await findPDAforSystemInitialization(program.programId)
 
-  const result = await program.methods.createProposalByStudent(title, content)
+  const proposal_category_config_pda = await findPDAforProposalCategoryConfig(program.programId, category)
+  const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, id, subject_code)
+  const open_proposals_counter_pda = await findPDAforOpenProposalsCounter(program.programId, authority.publicKey, subject_code)
+  const proposal_deposit_config_pda = await findPDAforProposalDepositConfig(program.programId)
+  const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+
+  const result = await program.methods.createProposalByStudent(title, content, toContentReference(content_reference), CryptoJS.SHA256(content).toString(), toAttachments(attachments), category, tags, voting_starts_at === null ? null : new anchor.BN(voting_starts_at), sections, evaluation_scheme)
     .accounts({
       authority: authority.publicKey,
       initializationSystemAccount: systemInitialization,
@@ -305,6 +453,12 @@ const initializeProposalByStudent = async (program: Program<TeachingProjectHandl
       professorProposalAccount: professor_proposal_pda,
       subjectAccount: subject_pda,
       codeIdSubjectRelation: code_id_relation_account,
+      subjectDependencyGraph: subject_dependency_graph_pda,
+      proposalCategoryConfig: proposal_category_config_pda,
+      proposalDeposit: proposal_deposit_pda,
+      openProposalsCounter: open_proposals_counter_pda,
+      proposalDepositConfig: proposal_deposit_config_pda,
+      proposalIndex: proposal_index_pda,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([authority])
@@ -313,7 +467,7 @@ const initializeProposalByStudent = async (program: Program<TeachingProjectHandl
   return result;
 }
 
-const initializeProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number): Promise<String> => {
+const initializeProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, id: number, title: string, content: string, subject_id: number, professor_proposal_id: number, subject_code: number, content_reference: ContentReferenceLike = DEFAULT_PROPOSAL_CONTENT_REFERENCE, attachments: Array<{ name: string, reference: ContentReferenceLike, mimeType: string }> = [], category: any = { other: {} }, tags: Array<string> = [], voting_starts_at: number = null, sections: Array<any> = [], evaluation_scheme: any = null): Promise<String> => {
 
   const pda = await findPDAforProposal(program.programId, id, subject_code)
   const professor_pda = await findPDAforProfessor(program.programId, authority.publicKey)
@@ -323,11 +477,18 @@ const initializeProposalByProfessor = async (program: Program<TeachingProjectHan
   const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
   const professor_proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, true, subject_code)
   const code_id_relation_account = await findPDAforCodeIdRelation(program.programId)
+  const subject_dependency_graph_pda = await findPDAforSubjectDependencyGraph(program.programId)
   const systemInitialization = await findPDAforSystemInitialization(program.programId)
 
 
 
-  const result = await program.methods.createProposalByProfessor(title, content)
+  const proposal_category_config_pda = await findPDAforProposalCategoryConfig(program.programId, category)
+  const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, id, subject_code)
+  const open_proposals_counter_pda = await findPDAforOpenProposalsCounter(program.programId, authority.publicKey, subject_code)
+  const proposal_deposit_config_pda = await findPDAforProposalDepositConfig(program.programId)
+  const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+
+  const result = await program.methods.createProposalByProfessor(title, content, toContentReference(content_reference), CryptoJS.SHA256(content).toString(), toAttachments(attachments), category, tags, voting_starts_at === null ? null : new anchor.BN(voting_starts_at), sections, evaluation_scheme)
     .accounts({
       authority: authority.publicKey,
       initializationSystemAccount: systemInitialization,
@@ -339,6 +500,12 @@ const initializeProposalByProfessor = async (program: Program<TeachingProjectHan
       professorProposalAccount: professor_proposal_pda,
       subjectAccount: subject_pda,
       codeIdSubjectRelation: code_id_relation_account,
+      subjectDependencyGraph: subject_dependency_graph_pda,
+      proposalCategoryConfig: proposal_category_config_pda,
+      proposalDeposit: proposal_deposit_pda,
+      openProposalsCounter: open_proposals_counter_pda,
+      proposalDepositConfig: proposal_deposit_config_pda,
+      proposalIndex: proposal_index_pda,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([authority])
@@ -372,21 +539,35 @@ const voteProposalByStudent = async (program: Program<TeachingProjectHandler>, a
   return result;
 }
 
-const updateProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, reference: string): Promise<String> => {
+const updateProposalByProfessor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, reference: ContentReferenceLike, section_references: Array<{ section: any, reference: ContentReferenceLike }> = []): Promise<String> => {
 
   const professor_account = await findPDAforProfessor(program.programId, authority.publicKey)
   const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
   const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
   const subject_pda = await findPDAforSubject(program.programId, subject_id)
 
+  // The penalty record is only created if the professor is late (its index is the number of penalties they already have)
+  const professor = await program.account.professor.fetch(professor_account)
+  const penalty_record_pda = await findPDAforPenaltyRecord(program.programId, professor.id, professor.numberOfPenalties)
+  const penalty_policy_pda = await findPDAforPenaltyPolicy(program.programId)
+
+  // A new version of the teaching project is recorded if the proposal does not require HighRank validation
+  const subject = await program.account.subject.fetch(subject_pda)
+  const teaching_project_version_pda = await findPDAforTeachingProjectVersion(program.programId, subject_code, subject.teachingProjectVersion + 1)
 
-  const result = await program.methods.updateProposalByProfessor(reference)
+  const result = await program.methods.updateProposalByProfessor(toContentReference(reference), section_references.map((x) => ({ section: x.section, reference: toContentReference(x.reference) })))
     .accounts({
       authority: authority.publicKey,
       professorAccount: professor_account,
       proposalAccount: proposal_pda,
       professorProposalAccount: professor_proposal_pda,
-      subjectAccount: subject_pda
+      subjectAccount: subject_pda,
+      penaltyPolicy: penalty_policy_pda,
+      penaltyRecord: penalty_record_pda,
+      professorReputation: await findPDAforReputation(program.programId, authority.publicKey),
+      teachingProjectVersion: teaching_project_version_pda,
+      evaluationScheme: await findPDAforEvaluationScheme(program.programId, subject_code),
+      systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([authority])
     .rpc(confirmOptions);
@@ -394,1537 +575,3899 @@ const updateProposalByProfessor = async (program: Program<TeachingProjectHandler
   return result;
 }
 
-const updateProposalByHighRank = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number): Promise<String> => {
+const assignProfessorProposalByRoundRobin = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, subject_professors: Array<anchor.web3.PublicKey>): Promise<String> => {
 
-  const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
   const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
   const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
   const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const professor_assignment_pda = await findPDAforProfessorAssignment(program.programId, subject_code)
+  const code_id_relation_pda = await findPDAforCodeIdRelation(program.programId)
 
+  const professor_pdas = []
+  for (const professor of subject_professors) {
+    professor_pdas.push({ pubkey: await findPDAforProfessor(program.programId, professor), isWritable: false, isSigner: false })
+  }
 
-  const result = await program.methods.updateProposalByHighRank(true)
+  const result = await program.methods.assignProfessorProposalByRoundRobin()
     .accounts({
       authority: authority.publicKey,
-      highRankAccount: high_rank_account,
       proposalAccount: proposal_pda,
       professorProposalAccount: professor_proposal_pda,
-      subjectAccount: subject_pda
+      subjectAccount: subject_pda,
+      professorAssignmentAccount: professor_assignment_pda,
+      codeIdSubjectRelation: code_id_relation_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
     })
+    .remainingAccounts(professor_pdas)
     .signers([authority])
     .rpc(confirmOptions);
 
   return result;
 }
 
-const giveCreditToStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, student_creator_public_key: anchor.web3.PublicKey, identifier_code: string, subject_code: number): Promise<String> => {
-
-  const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
-  const proposal_account_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
-  const creator_account_pda = await findPDAforStudent(program.programId, student_creator_public_key)
-  const mint = await findPDAforMint(program.programId)
-  const [pda, bump] = await findPDAforMintAuthority(program.programId, mint, identifier_code)
-
-  let mintAuthority: { pda: anchor.web3.PublicKey, bump: number };
-  mintAuthority = { pda: pda, bump: bump };
+const assignProfessorProposal = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, assigned_professor: anchor.web3.PublicKey): Promise<String> => {
 
-  const associatedTokenAccount = await getAssociatedTokenAddress(mint, student_creator_public_key, false);
+  const coordinator_pda = await findPDAforProfessor(program.programId, authority.publicKey)
+  const assigned_professor_pda = await findPDAforProfessor(program.programId, assigned_professor)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const subject_metadata_pda = await findPDAforSubjectMetadata(program.programId, subject_id)
 
-  const result = await program.methods.giveCreditsToWinningStudent(identifier_code, subject_code, mintAuthority.bump)
+  const result = await program.methods.assignProfessorProposal()
     .accounts({
       authority: authority.publicKey,
-      highRankAccount: high_rank_account,
-      proposalAccount: proposal_account_pda,
-      creatorAccount: creator_account_pda,
-      tokenAccount: associatedTokenAccount,
-      mintAuthorityAccount: mintAuthority.pda,
-      mint: mint,
-      tokenProgram: TOKEN_PROGRAM_ID,
-      systemProgram: anchor.web3.SystemProgram.programId,
-      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-
+      coordinatorAccount: coordinator_pda,
+      assignedProfessor: assigned_professor_pda,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      subjectAccount: subject_pda,
+      subjectMetadataAccount: subject_metadata_pda,
     })
     .signers([authority])
-    .rpc();
+    .rpc(confirmOptions);
 
   return result;
-
 }
 
-const deleteRejectedProposalByHighRank = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, professor_proposal_id: number, subject_code: number): Promise<String> => {
+const addProfessorProposalCollaborator = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, collaborator: anchor.web3.PublicKey): Promise<String> => {
 
+  const professor_pda = await findPDAforProfessor(program.programId, authority.publicKey)
+  const collaborator_pda = await findPDAforProfessor(program.programId, collaborator)
   const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
-  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
   const subject_pda = await findPDAforSubject(program.programId, subject_id)
-  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
 
-  const result = await program.methods.deleteRejectedProposalAccount()
+  const result = await program.methods.addProfessorProposalCollaborator()
     .accounts({
       authority: authority.publicKey,
-      highRankAccount: high_rank_pda,
+      professorAccount: professor_pda,
+      collaboratorAccount: collaborator_pda,
       proposalAccount: proposal_pda,
       professorProposalAccount: professor_proposal_pda,
       subjectAccount: subject_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
     })
     .signers([authority])
-    .rpc();
+    .rpc(confirmOptions);
 
   return result;
 }
 
-const findPDAforHighRank = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+const createEvaluationScheme = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, subject_id: number, subject_code: number, scheme: any): Promise<String> => {
 
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("highRank"), authority.toBytes()],
-    programId
-  );
-  return pda;
-}
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const evaluation_scheme_pda = await findPDAforEvaluationScheme(program.programId, subject_code)
 
-const findPDAforProfessor = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const result = await program.methods.createEvaluationScheme(scheme)
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      subjectAccount: subject_pda,
+      evaluationScheme: evaluation_scheme_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
 
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("professor"), authority.toBytes()],
-    programId
-  );
-  return pda;
+  return result;
 }
 
-const findPDAforStudent = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+const submitDraftRevision = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, reference: ContentReferenceLike): Promise<String> => {
 
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("student"), authority.toBytes()],
-    programId
-  );
-  return pda;
-}
+  const professor_pda = await findPDAforProfessor(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
 
-const findPDAforIdGenerator = async (programId: anchor.web3.PublicKey, account_info: string): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode(account_info + 'IdHandler')],
-    programId
-  );
-  return pda;
+  const result = await program.methods.submitDraftRevision(toContentReference(reference))
+    .accounts({
+      authority: authority.publicKey,
+      professorAccount: professor_pda,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      subjectAccount: subject_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
 }
 
-const findPDAforProposalIdGenerator = async (programId: anchor.web3.PublicKey, is_professor: boolean, subject_code: number): Promise<anchor.web3.PublicKey> => {
+const signOffDraft = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number): Promise<String> => {
 
-  let adding: string;
-  if (is_professor) { adding = 'professorP' } else { adding = 'p' }
-  let identifier: string = adding + 'roposalIdHandler';
+  const professor_pda = await findPDAforProfessor(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
 
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode(identifier), numberToLEBytes(subject_code)],
-    programId
-  );
-  return pda;
-}
+  const result = await program.methods.signOffDraft()
+    .accounts({
+      authority: authority.publicKey,
+      professorAccount: professor_pda,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      subjectAccount: subject_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
 
-const findPDAforFaculty = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("faculty"), numberToLEBytes(id)],
-    programId
-  );
-  return pda;
+  return result;
 }
 
-const findPDAforDegree = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("degree"), numberToLEBytes(id)],
-    programId
-  );
-  return pda;
-}
+const requestExtension = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, reason_reference: ContentReferenceLike, requested_ending_timestamp: number): Promise<String> => {
 
-const findPDAforSpecialty = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("specialty"), numberToLEBytes(id)],
-    programId
-  );
-  return pda;
-}
+  const professor_pda = await findPDAforProfessor(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
 
-const findPDAforSubject = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("subject"), numberToLEBytes(id)],
-    programId
-  );
-  return pda;
+  const result = await program.methods.requestExtension(toContentReference(reason_reference), new anchor.BN(requested_ending_timestamp))
+    .accounts({
+      authority: authority.publicKey,
+      professorAccount: professor_pda,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      subjectAccount: subject_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
 }
 
-const findPDAforProposal = async (programId: anchor.web3.PublicKey, id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("proposal"), numberToLEBytes(id), numberToLEBytes(subject_code)],
-    programId
-  );
-  return pda;
+const resolveExtension = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, approved: boolean): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+
+  const result = await program.methods.resolveExtension(approved)
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      subjectAccount: subject_pda,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
 }
 
-const findPDAforProfessorProposal = async (programId: anchor.web3.PublicKey, id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("professorProposal"), numberToLEBytes(id), numberToLEBytes(subject_code)],
-    programId
-  );
-  return pda;
+const updateProposalByHighRank = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, profesor_proposal_id: number, subject_code: number, subject_id: number, decision: any = { accept: {} }, feedback_reference: ContentReferenceLike = DEFAULT_PROPOSAL_CONTENT_REFERENCE): Promise<String> => {
+
+  const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, profesor_proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const professor_proposal = await program.account.professorProposal.fetch(professor_proposal_pda)
+  const professor_reputation_pda = await findPDAforReputation(program.programId, professor_proposal.deliveredBy)
+
+  // The professor that delivered the work is rewarded through the same mint authority used for the creators (only if the work is accepted)
+  const is_accepted = decision.accept !== undefined
+  const mint = await findPDAforMint(program.programId)
+  const [mint_authority_pda, mint_authority_bump] = await findPDAforMintAuthority(program.programId, mint, "1111")
+  const professor_token_account = await getAssociatedTokenAddress(mint, professor_proposal.deliveredBy, false)
+  const professor_reward_config_pda = await findPDAforProfessorRewardConfig(program.programId)
+
+  const subject = await program.account.subject.fetch(subject_pda)
+  const teaching_project_version_pda = await findPDAforTeachingProjectVersion(program.programId, subject_code, subject.teachingProjectVersion + 1)
+
+  const result = await program.methods.updateProposalByHighRank(decision, toContentReference(feedback_reference), "1111", mint_authority_bump)
+    .accounts({
+      authority: authority.publicKey,
+      highRankAccount: high_rank_account,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      subjectAccount: subject_pda,
+      professorReputation: professor_reputation_pda,
+      teachingProjectVersion: teaching_project_version_pda,
+      evaluationScheme: await findPDAforEvaluationScheme(program.programId, subject_code),
+      professorAccount: is_accepted ? await findPDAforProfessor(program.programId, professor_proposal.deliveredBy) : null,
+      professorRewardConfig: professor_reward_config_pda,
+      mintAuthorityAccount: is_accepted ? mint_authority_pda : null,
+      mint: is_accepted ? mint : null,
+      professorTokenAccount: is_accepted ? professor_token_account : null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
 }
 
-const findPDAforMint = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("creditToken")],
-    programId
-  );
-  return pda;
+const giveCreditToStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, student_creator_public_key: anchor.web3.PublicKey, identifier_code: string, subject_code: number, co_authors: Array<anchor.web3.PublicKey> = []): Promise<String> => {
+
+  const high_rank_account = await findPDAforHighRank(program.programId, authority.publicKey)
+  const proposal_account_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const creator_account_pda = await findPDAforStudent(program.programId, student_creator_public_key)
+  const mint = await findPDAforMint(program.programId)
+  const [pda, bump] = await findPDAforMintAuthority(program.programId, mint, identifier_code)
+
+  let mintAuthority: { pda: anchor.web3.PublicKey, bump: number };
+  mintAuthority = { pda: pda, bump: bump };
+
+  // Token accounts of the signed co-authors followed by their reputation accounts and their user accounts (in the same order they were invited)
+  const co_authors_accounts = []
+  for (const co_author of co_authors) {
+    co_authors_accounts.push({ pubkey: await getAssociatedTokenAddress(mint, co_author, false), isWritable: true, isSigner: false })
+  }
+  for (const co_author of co_authors) {
+    co_authors_accounts.push({ pubkey: await findPDAforReputation(program.programId, co_author), isWritable: true, isSigner: false })
+  }
+  const proposal = await program.account.proposal.fetch(proposal_account_pda)
+  for (const co_author of co_authors) {
+    const is_professor = proposal.coAuthors.find((x) => x.authority.equals(co_author)).userType.professor !== undefined
+    const user_account_pda = is_professor ? await findPDAforProfessor(program.programId, co_author) : await findPDAforStudent(program.programId, co_author)
+    co_authors_accounts.push({ pubkey: user_account_pda, isWritable: true, isSigner: false })
+  }
+
+  const associatedTokenAccount = await getAssociatedTokenAddress(mint, student_creator_public_key, false);
+
+  const result = await program.methods.giveCreditsToWinningStudent(identifier_code, subject_code, mintAuthority.bump)
+    .accounts({
+      authority: authority.publicKey,
+      highRankAccount: high_rank_account,
+      proposalAccount: proposal_account_pda,
+      creatorAccount: creator_account_pda,
+      tokenAccount: associatedTokenAccount,
+      creatorReputation: await findPDAforReputation(program.programId, student_creator_public_key),
+      mintAuthorityAccount: mintAuthority.pda,
+      mint: mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+
+    })
+    .remainingAccounts(co_authors_accounts)
+    .signers([authority])
+    .rpc();
+
+  return result;
+
+}
+
+const editProposal = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, title: string, content: string, content_reference: ContentReferenceLike = DEFAULT_PROPOSAL_CONTENT_REFERENCE, attachments: Array<{ name: string, reference: ContentReferenceLike, mimeType: string }> = []): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+
+  const result = await program.methods.editProposal(title, content, toContentReference(content_reference), CryptoJS.SHA256(content).toString(), toAttachments(attachments))
+    .accounts({
+      authority: authority.publicKey,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      proposalIndex: proposal_index_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const withdrawProposal = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, professor_proposal_id: number, subject_code: number): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
+  const proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, false, subject_code)
+  const professor_proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, true, subject_code)
+  const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, proposal_id, subject_code)
+  const appeal_pda = await findPDAforAppeal(program.programId, proposal_id, subject_code)
+  const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+  const open_proposals_counter_pda = await findPDAforOpenProposalsCounter(program.programId, authority.publicKey, subject_code)
+
+  const result = await program.methods.withdrawProposal()
+    .accounts({
+      authority: authority.publicKey,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      proposalDeposit: proposal_deposit_pda,
+      openProposalsCounter: open_proposals_counter_pda,
+      subjectAccount: subject_pda,
+      proposalIdHandler: proposal_id_handler,
+      professorProposalIdHandler: professor_proposal_id_handler,
+      appealAccount: appeal_pda,
+      proposalIndex: proposal_index_pda,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const commentProposalByStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, comment_id: number, parent_comment_id: number, content_reference: ContentReferenceLike, text: string): Promise<String> => {
+
+  const student_pda = await findPDAforStudent(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const comment_pda = await findPDAforComment(program.programId, proposal_id, subject_code, comment_id)
+
+  const result = await program.methods.commentProposalByStudent(parent_comment_id, content_reference === "" ? null : toContentReference(content_reference), text)
+    .accounts({
+      authority: authority.publicKey,
+      studentAccount: student_pda,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      commentAccount: comment_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const proposeAmendmentByStudent = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, amendment_id: number, title: string, content: string): Promise<String> => {
+
+  const student_pda = await findPDAforStudent(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const amendment_pda = await findPDAforAmendment(program.programId, proposal_id, subject_code, amendment_id)
+
+  const result = await program.methods.proposeAmendmentByStudent(title, content, toContentReference(DEFAULT_PROPOSAL_CONTENT_REFERENCE), CryptoJS.SHA256(content).toString())
+    .accounts({
+      authority: authority.publicKey,
+      studentAccount: student_pda,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      amendmentAccount: amendment_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const acceptAmendment = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, amendment_id: number, reset_votes: boolean): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+  const amendment_pda = await findPDAforAmendment(program.programId, proposal_id, subject_code, amendment_id)
+
+  const result = await program.methods.acceptAmendment(reset_votes)
+    .accounts({
+      authority: authority.publicKey,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      amendmentAccount: amendment_pda,
+      proposalIndex: proposal_index_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const updateProposalDepositConfig = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, deposit_lamports: number, maximum_open_proposals_per_user: number): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const proposal_deposit_config_pda = await findPDAforProposalDepositConfig(program.programId)
+
+  const result = await program.methods.updateProposalDepositConfig(new anchor.BN(deposit_lamports), maximum_open_proposals_per_user)
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      proposalDepositConfig: proposal_deposit_config_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const updatePenaltyPolicy = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, grace_period: number, penalty_interval: number, penalty_per_interval: number, maximum_penalty: number, decay_per_term: number, term_duration: number): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const penalty_policy_pda = await findPDAforPenaltyPolicy(program.programId)
+
+  const result = await program.methods.updatePenaltyPolicy(new anchor.BN(grace_period), new anchor.BN(penalty_interval), penalty_per_interval, maximum_penalty, decay_per_term, new anchor.BN(term_duration))
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      penaltyPolicy: penalty_policy_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const updateProfessorRewardConfig = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, reward_tokens: number, early_delivery_bonus_tokens: number, early_delivery_period: number): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const professor_reward_config_pda = await findPDAforProfessorRewardConfig(program.programId)
+
+  const result = await program.methods.updateProfessorRewardConfig(reward_tokens, early_delivery_bonus_tokens, new anchor.BN(early_delivery_period))
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      professorRewardConfig: professor_reward_config_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const voidPenalty = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, professor: anchor.web3.PublicKey, penalty_index: number, void_reference: ContentReferenceLike): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const professor_pda = await findPDAforProfessor(program.programId, professor)
+  const professor_account = await program.account.professor.fetch(professor_pda)
+  const penalty_record_pda = await findPDAforPenaltyRecord(program.programId, professor_account.id, penalty_index)
+
+  const result = await program.methods.voidPenalty(toContentReference(void_reference))
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      professorAccount: professor_pda,
+      penaltyRecord: penalty_record_pda,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
 }
 
-const findPDAforCodeIdRelation = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("codeIdSubjectRelation")],
-    programId
-  );
-  return pda;
-}
+const settleProposalDeposit = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, depositor: anchor.web3.PublicKey): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, proposal_id, subject_code)
+  const open_proposals_counter_pda = await findPDAforOpenProposalsCounter(program.programId, depositor, subject_code)
+  const treasury_pda = await findPDAforTreasury(program.programId)
+
+  const result = await program.methods.settleProposalDeposit()
+    .accounts({
+      authority: authority.publicKey,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      proposalDeposit: proposal_deposit_pda,
+      depositor: depositor,
+      openProposalsCounter: open_proposals_counter_pda,
+      treasury: treasury_pda,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const updateDuplicateProposalCooldown = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, subject_id: number, subject_code: number, cooldown: number): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+
+  const result = await program.methods.updateDuplicateProposalCooldown(new anchor.BN(cooldown))
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      subjectAccount: subject_pda,
+      proposalIndex: proposal_index_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const inviteCoAuthor = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, co_author: anchor.web3.PublicKey, user_type: any, share: number): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const co_author_pda = "student" in user_type ? await findPDAforStudent(program.programId, co_author) : await findPDAforProfessor(program.programId, co_author)
+
+  const result = await program.methods.inviteCoAuthor(co_author, user_type, share)
+    .accounts({
+      authority: authority.publicKey,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      coAuthorAccount: co_author_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const coSignProposal = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+
+  const result = await program.methods.coSignProposal()
+    .accounts({
+      authority: authority.publicKey,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const fileAppeal = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, reason_reference: ContentReferenceLike): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const appeal_pda = await findPDAforAppeal(program.programId, proposal_id, subject_code)
+
+  const result = await program.methods.fileAppeal(toContentReference(reason_reference))
+    .accounts({
+      authority: authority.publicKey,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      appealAccount: appeal_pda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const resolveAppeal = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, subject_code: number, reopen_votation: boolean, justification_reference: ContentReferenceLike, new_ending_timestamp: number): Promise<String> => {
+
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const appeal_pda = await findPDAforAppeal(program.programId, proposal_id, subject_code)
+
+  const result = await program.methods.resolveAppeal(reopen_votation, toContentReference(justification_reference), new anchor.BN(new_ending_timestamp))
+    .accounts({
+      authority: authority.publicKey,
+      highRank: high_rank_pda,
+      proposalAccount: proposal_pda,
+      subjectAccount: subject_pda,
+      appealAccount: appeal_pda,
+      proposalDeposit: await findPDAforProposalDeposit(program.programId, proposal_id, subject_code),
+    })
+    .signers([authority])
+    .rpc(confirmOptions);
+
+  return result;
+}
+
+const deleteRejectedProposalByHighRank = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.Keypair, proposal_id: number, subject_id: number, professor_proposal_id: number, subject_code: number): Promise<String> => {
+
+  const proposal_pda = await findPDAforProposal(program.programId, proposal_id, subject_code)
+  const high_rank_pda = await findPDAforHighRank(program.programId, authority.publicKey)
+  const subject_pda = await findPDAforSubject(program.programId, subject_id)
+  const professor_proposal_pda = await findPDAforProfessorProposal(program.programId, professor_proposal_id, subject_code)
+  const proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, false, subject_code)
+  const professor_proposal_id_handler = await findPDAforProposalIdGenerator(program.programId, true, subject_code)
+  const proposal_deposit_pda = await findPDAforProposalDeposit(program.programId, proposal_id, subject_code)
+  const appeal_pda = await findPDAforAppeal(program.programId, proposal_id, subject_code)
+  const proposal_index_pda = await findPDAforProposalIndex(program.programId, subject_code)
+
+  const result = await program.methods.deleteRejectedProposalAccount()
+    .accounts({
+      authority: authority.publicKey,
+      highRankAccount: high_rank_pda,
+      proposalAccount: proposal_pda,
+      professorProposalAccount: professor_proposal_pda,
+      subjectAccount: subject_pda,
+      proposalIdHandler: proposal_id_handler,
+      professorProposalIdHandler: professor_proposal_id_handler,
+      proposalDeposit: proposal_deposit_pda,
+      appealAccount: appeal_pda,
+      proposalIndex: proposal_index_pda,
+    })
+    .signers([authority])
+    .rpc();
+
+  return result;
+}
+
+const findPDAforHighRank = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("highRank"), authority.toBytes()],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProfessor = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("professor"), authority.toBytes()],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforStudent = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("student"), authority.toBytes()],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforIdGenerator = async (programId: anchor.web3.PublicKey, account_info: string): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode(account_info + 'IdHandler')],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProposalIdGenerator = async (programId: anchor.web3.PublicKey, is_professor: boolean, subject_code: number): Promise<anchor.web3.PublicKey> => {
+
+  let adding: string;
+  if (is_professor) { adding = 'professorP' } else { adding = 'p' }
+  let identifier: string = adding + 'roposalIdHandler';
+
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode(identifier), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforFaculty = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("faculty"), numberToLEBytes(id)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforDegree = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("degree"), numberToLEBytes(id)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforSpecialty = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("specialty"), numberToLEBytes(id)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforSubject = async (programId: anchor.web3.PublicKey, id: Number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("subject"), numberToLEBytes(id)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProfessorAssignment = async (programId: anchor.web3.PublicKey, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("professorAssignment"), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforSubjectMetadata = async (programId: anchor.web3.PublicKey, subject_id: Number): Promise<anchor.web3.PublicKey> => {
+
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("subjectMetadata"), numberToLEBytes(subject_id)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProposal = async (programId: anchor.web3.PublicKey, id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("proposal"), numberToLEBytes(id), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforComment = async (programId: anchor.web3.PublicKey, proposal_id: Number, subject_code: number, comment_id: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("comment"), numberToLEBytes(proposal_id), numberToLEBytes(subject_code), numberToLEBytes(comment_id)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforAmendment = async (programId: anchor.web3.PublicKey, proposal_id: Number, subject_code: number, amendment_id: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("amendment"), numberToLEBytes(proposal_id), numberToLEBytes(subject_code), numberToLEBytes(amendment_id)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProfessorProposal = async (programId: anchor.web3.PublicKey, id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("professorProposal"), numberToLEBytes(id), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforMint = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("creditToken")],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforCodeIdRelation = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("codeIdSubjectRelation")],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforSubjectDependencyGraph = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("subjectDependencyGraph")],
+    programId
+  );
+  return pda;
+}
+
+// Seed of the category config --> index of the variant in the 'ProposalCategory' enum
+const PROPOSAL_CATEGORIES = ["evaluationCriteria", "bibliography", "schedule", "competences", "other"];
+
+const findPDAforProposalCategoryConfig = async (programId: anchor.web3.PublicKey, category: any): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("proposalCategoryConfig"), Buffer.from([PROPOSAL_CATEGORIES.indexOf(Object.keys(category)[0])])],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforTreasury = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("treasury")],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforReputation = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("reputation"), authority.toBytes()],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProfessorRewardConfig = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("professorRewardConfig")],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforPenaltyPolicy = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("penaltyPolicy")],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforPenaltyRecord = async (programId: anchor.web3.PublicKey, professor_id: number, penalty_index: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("penaltyRecord"), numberToLEBytes(professor_id), numberToLEBytes(penalty_index)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforTeachingProjectVersion = async (programId: anchor.web3.PublicKey, subject_code: number, version: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("teachingProjectVersion"), numberToLEBytes(subject_code), numberToLEBytes(version)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforEvaluationScheme = async (programId: anchor.web3.PublicKey, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("evaluationScheme"), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProposalDepositConfig = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("proposalDepositConfig")],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProposalDeposit = async (programId: anchor.web3.PublicKey, proposal_id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("proposalDeposit"), numberToLEBytes(proposal_id), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforAppeal = async (programId: anchor.web3.PublicKey, proposal_id: Number, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("appeal"), numberToLEBytes(proposal_id), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforOpenProposalsCounter = async (programId: anchor.web3.PublicKey, authority: anchor.web3.PublicKey, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("openProposals"), authority.toBytes(), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforProposalIndex = async (programId: anchor.web3.PublicKey, subject_code: number): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("proposalIndex"), numberToLEBytes(subject_code)],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforSystemInitialization = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
+  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("systemInitialization")],
+    programId
+  );
+  return pda;
+}
+
+const findPDAforMintAuthority = async (programId: anchor.web3.PublicKey, mint: anchor.web3.PublicKey, high_rank_identifier_code: string): Promise<[anchor.web3.PublicKey, number]> => {
+  const [pda, bump] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("mint_authority"), mint.toBytes(), utf8.encode(high_rank_identifier_code)],
+    programId
+  );
+  return [pda, bump]
+}
+
+const fetchHighRankAccount = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.PublicKey) => {
+  return await program.account.highRank.fetch(await findPDAforHighRank(program.programId, authority))
+}
+
+const fetchProfessorAccount = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.PublicKey) => {
+  return await program.account.professor.fetch(await findPDAforProfessor(program.programId, authority))
+}
+
+const fetchFacultyAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
+  return await program.account.faculty.fetch(await findPDAforFaculty(program.programId, id))
+}
+
+const fetchDegreeAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
+  return await program.account.degree.fetch(await findPDAforDegree(program.programId, id))
+}
+
+const fetchSpecialtyAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
+  return await program.account.specialty.fetch(await findPDAforSpecialty(program.programId, id))
+}
+
+const fetchSubjectAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
+  return await program.account.subject.fetch(await findPDAforSubject(program.programId, id))
+}
+
+const fetchSubjectMetadataAccount = async (program: Program<TeachingProjectHandler>, subject_id: Number) => {
+  return await program.account.subjectMetadata.fetch(await findPDAforSubjectMetadata(program.programId, subject_id));
+}
+
+const fetchStudentAccount = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.PublicKey) => {
+  return await program.account.student.fetch(await findPDAforStudent(program.programId, authority))
+}
+
+const fetchProposalAccount = async (program: Program<TeachingProjectHandler>, id: Number, subject_code: number) => {
+  return await program.account.proposal.fetch(await findPDAforProposal(program.programId, id, subject_code))
+}
+
+const fetchCommentAccount = async (program: Program<TeachingProjectHandler>, proposal_id: Number, subject_code: number, comment_id: number) => {
+  return await program.account.comment.fetch(await findPDAforComment(program.programId, proposal_id, subject_code, comment_id))
+}
+
+const fetchAmendmentAccount = async (program: Program<TeachingProjectHandler>, proposal_id: Number, subject_code: number, amendment_id: number) => {
+  return await program.account.amendment.fetch(await findPDAforAmendment(program.programId, proposal_id, subject_code, amendment_id))
+}
+
+const fetchProfessorProposalAccount = async (program: Program<TeachingProjectHandler>, id: number, subject_code: number) => {
+  return await program.account.professorProposal.fetch(await findPDAforProfessorProposal(program.programId, id, subject_code))
+}
+
+const fetchIdAccount = async (program: Program<TeachingProjectHandler>, account_info: string) => {
+  return await program.account.idHandler.fetch(await findPDAforIdGenerator(program.programId, account_info))
+}
+
+const fetchProposalIdAccount = async (program: Program<TeachingProjectHandler>, is_professor: boolean, subject_code: number) => {
+  return await program.account.idHandler.fetch(await findPDAforProposalIdGenerator(program.programId, is_professor, subject_code))
+}
+
+const fetchCodeIdRelationAccount = async (program: Program<TeachingProjectHandler>) => {
+  return await program.account.codeIdSubjectRelation.fetch(await findPDAforCodeIdRelation(program.programId))
+}
+
+const fetchSystemInitialization = async (program: Program<TeachingProjectHandler>) => {
+  return await program.account.systemInitialization.fetch(await findPDAforSystemInitialization(program.programId))
+}
+
+
+const getReturnLog = (confirmedTransaction) => {
+  const prefix = "Program return: ";
+  let log = confirmedTransaction.meta.logMessages.find((log) =>
+    log.startsWith(prefix)
+  );
+  log = log.slice(prefix.length);
+  const [key, data] = log.split(" ", 2);
+  const buffer = Buffer.from(data, "base64");
+  return [key, data, buffer];
+};
+
+
+// Plain strings are taken as IPFS CIDs without a sha256 of the document
+function toContentReference(reference: ContentReferenceLike) {
+  if (reference === null || typeof reference !== "string") { return reference; }
+  return { backend: { ipfs: {} }, identifier: reference, sha256: null };
+}
+
+function toAttachments(attachments: Array<{ name: string, reference: ContentReferenceLike, mimeType: string }>) {
+  return attachments.map((x) => ({ ...x, reference: toContentReference(x.reference) }));
+}
+
+function numberToLEBytes(number) {
+  const buffer = new ArrayBuffer(4);
+  const view = new DataView(buffer);
+  view.setUint32(0, number, true);
+  const bytes = new Uint8Array(buffer);
+  return bytes;
+}
+
+
+describe("Testing the Teaching Project Handler Smart Contract...\n\n", () => {
+
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+
+  // shared objects
+  const program = anchor.workspace.TeachingProjectHandler as Program<TeachingProjectHandler>;
+  const connection = anchor.getProvider().connection;
+
+  let wallet1: anchor.web3.Keypair;
+  let wallet2: anchor.web3.Keypair;
+  let wallet3: anchor.web3.Keypair;
+  let wallet4: anchor.web3.Keypair;
+
+  let alternativeWallet: anchor.web3.Keypair;
+
+  beforeEach(async () => {
+
+    wallet1 = await createWallet(connection, 10); // HighRank
+    wallet2 = await createWallet(connection, 40); // Professor
+    wallet3 = await createWallet(connection, 10); // Student
+    wallet4 = await createWallet(connection, 10);
+    alternativeWallet = await createWallet(connection, 10)
+
+  });
+
+  it("HighRank is initializated properly", async () => {
+
+    /*
+     * Check if ID's generator for HighRank accounts exist --> 
+     * If so, the smaller ID available is obtained from it 
+     * If not, the new ID will be 1 since no ID's generator created implies no HighRank Account has been created previously
+    */
+    var idExpected = 1;
+    try {
+      let highRankIdGeneratorBefore = await fetchIdAccount(program, "highRank");
+      idExpected = highRankIdGeneratorBefore.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+    }
+
+    //   The new ID that should be available in the ID's generator after creating the new HighRank account
+    const newIdAvailable = idExpected + 1
+
+    //   Initializing HighRankAccount and getting the signature of the transaction
+    const signature = await initializeHighRank(program, wallet1);
+
+    //   Confirming the previous transaction in the validator node
+    await connection.confirmTransaction(signature.toString(), "confirmed")
+
+    //   Getting all the information relative to the transaction that has been carried out
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
+
+    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
+     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
+    */
+    const [key, data, buffer] = getReturnLog(transaction);
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    //   Fetching the data of the new account created through its PDA
+    const newHighRankAccount = await fetchHighRankAccount(program, wallet1.publicKey);
+
+    //   Getting the data of the HighRank ID's generator
+    const highRankIdGeneratorAfterCreating = await fetchIdAccount(program, "highRank");
+
+
+    //   The ID of the new HighRank Account must be equal to the idExpected param (which is the ID provided by the ID's generator for HighRank)
+    expect(new anchor.BN(newHighRankAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+
+    //   The new smaller availabled ID on the ID's generator must be equal to the param newIdAvailable (i.e. the new id available must have been incremented in +1)
+    expect(new anchor.BN(highRankIdGeneratorAfterCreating.smallerIdAvailable).eq(new anchor.BN(newIdAvailable))).to.be.true;
+
+    //   The identifierCode hash must be equal to the sha256 hash of '1111' (which is the identifier of every highRank to have certain privileges)
+    assert.equal(newHighRankAccount.identifierCodeHash, CryptoJS.SHA256("1111").toString());
+
+    //   The program must return true if everything is correct
+    expect(program_return).to.be.true;
+
+  });
+
+  it("System is initializated properly", async () => {
+
+    await initializeHighRank(program, wallet1);
+
+    // Giving extra funds to the HighRank to pay the initializacion space for the accounts
+    getExtraFunds(connection, 50, wallet1)
+
+    // Initializing the system by the HighRank owed by wallet1
+    const signature = await initializeSystem(program, wallet1)
+
+    // Confirming the previous transaction in the validator node
+    await connection.confirmTransaction(signature.toString())
+
+    // Getting all the information relative to the transaction that has been carried out
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+
+    // Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
+
+    const [key, data, buffer] = getReturnLog(transaction);
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    // Fetching the system account and IdHandler's that have been initializated
+    const newSystemAccount = await fetchSystemInitialization(program)
+    const degreeIdHandler = await fetchIdAccount(program, "degree")
+    const facultyIdHandler = await fetchIdAccount(program, "faculty")
+    const specialtyIdHandler = await fetchIdAccount(program, "specialty")
+    const subjectIdHandler = await fetchIdAccount(program, "subject")
+
+
+    // The newSystemAccount must have its param system_is_initializated as 'true'
+    expect(newSystemAccount.systemIsInitialized).to.be.true;
+
+    // The new ID Handler's must have their smallerIdAvailable param with '1' value
+    expect(new anchor.BN(degreeIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
+    expect(new anchor.BN(facultyIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
+    expect(new anchor.BN(specialtyIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
+    expect(new anchor.BN(subjectIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
+
+    // The program must return true if everything is correct
+    expect(program_return).to.be.true;
+
+  });
+
+  it("Professor is initializated properly", async () => {
+
+    /*
+    * Check if ID's generator for Professor accounts exist --> 
+    * If so, the smaller ID available is obtained from it 
+    * If not, the new ID will be 0 since no ID's generator created implies no Professor Acccount has been created previously
+   */
+    var idExpected = 1;
+    try {
+      let professorIdGeneratorBefore = await fetchIdAccount(program, "professor");
+      idExpected = professorIdGeneratorBefore.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+    }
+
+    /*
+    * Check if Code-Id Relation account exist --> 
+    * If not, the account that stores the relation must be created
+   */
+
+    await getExtraFunds(connection, 50, wallet2);
+
+    // Initializing ProfessorAccount and getting the signature of the transaction
+    const signature = await initializeProfessor(program, wallet2, [43222, 43212]);
+
+    // Confirming the previous transaction in the validator node
+    await connection.confirmTransaction(signature.toString(), "confirmed")
+
+    // Getting all the information relative to the transaction that has been carried out
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
+
+    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
+     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
+    */
+    const [key, data, buffer] = getReturnLog(transaction);
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    // Fetching the data of the new account created through its PDA
+    const newProfessorAccount = await fetchProfessorAccount(program, wallet2.publicKey);
+
+    // The ID of the new Professor Account must be equal to the idExpected param (which is the ID provided by the ID's generator for Professor)
+    expect(new anchor.BN(newProfessorAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+
+    // The identifierCode hash must be equal to the sha256 hash of '2222' (which is the identifier of every Professor to have certain privileges)
+    assert.equal(newProfessorAccount.identifierCodeHash, CryptoJS.SHA256("2222").toString());
+
+    // The subjects that the professor belongs to must be equal to the ones passed by parameter 
+    // 'deep.equal' is used to compare the actual content of the objects and not the memory reference (which will be always false since the references cannot be the same)'deep.equal' is used to compare the actual content of the objects and not the memory reference (which will be always false since the references cannot be the same)
+    expect(newProfessorAccount.subjects).to.deep.equal([43222, 43212]);
+
+    //Fetching the token account and checking balance
+    const mint = await findPDAforMint(program.programId)
+    const professorAssociatedTokenAccount = await getAssociatedTokenAddress(mint, wallet2.publicKey, false);
+    const tokenAccountBuyerAfter = await getAccount(connection, professorAssociatedTokenAccount);
+    const balance = Number(tokenAccountBuyerAfter.amount);
+
+    expect(new anchor.BN(balance).eq(new anchor.BN(0))).to.be.true;
+
+    // The program must return true if everything is correct
+    expect(program_return).to.be.true;
+
+  });
+
+  it("Reinitializing the same professor with different ID...", async () => {
+
+    /**
+     * Trying to initialize a new Professor Account for the same wallet --> 
+     * Since the PDA (address) is calculated through the wallet's PublicKey, the PDA will result in the same than the previous initialization
+     * This will raise up an error due to the constraint init, which cannot init an account with an address which is already initializated
+     */
+
+    await initializeProfessor(program, wallet2, [4322, 4312]);
+
+    try {
+      await initializeProfessor(program, wallet2, [4322, 4312]);
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "custom program error: 0x0"); //0x0 is the error for initializing an already initializated account
+      return;
+    }
+
+    // The test is expecting an error, so it will fail if an error is not raised
+    assert.fail("Expected an error to be thrown");
+
+  });
+
+  it("Student is initializated properly", async () => {
+
+    /*
+    * Check if ID's generator for Student accounts exist --> 
+    * If so, the smaller ID available is obtained from it 
+    * If not, the new ID will be 0 since no ID's generator created implies no Professor Acccount has been created previously
+   */
+    var idExpected = 1;
+    try {
+      let studentIdGeneratorBefore = await fetchIdAccount(program, "student");
+      idExpected = studentIdGeneratorBefore.smallerIdAvailable;
+    } catch (err) {
+      assert.instanceOf(err, Error);
+    }
+
+    // The new ID that should be available in the ID's generator after creating the new Professor account
+    const newIdAvailable = idExpected + 1
+
+    // Initializing StudentAccount and getting the signature of the transaction
+    const signature = await initializeStudent(program, wallet3, [43222, 43212, 43123, 43124, 43125, 43126, 43127, 43128]);
+
+    // Confirming the previous transaction in the validator node
+    await connection.confirmTransaction(signature.toString(), "confirmed")
+
+    // Getting all the information relative to the transaction that has been carried out
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
+
+    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
+     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
+    */
+    const [key, data, buffer] = getReturnLog(transaction);
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    // Fetching the data of the new account created through its PDA
+    const newStudentAccount = await fetchStudentAccount(program, wallet3.publicKey);
+
+    // Getting the data of the Student ID's generator
+    const studentIdGeneratorAfterCreating = await fetchIdAccount(program, "student");
+
+    // The ID of the new Student Account must be equal to the idExpected param (which is the ID provided by the ID's generator for Students)
+    expect(new anchor.BN(newStudentAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+
+    // The new smaller availabled ID on the ID's generator must be equal to the param newIdAvailable (i.e. the new id available must have been incremented in +1)
+    expect(new anchor.BN(studentIdGeneratorAfterCreating.smallerIdAvailable).eq(new anchor.BN(newIdAvailable))).to.be.true;
+
+    // The identifierCode hash must be equal to the sha256 hash of '1111' (which is the identifier of every highRank to have certain privileges)
+    assert.equal(newStudentAccount.identifierCodeHash, CryptoJS.SHA256("3333").toString());
+
+    expect(newStudentAccount.subjects).to.deep.equal([43222, 43212, 43123, 43124, 43125, 43126, 43127, 43128]);
+
+    //Fetching the token account and checking balance
+    const mint = await findPDAforMint(program.programId)
+    const studentAssociatedTokenAccount = await getAssociatedTokenAddress(mint, wallet3.publicKey, false);
+    const tokenAccountBuyerAfter = await getAccount(connection, studentAssociatedTokenAccount);
+    const balance = Number(tokenAccountBuyerAfter.amount);
+
+    // The program must return true if everything is correct
+    expect(program_return).to.be.true;
+  });
+
+  it("Faculty is properly initializated", async () => {
+
+    // Initializating highRank and giving extra funds (SOL) to wallet1 (which is allowed by a HighRank user)
+    await initializeHighRank(program, wallet1)
+    getExtraFunds(connection, 50, wallet1)
+
+    var idExpected = 1;
+
+    // Getting the ID generator that has been previously initializated by a HighRank
+    const account = await fetchIdAccount(program, "faculty");
+    idExpected = account.smallerIdAvailable
+
+    // Initializing FacultyAccount and getting the signature of the transaction
+    const signature = await initializeFaculty(program, wallet1, idExpected, "Asignatura de prueba")
+
+    // Confirming the previous transaction in the validator node
+    await connection.confirmTransaction(signature.toString())
+
+    // Getting all the information relative to the transaction that has been carried out
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+
+    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
+     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
+    */
+    const [key, data, buffer] = getReturnLog(transaction);
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    // Fetching the data of the new account created through its PDA
+    const newFacultyAccount = await fetchFacultyAccount(program, idExpected);
+
+    // Getting the data of the Faculty ID's generator
+    const FacultyIdGeneratorAccount = await fetchIdAccount(program, "faculty");
+
+    // The ID of the new Faculty Account must be equal to the idExpected param (which is the ID provided by the ID's generator for Faculty)
+    expect(new anchor.BN(newFacultyAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+
+    // The new smaller availabled ID on the ID's generator must be equal to 'idExpected' incremented in +1)
+    expect(new anchor.BN(FacultyIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+
+    // The program must return true if everything is correct
+    expect(program_return).to.be.true;
+  });
+
+  it("Degree is properly initializated", async () => {
+
+    await initializeHighRank(program, wallet1);
+    getExtraFunds(connection, 50, wallet1);
+
+    var idExpected = 1;
+
+
+    const account = await fetchIdAccount(program, "degree");
+    idExpected = account.smallerIdAvailable
+
+    const signature = await initializeDegree(program, wallet1, idExpected, "Grado de prueba perteneciente a Facultad con id=0", 1)
+    await connection.confirmTransaction(signature.toString())
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+    const [key, data, buffer] = getReturnLog(transaction);
+
+    const newDegreeAccount = await fetchDegreeAccount(program, idExpected);
+    const degreeIdGeneratorAccount = await fetchIdAccount(program, "degree");
+
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    expect(new anchor.BN(newDegreeAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    expect(new anchor.BN(degreeIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+    expect(program_return).to.be.true;
+
+  });
+
+  it("Several degrees are initializated in a single batch", async () => {
+
+    await initializeHighRank(program, wallet1);
+    getExtraFunds(connection, 50, wallet1);
+
+    const account = await fetchIdAccount(program, "degree");
+    const firstIdExpected = account.smallerIdAvailable
+
+    let event_emitted: any;
+    const listener = program.addEventListener("AcademicCatalogueBatchCreated", (event, _slot, _signature) => { event_emitted = event })
+
+    await createDegreesBatch(program, wallet1, firstIdExpected, [{ name: "Grado en lote 1", facultyId: 1 }, { name: "Grado en lote 2", facultyId: 1 }])
+
+    const firstDegreeAccount = await fetchDegreeAccount(program, firstIdExpected);
+    const secondDegreeAccount = await fetchDegreeAccount(program, firstIdExpected + 1);
+    const degreeIdGeneratorAccount = await fetchIdAccount(program, "degree");
+
+    expect(firstDegreeAccount.name).to.equal("Grado en lote 1");
+    expect(secondDegreeAccount.name).to.equal("Grado en lote 2");
+    expect(new anchor.BN(degreeIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(firstIdExpected + 2))).to.be.true;
+
+    // A single event summarizes the whole batch
+    expect(event_emitted.ids).to.deep.equal([firstIdExpected, firstIdExpected + 1]);
+    await program.removeEventListener(listener);
+
+  });
+
+  it("Specialty is properly initializated", async () => {
+
+    await initializeHighRank(program, wallet1)
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+
+    var idExpected = 1;
+
+
+    const account = await fetchIdAccount(program, "specialty");
+    idExpected = account.smallerIdAvailable
+
+
+    const signature = await initializeSpecialty(program, wallet1, idExpected, "Especialidad de prueba", 1)
+    await connection.confirmTransaction(signature.toString())
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+    const [key, data, buffer] = getReturnLog(transaction);
+
+    const newSpecialtyAccount = await fetchSpecialtyAccount(program, idExpected);
+    const degreeIdGeneratorAccount = await fetchIdAccount(program, "specialty");
+
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+
+    expect(new anchor.BN(newSpecialtyAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    expect(new anchor.BN(degreeIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+    expect(program_return).to.be.true;
+
+  });
+
+  it("Specialty with incorrect ID trying to be initializated", async () => {
+
+    await initializeHighRank(program, wallet1);
+    getExtraFunds(connection, 50, wallet1);
+    var idExpected = 1;
+
+    const account = await fetchIdAccount(program, "specialty");
+    idExpected = account.smallerIdAvailable
+
+    /*
+     * Account cannot be created since the provided ID is invalid (less than 0)
+     * One of the constraints in the SC must raise an error, which is expected to be an instance of Error 
+     * The error must also contain "A raw constraint was violated" as part of the message
+    */
+    try {
+      await initializeSpecialty(program, wallet1, idExpected, "Especialidad de prueba", -1)
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "A raw constraint was violated");
+      return;
+    }
+
+    assert.fail("Expected an error to be thrown");
+
+  });
+
+  it("Subject is properly initializated", async () => {
+
+    await initializeHighRank(program, wallet1)
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+
+    // Fetching the codeIdSubject relation before carrying out the test 
+    const codeIdSubjectRelationBefore = await fetchCodeIdRelationAccount(program);
+
+    // creating a new aux professor
+    const auxSignature = await initializeProfessor(program, wallet4, [43111, 44323]);
+    await connection.confirmTransaction(auxSignature.toString())
+
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+    var idExpected = 1;
+
+
+    const account = await fetchIdAccount(program, "subject");
+    idExpected = account.smallerIdAvailable
+
+    /* Due to a previous test --> there will always be a professor with ID '0' 
+     * The aux professor's id is passed as part of the array of professors' id via 'auxProfessorAccount.id'
+    */
+    const signature = await initializeSubject(program, wallet1, idExpected, "Especialidad de prueba", 1, 1, { first: {} }, 43111, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(signature.toString(), "confirmed")
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
+    const [key, data, buffer] = getReturnLog(transaction);
+
+    const newSubjectAccount = await fetchSubjectAccount(program, idExpected);
+    const subjectIdGeneratorAccount = await fetchIdAccount(program, "subject");
+
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    expect(new anchor.BN(newSubjectAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    expect(new anchor.BN(subjectIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+    expect(new anchor.BN(newSubjectAccount.degreeId).eq(new anchor.BN(1))).to.be.true;
+
+    assert.equal("QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs", newSubjectAccount.teachingProjectReference.identifier)
+
+    // Checking if the course is equal to SubjectCourse::First (enum in Rust)
+    expect(newSubjectAccount.course).to.deep.equal({ first: {} });
+
+    // The program return data must be the boolean 'true'
+    expect(program_return).to.be.true;
+
+  });
+
+  it("Subject is initializated with incorrect code", async () => {
+
+    await initializeHighRank(program, wallet1)
+
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+    var idExpected = 1;
+
+
+    const account = await fetchIdAccount(program, "subject");
+    idExpected = account.smallerIdAvailable
+
+
+    try {
+      await initializeSubject(program, wallet1, idExpected, "Especialidad de prueba", 1, 1, { first: {} }, -43112, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "RangeError [ERR_OUT_OF_RANGE]");
+      return;
+    }
+
+    assert.fail("Expected an error to be thrown");
+
+  });
+
+  it("Subject is cross-listed in another degree without being duplicated", async () => {
+
+    await initializeHighRank(program, wallet1)
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+
+    const account = await fetchIdAccount(program, "subject");
+    const subjectId = account.smallerIdAvailable
+
+    const signature = await initializeSubject(program, wallet1, subjectId, "Asignatura compartida", 1, 1, { first: {} }, 43117, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(signature.toString(), "confirmed")
+
+    // The subject is shared with the degree 1 without any specialty (-1)
+    await crossListSubject(program, wallet1, subjectId, 1, -1)
+
+    const subjectAccount = await fetchSubjectAccount(program, subjectId);
+    expect(subjectAccount.crossListings).to.deep.equal([{ degreeId: 1, specialtyId: -1 }]);
+    assert.equal("QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs", subjectAccount.teachingProjectReference.identifier)
+
+    // Cross-listing the subject twice in the same degree must fail
+    try {
+      await crossListSubject(program, wallet1, subjectId, 1, -1)
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "SubjectAlreadyOfferedInDegree");
+      return;
+    }
+
+    assert.fail("Expected an error to be thrown");
+
+  });
+
+  it("Subject metadata is stored and updated by a HighRank", async () => {
+
+    await initializeHighRank(program, wallet1)
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+
+    const subjectId = 1
+
+    // 6 ECTS taught in English during the second semester of the 11th course (beyond SubjectCourse::Nineth)
+    await updateSubjectMetadata(program, wallet1, subjectId, 60, { second: {} }, "en", { hybrid: {} }, -1, 11)
+
+    const metadata = await fetchSubjectMetadataAccount(program, subjectId);
+    expect(metadata.version).to.equal(1);
+    expect(metadata.ectsCredits).to.equal(60);
+    expect(metadata.semester).to.deep.equal({ second: {} });
+    expect(metadata.teachingLanguage).to.equal("en");
+    expect(metadata.modality).to.deep.equal({ hybrid: {} });
+    expect(metadata.coordinatorProfessorId).to.equal(-1);
+    expect(metadata.courseNumber).to.equal(11);
+
+    try {
+      await updateSubjectMetadata(program, wallet1, subjectId, 0, { second: {} }, "en", { hybrid: {} }, -1, 11)
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectEctsCredits");
+      return;
+    }
+
+    assert.fail("Expected an error to be thrown");
+
+  });
+
+  it("Subject is initializated with prerequisites and corequisites", async () => {
+
+    await initializeHighRank(program, wallet1)
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+
+    const account = await fetchIdAccount(program, "subject");
+    const subjectId = account.smallerIdAvailable
+
+    // Subjects 43117 and 43111 have been created in the previous tests
+    const signature = await initializeSubject(program, wallet1, subjectId, "Asignatura con requisitos", 1, 1, { second: {} }, 43118, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs", [43117], [43111])
+    await connection.confirmTransaction(signature.toString(), "confirmed")
+
+    const subjectAccount = await fetchSubjectAccount(program, subjectId);
+    expect(subjectAccount.prerequisites).to.deep.equal([43117]);
+    expect(subjectAccount.corequisites).to.deep.equal([43111]);
+
+    // A subject whose prerequisite does not exist cannot be created
+    const nextAccount = await fetchIdAccount(program, "subject");
+    try {
+      await initializeSubject(program, wallet1, nextAccount.smallerIdAvailable, "Asignatura con requisitos", 1, 1, { second: {} }, 43119, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs", [99999], [])
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "SubjectDependencyNotFound");
+      return;
+    }
+
+    assert.fail("Expected an error to be thrown");
+
+  });
+
+  /*
+  * This two next tests will only run properly once (since it will initializate the subject 43500, which cannot be created again) --> 
+  * For this test to pass again, validator node must be restarted with command 'solana-test-validator -r'
+  */
+
+  it("Proposal is created properly by Student", async () => {
+
+    await initializeHighRank(program, wallet1);
+
+    // Initializing new subject and fetching it
+
+    var subjectId = 1;
+    const subjectCode = 43600;
+
+
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
+
+
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
+
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+    const codeIdSubjectRelationAfter = await fetchCodeIdRelationAccount(program);
+
+    /*
+    * Using logging on console to verify some aspects of the codeIdRelation that are difficult to check via tests
+    */
+
+    console.log(newSubjectAccount)
+    console.log(codeIdSubjectRelationAfter.keyId)
+    console.log(codeIdSubjectRelationAfter.codeValue)
+
+    // Creating new student
+
+    await initializeStudent(program, wallet3, [subjectCode]);
+    getExtraFunds(connection, 50, wallet3) // wallet3 is allowed by a Student
+
+    // Creating new proposal
+
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
+
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
+
+
+    // Getting the professorProposal's id from professorProposalIdGenerator and if it does not exist, the ID expected (and used for the calculation of PDA) must be 0
+    try {
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
+
+    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta de prueba", "Desarollo o contenido de la propuesta de prueba", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+    const [key, data, buffer] = getReturnLog(transaction);
+
+    const newProposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    const proposalIdGeneratorAccount = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    const studentAccount = await fetchStudentAccount(program, wallet3.publicKey)
+    const subjectAccount = await fetchSubjectAccount(program, newProposalAccount.subjectId)
+
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    expect(new anchor.BN(newProposalAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    expect(new anchor.BN(proposalIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+
+    // Checking that the subjectId of the proposal is correct (the subject that the proposal is derived from)
+    expect(new anchor.BN(newProposalAccount.subjectId).eq(new anchor.BN(subjectAccount.id))).to.be.true;
+
+    // The ending_timestamp field must be equal to the publishingTimestamp plus 1 month (duration of the proposal) which is equal to 2592000 seconds in Unix Timestamp format
+    expect(Number(newProposalAccount.publishingTimestamp) + 2592000).eq(Number(newProposalAccount.endingTimestamp));
+
+    // The ID of the creator (sender) of the transaction must be properly registered in the field creatorId in the proposal
+    expect(new anchor.BN(studentAccount.id).eq(new anchor.BN(newProposalAccount.creatorId))).to.be.true;
+
+    // The expected votes (as established in the SC) must be equal to the amount of professors and students that owe the subject (1 students and 0 teachers + 20 extra votes expected)
+    // 20 votes are added in order to bear in mind the votes of professionals that are not registered in the subjects neither as students nor as professors
+    expect(new anchor.BN(newProposalAccount.expectedVotes).eq(new anchor.BN(21))).to.be.true;
+
+    expect(program_return).to.be.true;
+  });
+
+  it("Proposal is created properly by Professor", async () => {
+
+    await initializeHighRank(program, wallet1);
+
+    // Initializing new subject and fetching it
+
+    var subjectId = 1;
+    const subjectCode = 43700;
+
+
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
+
+
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
+
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+    const codeIdSubjectRelationAfter = await fetchCodeIdRelationAccount(program);
+
+    // Creating new professor
+
+    await initializeProfessor(program, wallet2, [subjectCode]);
+    getExtraFunds(connection, 50, wallet2)
+
+    // Creating new proposal
+
+    var correct = true;
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
+
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
+
+
+
+    // Getting the professorProposal's id from professorProposalIdGenerator and if it does not exist, the ID expected (and used for the calculation of PDA) must be 0
+    try {
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
+
+    const signature = await initializeProposalByProfessor(program, wallet2, idExpected, "Propuesta de prueba", "Desarollo o contenido de la propuesta de prueba", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
+    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+    const [key, data, buffer] = getReturnLog(transaction);
+
+    const newProposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    const proposalIdGeneratorAccount = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    const professorAccount = await fetchProfessorAccount(program, wallet2.publicKey)
+
+    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
+    const program_return = Boolean(reader_U8);
+
+    expect(new anchor.BN(newProposalAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    expect(new anchor.BN(proposalIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+
+    // Checking that the subjectId of the proposal is correct (the subject that the proposal is derived from)
+    expect(new anchor.BN(newProposalAccount.subjectId).eq(new anchor.BN(subjectId))).to.be.true;
+
+    // The ending_timestamp field must be equal to the publishingTimestamp plus 1 month (duration of the proposal) which is equal to 2592000 seconds in Unix Timestamp format
+    expect(Number(newProposalAccount.publishingTimestamp) + 2592000).eq(Number(newProposalAccount.endingTimestamp));
+
+    // The ID of the creator (sender) of the transaction must be properly registered in the field creatorId in the proposal
+    expect(new anchor.BN(professorAccount.id).eq(new anchor.BN(newProposalAccount.creatorId))).to.be.true;
+
+    // The expected votes (as established in the SC) must be equal to the amount of professors and students that owe the subject (1 students and 0 teachers + 20 extra votes expected)
+    // 20 votes are added in order to bear in mind the votes of professionals that are not registered in the subjects neither as students nor as professors
+    expect(new anchor.BN(newProposalAccount.expectedVotes).eq(new anchor.BN(21))).to.be.true;
+
+    expect(program_return).to.be.true;
+  });
+
+  it("Proposal is properly voted by a student", async () => {
+
+    // INITIALIZATING HIGH RANK 
+    await initializeHighRank(program, wallet1);
+
+    // INITIALIZATING A SUBJECT
+
+    var subjectId = 1;
+    const subjectCode = 43123;
+
+
+    const subjectAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectAccount.smallerIdAvailable
+
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
+
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+
+
+    // INITIALIZATING STUDENT
+
+    await initializeStudent(program, wallet3, [subjectCode]);
+    getExtraFunds(connection, 50, wallet3)
+
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
+
+
+    // CREATING NEW PROPOSAL (just exactly the previous tests)
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
+
+
+    try {
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
+
+    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
+
+
+    // Fetching the proposal account before voting
+    const proposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+
+
+    // VOTING THE PROPOSAL (by the creator)
+    const vote_signature = await voteProposalByStudent(program, wallet3, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), true, newSubjectAccount.code)
+    await connection.confirmTransaction(vote_signature.toString())
+    const transaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
+
+
+    // Getting the Program Return
+    const [key, data, buffer] = getReturnLog(transaction);
+    const program_return = new Borsh.BinaryReader(buffer).readString();
+
+    // Fetching the proposal account after voting
+    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+
+    // The creator is voting in favor of the proposal, so the supporting_votes field must be incremented in +1
+    expect(new anchor.BN(proposalAccount.supportingVotes + 1).eq(new anchor.BN(proposalAccountAfterVoting.supportingVotes))).to.be.true;
+
+    // The against_votes field must remain equal (i.e. with value 0)
+    expect(new anchor.BN(proposalAccount.againstVotes).eq(new anchor.BN(proposalAccountAfterVoting.againstVotes))).to.be.true;
+
+    // After voting, the votation must continue being 'VotationInProgress'
+    expect(program_return).to.deep.equal("VotationInProgress")
+
+  });
+
+  it("Proposal trying to be voted by the same student (the creator, in this case)", async () => {
+
+    // INITIALIZATING HIGH RANK 
+    await initializeHighRank(program, wallet1);
+
+    // INITIALIZATING A SUBJECT
+
+    var subjectId = 1;
+    const subjectCode = 44230;
+
+
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
+
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
+
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+
+
+    // INITIALIZATING STUDENT
+
+    await initializeStudent(program, wallet3, [subjectCode]);
+    getExtraFunds(connection, 50, wallet3)
+
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
+
+
+    // CREATING NEW PROPOSAL (just exactly the previous tests)
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
+
+    try {
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
+
+    // CREATING PROPOSAL 
+
+    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
+
+
+    // Fetching the proposal account before voting
+    const proposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+
+
+    // VOTING THE PROPOSAL (by the creator)
+    const vote_signature = await voteProposalByStudent(program, wallet3, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), true, newSubjectAccount.code)
+    await connection.confirmTransaction(vote_signature.toString())
+
+    try {
+      await voteProposalByStudent(program, wallet3, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), false, newSubjectAccount.code)
+
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "UserHasAlreadyVoted");
+      return
+    }
+
+    assert.fail("Expected an error to be thrown");
+
+  });
+
+  it("Proposal is trying to be created by an student that does not belong to it", async () => {
+
+    // INITIALIZATING HIGH RANK 
+    await initializeHighRank(program, wallet1);
+
+    // INITIALIZATING A SUBJECT
+
+    var subjectId = 1;
+    const subjectCode = 18000;
+
+
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
+
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
+
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+
+
+    // INITIALIZATING STUDENT
+
+    await initializeStudent(program, wallet3, [subjectCode + 3]);
+    getExtraFunds(connection, 50, wallet3)
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
+
+
+    // CREATING NEW PROPOSAL (just exactly the previous tests)
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
+
+
+    try {
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
+
+    // CREATING PROPOSAL 
+
+
+    try {
+      const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+      await connection.confirmTransaction(signature.toString())
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "UserDoesNotBelongToTheSubject");
+    }
+  });
+
+  it("Proposal is trying to be voted by an student that does not allow to it", async () => {
+
+    // INITIALIZATING HIGH RANK 
+    await initializeHighRank(program, wallet1);
+
+    // INITIALIZATING A SUBJECT
+
+    var subjectId = 1;
+
+    const subjectCode = 43193;
+
+
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
+
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
+
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+
+
+    // INITIALIZATING STUDENT
+
+    await initializeStudent(program, wallet3, [subjectCode]);
+    getExtraFunds(connection, 50, wallet3)
+
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
+
+
+    // CREATING NEW PROPOSAL (just exactly the previous tests)
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
+
+    try {
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
+
+    // CREATING PROPOSAL 
+
+    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
+
+
+    // Fetching the proposal account before voting
+    const proposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+
+    // INITIALIZING VOTING STUDENT
+    await initializeStudent(program, alternativeWallet, [])
+
+    // VOTING THE PROPOSAL (by the creator)
+    try {
+      await voteProposalByStudent(program, alternativeWallet, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), true, newSubjectAccount.code)
+      await connection.confirmTransaction(signature.toString())
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "UserDoesNotBelongToTheSubject");
+    }
+  });
+
+
+  //--------------------------TEST THAT ONLY WORK BY MODIFYING CERTAIN CONDITIONS OF THE SMART CONTRACT-----------------------//
+
+
+  /*
+    Condition modified: set maximum of votes as the minimum (expected_votes) instead of 2500
+  */
+
+  it("Reaching the number of expecting votes while voting true ", async () => {
+
+    // INITIALIZATING HIGH RANK 
+    await initializeHighRank(program, wallet1);
+
+    // INITIALIZATING A SUBJECT
+
+    var subjectId = 1;
+    const subjectCode = 50003;
+
+
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
+
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
+
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId)
+
+    // INITIALIZATING STUDENT
+
+    await initializeStudent(program, wallet3, [subjectCode]);
+    getExtraFunds(connection, 50, wallet3)
+
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
+
+
+    // CREATING NEW PROPOSAL (just exactly the previous tests)
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
+
+
+
+    try {
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
+
+    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
+
+    // VOTING TRUE BY DIFFERENT STUDENTS 
+
+    // Fetching the proposal account before voting
+    const proposalAccountBeforeVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+
+    const number_of_votes_expected = 20 // Must be set to 20 on SC (MAXIMUM_PARTICIPATION const)
 
-const findPDAforSystemInitialization = async (programId: anchor.web3.PublicKey): Promise<anchor.web3.PublicKey> => {
-  const [pda, _bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("systemInitialization")],
-    programId
-  );
-  return pda;
-}
+    // Subscribing to the event emitted when the votation is finished and accepted 
+    let event_emitted: any;
+    program.addEventListener("NewProfessorProposalCreated", (event, _slot, _signature) => { event_emitted = event })
 
-const findPDAforMintAuthority = async (programId: anchor.web3.PublicKey, mint: anchor.web3.PublicKey, high_rank_identifier_code: string): Promise<[anchor.web3.PublicKey, number]> => {
-  const [pda, bump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("mint_authority"), mint.toBytes(), utf8.encode(high_rank_identifier_code)],
-    programId
-  );
-  return [pda, bump]
-}
+    // We create students to vote in favor until the number of votes expected is reached and the votation is forced to finalize
+    var vote_signature: String;
 
-const fetchHighRankAccount = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.PublicKey) => {
-  return await program.account.highRank.fetch(await findPDAforHighRank(program.programId, authority))
-}
+    for (var i: number = 0; i < number_of_votes_expected; i++) {
+      alternativeWallet = await createWallet(connection, 10);
+      await initializeStudent(program, alternativeWallet, [subjectCode])
+      vote_signature = await voteProposalByStudent(program, alternativeWallet, proposalAccountBeforeVoting.id, proposalAccountBeforeVoting.subjectId, proposalAccountBeforeVoting.associatedProfessorProposalId, true, newSubjectAccount.code)
+      await connection.confirmTransaction(vote_signature.toString())
 
-const fetchProfessorAccount = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.PublicKey) => {
-  return await program.account.professor.fetch(await findPDAforProfessor(program.programId, authority))
-}
+    }
 
-const fetchFacultyAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
-  return await program.account.faculty.fetch(await findPDAforFaculty(program.programId, id))
-}
+    // Fetching the proposal account after voting
+    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
 
-const fetchDegreeAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
-  return await program.account.degree.fetch(await findPDAforDegree(program.programId, id))
-}
+    // The Creator's Public Key must be equal to the Wallet3's (Student) PublicKey
+    expect(proposalAccountAfterVoting.creatorPublicKey.toString()).to.deep.equal(wallet3.publicKey.toString())
 
-const fetchSpecialtyAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
-  return await program.account.specialty.fetch(await findPDAforSpecialty(program.programId, id))
-}
+    expect(new anchor.BN(proposalAccountAfterVoting.againstVotes + proposalAccountAfterVoting.supportingVotes).eq(new anchor.BN(number_of_votes_expected))).to.be.true;
 
-const fetchSubjectAccount = async (program: Program<TeachingProjectHandler>, id: Number) => {
-  return await program.account.subject.fetch(await findPDAforSubject(program.programId, id))
-}
+    // Getting the Program Return
+    const lastVotingTransaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
+    const [key, data, buffer] = getReturnLog(lastVotingTransaction);
+    const program_return = new Borsh.BinaryReader(buffer).readString();
 
-const fetchStudentAccount = async (program: Program<TeachingProjectHandler>, authority: anchor.web3.PublicKey) => {
-  return await program.account.student.fetch(await findPDAforStudent(program.programId, authority))
-}
+    // After completing the voting process, the votation must continue being 'WaitingForTeacher' since all students voted 'true' (supporting the proposal)
+    expect(program_return).to.deep.equal("WaitingForTeacher")
 
-const fetchProposalAccount = async (program: Program<TeachingProjectHandler>, id: Number, subject_code: number) => {
-  return await program.account.proposal.fetch(await findPDAforProposal(program.programId, id, subject_code))
-}
+    // fetching the professorProposalIdHandler and the professorProposal
+    const professorProposalIdHandler = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+    const idExpectedFromProfessorProposal = Number(professorProposalIdHandler.smallerIdAvailable - 1)
 
-const fetchProfessorProposalAccount = async (program: Program<TeachingProjectHandler>, id: number, subject_code: number) => {
-  return await program.account.professorProposal.fetch(await findPDAforProfessorProposal(program.programId, id, subject_code))
-}
+    const professorProposal = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
 
-const fetchIdAccount = async (program: Program<TeachingProjectHandler>, account_info: string) => {
-  return await program.account.idHandler.fetch(await findPDAforIdGenerator(program.programId, account_info))
-}
+    // Checking the result emitted is correct
+    expect(event_emitted).to.deep.equal({ proposalId: proposalAccountAfterVoting.id, professorProposalId: idExpectedFromProfessorProposal })
 
-const fetchProposalIdAccount = async (program: Program<TeachingProjectHandler>, is_professor: boolean, subject_code: number) => {
-  return await program.account.idHandler.fetch(await findPDAforProposalIdGenerator(program.programId, is_professor, subject_code))
-}
+    // Evaluating the professorProposal is properly initializated 
+    expect(new anchor.BN(proposalAccountAfterVoting.associatedProfessorProposalId).eq(new anchor.BN(professorProposal.id))).to.be.true;
+    expect(professorProposal.state).to.deep.equal({ pending: {} });
 
-const fetchCodeIdRelationAccount = async (program: Program<TeachingProjectHandler>) => {
-  return await program.account.codeIdSubjectRelation.fetch(await findPDAforCodeIdRelation(program.programId))
-}
+  });
 
-const fetchSystemInitialization = async (program: Program<TeachingProjectHandler>) => {
-  return await program.account.systemInitialization.fetch(await findPDAforSystemInitialization(program.programId))
-}
 
+  it("Forcing the test to finalize (reaching the number of expecting votes) and tokens -credits- are properly delivered ", async () => {
 
-const getReturnLog = (confirmedTransaction) => {
-  const prefix = "Program return: ";
-  let log = confirmedTransaction.meta.logMessages.find((log) =>
-    log.startsWith(prefix)
-  );
-  log = log.slice(prefix.length);
-  const [key, data] = log.split(" ", 2);
-  const buffer = Buffer.from(data, "base64");
-  return [key, data, buffer];
-};
+    // INITIALIZATING HIGH RANK 
+    await initializeHighRank(program, wallet1);
 
+    // INITIALIZATING A SUBJECT
 
-function numberToLEBytes(number) {
-  const buffer = new ArrayBuffer(4);
-  const view = new DataView(buffer);
-  view.setUint32(0, number, true);
-  const bytes = new Uint8Array(buffer);
-  return bytes;
-}
+    var subjectId = 1;
+    const subjectCode = 80003;
 
 
-describe("Testing the Teaching Project Handler Smart Contract...\n\n", () => {
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
 
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
 
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-  // shared objects
-  const program = anchor.workspace.TeachingProjectHandler as Program<TeachingProjectHandler>;
-  const connection = anchor.getProvider().connection;
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId)
 
-  let wallet1: anchor.web3.Keypair;
-  let wallet2: anchor.web3.Keypair;
-  let wallet3: anchor.web3.Keypair;
-  let wallet4: anchor.web3.Keypair;
+    // INITIALIZATING STUDENT
 
-  let alternativeWallet: anchor.web3.Keypair;
+    await initializeStudent(program, wallet3, [subjectCode]);
+    getExtraFunds(connection, 50, wallet3)
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
 
-  beforeEach(async () => {
 
-    wallet1 = await createWallet(connection, 10); // HighRank
-    wallet2 = await createWallet(connection, 40); // Professor
-    wallet3 = await createWallet(connection, 10); // Student
-    wallet4 = await createWallet(connection, 10);
-    alternativeWallet = await createWallet(connection, 10)
+    // CREATING NEW PROPOSAL (just exactly the previous tests)
+
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
 
-  });
 
-  it("HighRank is initializated properly", async () => {
 
-    /*
-     * Check if ID's generator for HighRank accounts exist --> 
-     * If so, the smaller ID available is obtained from it 
-     * If not, the new ID will be 1 since no ID's generator created implies no HighRank Account has been created previously
-    */
-    var idExpected = 1;
     try {
-      let highRankIdGeneratorBefore = await fetchIdAccount(program, "highRank");
-      idExpected = highRankIdGeneratorBefore.smallerIdAvailable
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
     } catch (err) {
       assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
     }
 
-    //   The new ID that should be available in the ID's generator after creating the new HighRank account
-    const newIdAvailable = idExpected + 1
+    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
 
-    //   Initializing HighRankAccount and getting the signature of the transaction
-    const signature = await initializeHighRank(program, wallet1);
+    // VOTING TRUE BY DIFFERENT STUDENTS 
 
-    //   Confirming the previous transaction in the validator node
-    await connection.confirmTransaction(signature.toString(), "confirmed")
+    // Fetching the proposal account before voting
+    const proposalAccountBeforeVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
 
-    //   Getting all the information relative to the transaction that has been carried out
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
+    const number_of_votes_expected = 20 // Must be set to 20 on SC (MAXIMUM_PARTICIPATION const)
 
-    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
-     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
-    */
-    const [key, data, buffer] = getReturnLog(transaction);
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
+    // Subscribing to the event emitted when the votation is finished and accepted 
+    let event_emitted: any;
+    program.addEventListener("NewProfessorProposalCreated", (event, _slot, _signature) => { event_emitted = event })
 
-    //   Fetching the data of the new account created through its PDA
-    const newHighRankAccount = await fetchHighRankAccount(program, wallet1.publicKey);
+    // We create students to vote in favor until the number of votes expected is reached and the votation is forced to finalize
+    var vote_signature: String;
 
-    //   Getting the data of the HighRank ID's generator
-    const highRankIdGeneratorAfterCreating = await fetchIdAccount(program, "highRank");
+    for (var i: number = 0; i < number_of_votes_expected; i++) {
+      alternativeWallet = await createWallet(connection, 10);
+      await initializeStudent(program, alternativeWallet, [subjectCode])
+      vote_signature = await voteProposalByStudent(program, alternativeWallet, proposalAccountBeforeVoting.id, proposalAccountBeforeVoting.subjectId, proposalAccountBeforeVoting.associatedProfessorProposalId, true, newSubjectAccount.code)
+      await connection.confirmTransaction(vote_signature.toString())
 
+    }
 
-    //   The ID of the new HighRank Account must be equal to the idExpected param (which is the ID provided by the ID's generator for HighRank)
-    expect(new anchor.BN(newHighRankAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    // Fetching the proposal account after voting
+    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
 
-    //   The new smaller availabled ID on the ID's generator must be equal to the param newIdAvailable (i.e. the new id available must have been incremented in +1)
-    expect(new anchor.BN(highRankIdGeneratorAfterCreating.smallerIdAvailable).eq(new anchor.BN(newIdAvailable))).to.be.true;
+    expect(new anchor.BN(proposalAccountAfterVoting.againstVotes + proposalAccountAfterVoting.supportingVotes).eq(new anchor.BN(number_of_votes_expected))).to.be.true;
 
-    //   The identifierCode hash must be equal to the sha256 hash of '1111' (which is the identifier of every highRank to have certain privileges)
-    assert.equal(newHighRankAccount.identifierCodeHash, CryptoJS.SHA256("1111").toString());
+    // Getting the Program Return
+    const lastVotingTransaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
+    const [key, data, buffer] = getReturnLog(lastVotingTransaction);
+    const program_return = new Borsh.BinaryReader(buffer).readString();
 
-    //   The program must return true if everything is correct
-    expect(program_return).to.be.true;
+    // After completing the voting process, the votation must continue being 'WaitingForTeacher' since all students voted 'true' (supporting the proposal)
+    expect(program_return).to.deep.equal("WaitingForTeacher")
 
-  });
+    // fetching the professorProposalIdHandler and the professorProposal
+    const professorProposalIdHandler = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+    const idExpectedFromProfessorProposal = Number(professorProposalIdHandler.smallerIdAvailable - 1)
 
-  it("System is initializated properly", async () => {
+    const professorProposal = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
 
-    await initializeHighRank(program, wallet1);
+    // Checking the result emitted is correct
+    expect(event_emitted).to.deep.equal({ proposalId: proposalAccountAfterVoting.id, professorProposalId: idExpectedFromProfessorProposal })
 
-    // Giving extra funds to the HighRank to pay the initializacion space for the accounts
-    getExtraFunds(connection, 50, wallet1)
+    // Evaluating the professorProposal is properly initializated 
+    expect(new anchor.BN(proposalAccountAfterVoting.associatedProfessorProposalId).eq(new anchor.BN(professorProposal.id))).to.be.true;
+    expect(professorProposal.state).to.deep.equal({ pending: {} });
 
-    // Initializing the system by the HighRank owed by wallet1
-    const signature = await initializeSystem(program, wallet1)
+    // Updating Proposal by Professor
+    const professorWallet = await createWallet(connection, 10);
+    await initializeProfessor(program, professorWallet, [subjectCode])
+    await assignProfessorProposalByRoundRobin(program, wallet1, proposalAccountAfterVoting.id, professorProposal.id, newSubjectAccount.code, newSubjectAccount.id, [professorWallet.publicKey])
+    await updateProposalByProfessor(program, professorWallet, proposalAccountAfterVoting.id, professorProposal.id, newSubjectAccount.code, newSubjectAccount.id, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
 
-    // Confirming the previous transaction in the validator node
-    await connection.confirmTransaction(signature.toString())
+    const proposalAccountAfterProfessorUpdating = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    const professorProposalAfterProfessorUpdating = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
 
-    // Getting all the information relative to the transaction that has been carried out
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+    expect(professorProposalAfterProfessorUpdating.state).to.deep.equal({ complete: {} });
+    expect(proposalAccountAfterProfessorUpdating.state).to.deep.equal({ waitingForHighRank: {} });
 
-    // Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
+    // Updating Proposal by HighRank
+    const professorTokenAccount = await getAssociatedTokenAddress(await findPDAforMint(program.programId), professorWallet.publicKey, false);
+    const professorBalanceBeforeAccepting = Number((await getAccount(connection, professorTokenAccount)).amount)
 
-    const [key, data, buffer] = getReturnLog(transaction);
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
+    await updateProposalByHighRank(program, wallet1, proposalAccountAfterVoting.id, professorProposal.id, newSubjectAccount.code, newSubjectAccount.id)
 
-    // Fetching the system account and IdHandler's that have been initializated
-    const newSystemAccount = await fetchSystemInitialization(program)
-    const degreeIdHandler = await fetchIdAccount(program, "degree")
-    const facultyIdHandler = await fetchIdAccount(program, "faculty")
-    const specialtyIdHandler = await fetchIdAccount(program, "specialty")
-    const subjectIdHandler = await fetchIdAccount(program, "subject")
+    // The work was delivered more than a week before the deadline --> 1 token plus 1 token of bonus (10 units each)
+    const professorBalanceAfterAccepting = Number((await getAccount(connection, professorTokenAccount)).amount)
+    expect(professorBalanceAfterAccepting).eq(professorBalanceBeforeAccepting + 20);
 
+    const proposalAccountAfterHighRankUpdating = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    const professorProposalAfterHighRankUpdating = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
 
-    // The newSystemAccount must have its param system_is_initializated as 'true'
-    expect(newSystemAccount.systemIsInitialized).to.be.true;
+    expect(professorProposalAfterHighRankUpdating.state).to.deep.equal({ complete: {} });
+    expect(proposalAccountAfterHighRankUpdating.state).to.deep.equal({ accepted: {} });
+
+    //Checking the giving of tokens
 
-    // The new ID Handler's must have their smallerIdAvailable param with '1' value
-    expect(new anchor.BN(degreeIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
-    expect(new anchor.BN(facultyIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
-    expect(new anchor.BN(specialtyIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
-    expect(new anchor.BN(subjectIdHandler.smallerIdAvailable).eq(new anchor.BN(1))).to.be.true;
+    const mint = await findPDAforMint(program.programId)
+    const studentAssociatedTokenAccount = await getAssociatedTokenAddress(mint, proposalAccountAfterHighRankUpdating.creatorPublicKey, false);
 
-    // The program must return true if everything is correct
-    expect(program_return).to.be.true;
+    var balanceBeforeGiving: number = 0
 
-  });
+    const tokenAccountBuyerBefore = await getAccount(connection, studentAssociatedTokenAccount);
+    balanceBeforeGiving = Number(tokenAccountBuyerBefore.amount)
 
-  it("Professor is initializated properly", async () => {
+    await giveCreditToStudent(program, wallet1, proposalAccountAfterHighRankUpdating.id, proposalAccountAfterHighRankUpdating.creatorPublicKey, "1111", newSubjectAccount.code)
 
-    /*
-    * Check if ID's generator for Professor accounts exist --> 
-    * If so, the smaller ID available is obtained from it 
-    * If not, the new ID will be 0 since no ID's generator created implies no Professor Acccount has been created previously
-   */
-    var idExpected = 1;
-    try {
-      let professorIdGeneratorBefore = await fetchIdAccount(program, "professor");
-      idExpected = professorIdGeneratorBefore.smallerIdAvailable
-    } catch (err) {
-      assert.instanceOf(err, Error);
-    }
+    const tokenAccountBuyerAfter = await getAccount(connection, studentAssociatedTokenAccount);
+    const balanceAfterGiving = Number(tokenAccountBuyerAfter.amount);
 
-    /*
-    * Check if Code-Id Relation account exist --> 
-    * If not, the account that stores the relation must be created
-   */
+    // The reward is 1 token --> 10 units since the mint has 1 decimal
+    expect(new anchor.BN(balanceAfterGiving).eq(new anchor.BN(balanceBeforeGiving + 10))).to.be.true;
 
-    await getExtraFunds(connection, 50, wallet2);
+    // The outcomes are recorded on the reputation of the creator and of the professor that delivered the work on time
+    const creatorReputation = await program.account.reputation.fetch(await findPDAforReputation(program.programId, proposalAccountAfterHighRankUpdating.creatorPublicKey));
+    expect(creatorReputation.grantedProposals).eq(1);
+    expect(creatorReputation.subjects[0].subjectCode).eq(subjectCode);
+    expect(creatorReputation.score).eq(10);
 
-    // Initializing ProfessorAccount and getting the signature of the transaction
-    const signature = await initializeProfessor(program, wallet2, [43222, 43212]);
+    const professorReputation = await program.account.reputation.fetch(await findPDAforReputation(program.programId, professorWallet.publicKey));
+    expect(professorReputation.onTimeDeliveries).eq(1);
+    expect(professorReputation.lateDeliveries).eq(0);
 
-    // Confirming the previous transaction in the validator node
-    await connection.confirmTransaction(signature.toString(), "confirmed")
+    const professorAccount = await program.account.professor.fetch(await findPDAforProfessor(program.programId, professorWallet.publicKey));
+    expect(professorAccount.rewards).eq(1);
 
-    // Getting all the information relative to the transaction that has been carried out
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
+  });
 
-    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
-     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
-    */
-    const [key, data, buffer] = getReturnLog(transaction);
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
 
-    // Fetching the data of the new account created through its PDA
-    const newProfessorAccount = await fetchProfessorAccount(program, wallet2.publicKey);
+  it("Forcing the test to finalize (reaching the number of expecting votes) and voting false", async () => {
 
-    // The ID of the new Professor Account must be equal to the idExpected param (which is the ID provided by the ID's generator for Professor)
-    expect(new anchor.BN(newProfessorAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    // INITIALIZATING HIGH RANK 
+    await initializeHighRank(program, wallet1);
 
-    // The identifierCode hash must be equal to the sha256 hash of '2222' (which is the identifier of every Professor to have certain privileges)
-    assert.equal(newProfessorAccount.identifierCodeHash, CryptoJS.SHA256("2222").toString());
+    // INITIALIZATING A SUBJECT
 
-    // The subjects that the professor belongs to must be equal to the ones passed by parameter 
-    // 'deep.equal' is used to compare the actual content of the objects and not the memory reference (which will be always false since the references cannot be the same)'deep.equal' is used to compare the actual content of the objects and not the memory reference (which will be always false since the references cannot be the same)
-    expect(newProfessorAccount.subjects).to.deep.equal([43222, 43212]);
+    var subjectId = 1;
+    const subjectCode = 78003;
 
-    //Fetching the token account and checking balance
-    const mint = await findPDAforMint(program.programId)
-    const professorAssociatedTokenAccount = await getAssociatedTokenAddress(mint, wallet2.publicKey, false);
-    const tokenAccountBuyerAfter = await getAccount(connection, professorAssociatedTokenAccount);
-    const balance = Number(tokenAccountBuyerAfter.amount);
 
-    expect(new anchor.BN(balance).eq(new anchor.BN(0))).to.be.true;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    subjectId = subjectIdAccount.smallerIdAvailable
 
-    // The program must return true if everything is correct
-    expect(program_return).to.be.true;
 
-  });
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-  it("Reinitializing the same professor with different ID...", async () => {
+    const newSubjectAccount = await fetchSubjectAccount(program, subjectId)
 
-    /**
-     * Trying to initialize a new Professor Account for the same wallet --> 
-     * Since the PDA (address) is calculated through the wallet's PublicKey, the PDA will result in the same than the previous initialization
-     * This will raise up an error due to the constraint init, which cannot init an account with an address which is already initializated
-     */
+    // INITIALIZATING STUDENT
 
-    await initializeProfessor(program, wallet2, [4322, 4312]);
+    await initializeStudent(program, wallet3, [subjectCode]);
+    getExtraFunds(connection, 50, wallet3)
+    var idExpected = 1;
+    var professorProposalIdExpected = 1;
 
-    try {
-      await initializeProfessor(program, wallet2, [4322, 4312]);
-    } catch (err) {
-      assert.instanceOf(err, Error);
-      assert.include(err.toString(), "custom program error: 0x0"); //0x0 is the error for initializing an already initializated account
-      return;
-    }
 
-    // The test is expecting an error, so it will fail if an error is not raised
-    assert.fail("Expected an error to be thrown");
+    // CREATING NEW PROPOSAL (just exactly the previous tests)
 
-  });
+    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    idExpected = account.smallerIdAvailable
 
-  it("Student is initializated properly", async () => {
 
-    /*
-    * Check if ID's generator for Student accounts exist --> 
-    * If so, the smaller ID available is obtained from it 
-    * If not, the new ID will be 0 since no ID's generator created implies no Professor Acccount has been created previously
-   */
-    var idExpected = 1;
     try {
-      let studentIdGeneratorBefore = await fetchIdAccount(program, "student");
-      idExpected = studentIdGeneratorBefore.smallerIdAvailable;
+      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
+      professorProposalIdExpected = account.smallerIdAvailable
     } catch (err) {
       assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
     }
 
-    // The new ID that should be available in the ID's generator after creating the new Professor account
-    const newIdAvailable = idExpected + 1
-
-    // Initializing StudentAccount and getting the signature of the transaction
-    const signature = await initializeStudent(program, wallet3, [43222, 43212, 43123, 43124, 43125, 43126, 43127, 43128]);
-
-    // Confirming the previous transaction in the validator node
-    await connection.confirmTransaction(signature.toString(), "confirmed")
-
-    // Getting all the information relative to the transaction that has been carried out
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
-
-    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
-     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
-    */
-    const [key, data, buffer] = getReturnLog(transaction);
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
+    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    await connection.confirmTransaction(signature.toString())
 
-    // Fetching the data of the new account created through its PDA
-    const newStudentAccount = await fetchStudentAccount(program, wallet3.publicKey);
+    // VOTING TRUE BY DIFFERENT STUDENTS 
 
-    // Getting the data of the Student ID's generator
-    const studentIdGeneratorAfterCreating = await fetchIdAccount(program, "student");
+    // Fetching the proposal account before voting
+    const proposalAccountBeforeVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
 
-    // The ID of the new Student Account must be equal to the idExpected param (which is the ID provided by the ID's generator for Students)
-    expect(new anchor.BN(newStudentAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    const number_of_votes_expected = 20 // Must be set to 20 on SC (MAXIMUM_PARTICIPATION const)
 
-    // The new smaller availabled ID on the ID's generator must be equal to the param newIdAvailable (i.e. the new id available must have been incremented in +1)
-    expect(new anchor.BN(studentIdGeneratorAfterCreating.smallerIdAvailable).eq(new anchor.BN(newIdAvailable))).to.be.true;
+    // We create students to vote in favor until the number of votes expected is reached and the votation is forced to finalize
+    let vote_signature: String;
+    for (var i = 0; i < number_of_votes_expected; i++) {
+      alternativeWallet = await createWallet(connection, 10);
+      await initializeStudent(program, alternativeWallet, [subjectCode])
+      vote_signature = await voteProposalByStudent(program, alternativeWallet, Number(proposalAccountBeforeVoting.id), Number(proposalAccountBeforeVoting.subjectId), Number(proposalAccountBeforeVoting.associatedProfessorProposalId), false, newSubjectAccount.code)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    // The identifierCode hash must be equal to the sha256 hash of '1111' (which is the identifier of every highRank to have certain privileges)
-    assert.equal(newStudentAccount.identifierCodeHash, CryptoJS.SHA256("3333").toString());
+    // Fetching the proposal account after voting
+    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    expect(new anchor.BN(proposalAccountAfterVoting.againstVotes + proposalAccountAfterVoting.supportingVotes).eq(new anchor.BN(number_of_votes_expected))).to.be.true;
 
-    expect(newStudentAccount.subjects).to.deep.equal([43222, 43212, 43123, 43124, 43125, 43126, 43127, 43128]);
+    // Getting the Program Return
+    const lastVotingTransaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
+    const [key, data, buffer] = getReturnLog(lastVotingTransaction);
+    const program_return = new Borsh.BinaryReader(buffer).readString();
 
-    //Fetching the token account and checking balance
-    const mint = await findPDAforMint(program.programId)
-    const studentAssociatedTokenAccount = await getAssociatedTokenAddress(mint, wallet3.publicKey, false);
-    const tokenAccountBuyerAfter = await getAccount(connection, studentAssociatedTokenAccount);
-    const balance = Number(tokenAccountBuyerAfter.amount);
+    // After completing the voting process, the votation must continue being 'Rejecting' since all students voted 'false' (against the proposal)
+    expect(program_return).to.deep.equal("Rejected")
 
-    // The program must return true if everything is correct
-    expect(program_return).to.be.true;
-  });
+    // The deposit of the proposal must be settled before deleting it (the votation reached the quorum, so it is returned)
+    await settleProposalDeposit(program, wallet1, proposalAccountAfterVoting.id, proposalAccountAfterVoting.subjectId, newSubjectAccount.code, proposalAccountAfterVoting.creatorPublicKey)
 
-  it("Faculty is properly initializated", async () => {
+    //Trying to delete the rejectedProposal by a HighRank
+    await deleteRejectedProposalByHighRank(program, wallet1, proposalAccountAfterVoting.id, proposalAccountAfterVoting.subjectId, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code);
 
-    // Initializating highRank and giving extra funds (SOL) to wallet1 (which is allowed by a HighRank user)
-    await initializeHighRank(program, wallet1)
-    getExtraFunds(connection, 50, wallet1)
 
-    var idExpected = 1;
+    // Trying to fetch the proposal account and the professor proposal account --> they both must not exist 
+    try {
+      await fetchProposalAccount(program, proposalAccountAfterVoting.id, newSubjectAccount.code);
+      assert.fail("Fetching the proposalAccount does not failed and the fail was expected")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
 
-    // Getting the ID generator that has been previously initializated by a HighRank
-    const account = await fetchIdAccount(program, "faculty");
-    idExpected = account.smallerIdAvailable
+    try {
+      await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code);
+      assert.fail("Fetching the proposalAccount does not failed and the fail was expected")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
 
-    // Initializing FacultyAccount and getting the signature of the transaction
-    const signature = await initializeFaculty(program, wallet1, idExpected, "Asignatura de prueba")
+    // The ids of the deleted accounts must be handed out first when creating the next proposal of the subject
+    const proposalIdHandlerAfterDeleting = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
+    const professorProposalIdHandlerAfterDeleting = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
 
-    // Confirming the previous transaction in the validator node
-    await connection.confirmTransaction(signature.toString())
+    expect(proposalIdHandlerAfterDeleting.reusedId.map((x) => Number(x))).to.deep.equal([proposalAccountAfterVoting.id]);
+    expect(professorProposalIdHandlerAfterDeleting.reusedId.map((x) => Number(x))).to.deep.equal([proposalAccountAfterVoting.associatedProfessorProposalId]);
 
-    // Getting all the information relative to the transaction that has been carried out
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
+  });
 
-    /* Getting the Return Log from the transaction information in order to get the boolean returned from the SC (Rust) function
-     * 'buffer' contains the raw binary information of the return, which contains the information of the boolean returned by the tested method
-    */
-    const [key, data, buffer] = getReturnLog(transaction);
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
 
-    // Fetching the data of the new account created through its PDA
-    const newFacultyAccount = await fetchFacultyAccount(program, idExpected);
+  it("Proposal is edited before being voted and withdrawn by its creator", async () => {
 
-    // Getting the data of the Faculty ID's generator
-    const FacultyIdGeneratorAccount = await fetchIdAccount(program, "faculty");
+    const subjectCode = 43119;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    // The ID of the new Faculty Account must be equal to the idExpected param (which is the ID provided by the ID's generator for Faculty)
-    expect(new anchor.BN(newFacultyAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de edicion", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    // The new smaller availabled ID on the ID's generator must be equal to 'idExpected' incremented in +1)
-    expect(new anchor.BN(FacultyIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+    const creatorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
 
-    // The program must return true if everything is correct
-    expect(program_return).to.be.true;
-  });
+    // Both id handlers are created along with the subject, so the ids can be read before creating the proposal
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-  it("Degree is properly initializated", async () => {
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta a editar", "Contenido original", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    await initializeHighRank(program, wallet1);
-    getExtraFunds(connection, 50, wallet1);
+    const proposalBeforeEditing = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalBeforeEditing.version).eq(1);
 
-    var idExpected = 1;
+    // Only the creator is allowed to edit the proposal
+    try {
+      await editProposal(program, wallet1, proposalId, subjectId, subjectCode, "Propuesta ajena", "Contenido ajeno")
+      assert.fail("Editing the proposal by a user that is not its creator was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "UserIsNotTheProposalCreator");
+    }
 
+    const editSignature = await editProposal(program, creatorWallet, proposalId, subjectId, subjectCode, "Propuesta editada", "Contenido editado y ampliado antes de la votacion")
+    await connection.confirmTransaction(editSignature.toString())
 
-    const account = await fetchIdAccount(program, "degree");
-    idExpected = account.smallerIdAvailable
+    const proposalAfterEditing = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAfterEditing.version).eq(2);
+    expect(proposalAfterEditing.title).to.deep.equal("Propuesta editada");
+    expect(proposalAfterEditing.content).to.deep.equal("Contenido editado y ampliado antes de la votacion");
+    expect(proposalAfterEditing.contentHashChain).to.not.deep.equal(proposalBeforeEditing.contentHashChain);
 
-    const signature = await initializeDegree(program, wallet1, idExpected, "Grado de prueba perteneciente a Facultad con id=0", 1)
-    await connection.confirmTransaction(signature.toString())
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
-    const [key, data, buffer] = getReturnLog(transaction);
+    // Withdrawing the proposal closes both accounts and removes it from the pending proposals of the subject
+    const withdrawSignature = await withdrawProposal(program, creatorWallet, proposalId, subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(withdrawSignature.toString())
 
-    const newDegreeAccount = await fetchDegreeAccount(program, idExpected);
-    const degreeIdGeneratorAccount = await fetchIdAccount(program, "degree");
+    try {
+      await fetchProposalAccount(program, proposalId, subjectCode);
+      assert.fail("Fetching the proposalAccount does not failed and the fail was expected")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "Account does not exist");
+    }
 
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
+    const subjectAccount = await fetchSubjectAccount(program, subjectId);
+    expect(subjectAccount.pendingProposals.map((x) => Number(x))).to.not.include(proposalId);
 
-    expect(new anchor.BN(newDegreeAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
-    expect(new anchor.BN(degreeIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
-    expect(program_return).to.be.true;
+    const proposalIdAccountAfterWithdrawing = await fetchProposalIdAccount(program, false, subjectCode)
+    expect(proposalIdAccountAfterWithdrawing.reusedId.map((x) => Number(x))).to.deep.equal([proposalId]);
 
   });
 
-  it("Specialty is properly initializated", async () => {
+  it("Proposal stores its body off-chain along with its attachments", async () => {
 
-    await initializeHighRank(program, wallet1)
-    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+    const subjectCode = 43120;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    var idExpected = 1;
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con adjuntos", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
+    const creatorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
 
-    const account = await fetchIdAccount(program, "specialty");
-    idExpected = account.smallerIdAvailable
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
+    // Attachments whose mime type does not follow the 'type/subtype' format are rejected
+    const wrongAttachments = [{ name: "Horario.pdf", reference: "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG", mimeType: "pdf" }]
+    try {
+      await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con adjuntos", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, wrongAttachments)
+      assert.fail("Creating a proposal with a wrong attachment was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectProposalAttachment");
+    }
 
-    const signature = await initializeSpecialty(program, wallet1, idExpected, "Especialidad de prueba", 1)
+    const attachments = [
+      { name: "Horario.pdf", reference: "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG", mimeType: "application/pdf" },
+      { name: "Temario.png", reference: "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o", mimeType: "image/png" }
+    ]
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con adjuntos", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, attachments)
     await connection.confirmTransaction(signature.toString())
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
-    const [key, data, buffer] = getReturnLog(transaction);
 
-    const newSpecialtyAccount = await fetchSpecialtyAccount(program, idExpected);
-    const degreeIdGeneratorAccount = await fetchIdAccount(program, "specialty");
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.content).to.deep.equal("Resumen");
+    expect(proposalAccount.contentReference.identifier).eq(DEFAULT_PROPOSAL_CONTENT_REFERENCE);
+    expect(proposalAccount.contentSha256).to.deep.equal(CryptoJS.SHA256("Resumen").toString());
+    expect(proposalAccount.attachments).to.deep.equal(attachments);
 
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
+  });
 
+  it("Proposal is discussed through comments and amended before the end of the votation", async () => {
 
-    expect(new anchor.BN(newSpecialtyAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
-    expect(new anchor.BN(degreeIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
-    expect(program_return).to.be.true;
+    const subjectCode = 43121;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-  });
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con debate", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-  it("Specialty with incorrect ID trying to be initializated", async () => {
+    const creatorWallet = await createWallet(connection, 10);
+    const memberWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeStudent(program, memberWallet, [subjectCode]);
 
-    await initializeHighRank(program, wallet1);
-    getExtraFunds(connection, 50, wallet1);
-    var idExpected = 1;
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    const account = await fetchIdAccount(program, "specialty");
-    idExpected = account.smallerIdAvailable
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta a debatir", "Resumen original", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    /*
-     * Account cannot be created since the provided ID is invalid (less than 0)
-     * One of the constraints in the SC must raise an error, which is expected to be an instance of Error 
-     * The error must also contain "A raw constraint was violated" as part of the message
-    */
+    // A top-level comment and a reply to it
+    await commentProposalByStudent(program, memberWallet, proposalId, subjectId, subjectCode, 0, -1, "", "Propongo cambiar la bibliografia")
+    await commentProposalByStudent(program, creatorWallet, proposalId, subjectId, subjectCode, 1, 0, DEFAULT_PROPOSAL_CONTENT_REFERENCE, "")
+
+    const reply = await fetchCommentAccount(program, proposalId, subjectCode, 1);
+    expect(reply.parentCommentId).eq(0);
+    expect(reply.author.toBase58()).to.deep.equal(creatorWallet.publicKey.toBase58());
+
+    // Replies to comments that have not been published are rejected
     try {
-      await initializeSpecialty(program, wallet1, idExpected, "Especialidad de prueba", -1)
+      await commentProposalByStudent(program, memberWallet, proposalId, subjectId, subjectCode, 2, 7, "", "Respuesta huerfana")
+      assert.fail("Replying to a comment that does not exist was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "A raw constraint was violated");
-      return;
+      assert.include(err.toString(), "CommentNotFound");
     }
 
-    assert.fail("Expected an error to be thrown");
+    await voteProposalByStudent(program, memberWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
 
-  });
+    // The amendment of the member is accepted by the creator and the votes over the previous version are discarded
+    await proposeAmendmentByStudent(program, memberWallet, proposalId, subjectId, subjectCode, 0, "Propuesta enmendada", "Resumen enmendado")
+    await proposeAmendmentByStudent(program, creatorWallet, proposalId, subjectId, subjectCode, 1, "Propuesta reescrita", "Resumen reescrito")
 
-  it("Subject is properly initializated", async () => {
+    try {
+      await acceptAmendment(program, creatorWallet, proposalId, subjectId, subjectCode, 0, false)
+      assert.fail("Accepting an amendment of a voted proposal without resetting the votes was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "AmendmentRequiresVoteReset");
+    }
 
-    await initializeHighRank(program, wallet1)
-    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
+    await acceptAmendment(program, creatorWallet, proposalId, subjectId, subjectCode, 0, true)
 
-    // Fetching the codeIdSubject relation before carrying out the test 
-    const codeIdSubjectRelationBefore = await fetchCodeIdRelationAccount(program);
+    // The second amendment was drafted over the first version of the proposal
+    try {
+      await acceptAmendment(program, creatorWallet, proposalId, subjectId, subjectCode, 1, true)
+      assert.fail("Accepting an amendment drafted over a previous version was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "AmendmentIsOutdated");
+    }
 
-    // creating a new aux professor
-    const auxSignature = await initializeProfessor(program, wallet4, [43111, 44323]);
-    await connection.confirmTransaction(auxSignature.toString())
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    const amendmentAccount = await fetchAmendmentAccount(program, proposalId, subjectCode, 0);
 
-    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
-    var idExpected = 1;
+    expect(proposalAccount.version).eq(2);
+    expect(proposalAccount.title).to.deep.equal("Propuesta enmendada");
+    expect(proposalAccount.numberOfComments).eq(2);
+    expect(proposalAccount.supportingVotes).eq(0);
+    expect(proposalAccount.studentsThatHaveVoted).to.deep.equal([]);
+    expect(amendmentAccount.state).to.deep.equal({ accepted: {} });
 
+  });
 
-    const account = await fetchIdAccount(program, "subject");
-    idExpected = account.smallerIdAvailable
+  it("Proposal follows the rules configured for its category", async () => {
 
-    /* Due to a previous test --> there will always be a professor with ID '0' 
-     * The aux professor's id is passed as part of the array of professors' id via 'auxProfessorAccount.id'
-    */
-    const signature = await initializeSubject(program, wallet1, idExpected, "Especialidad de prueba", 1, 1, { first: {} }, 43111, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
-    await connection.confirmTransaction(signature.toString(), "confirmed")
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed", maxSupportedTransactionVersion: 2 });
-    const [key, data, buffer] = getReturnLog(transaction);
+    // Bibliography updates are approved with a lower threshold and skip the HighRank validation
+    await updateProposalCategoryConfig(program, wallet1, { bibliography: {} }, 60, false)
 
-    const newSubjectAccount = await fetchSubjectAccount(program, idExpected);
-    const subjectIdGeneratorAccount = await fetchIdAccount(program, "subject");
+    try {
+      await updateProposalCategoryConfig(program, wallet1, { schedule: {} }, 40, true)
+      assert.fail("Configuring a threshold that is not a majority was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectApprovalThreshold");
+    }
 
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
+    const subjectCode = 43122;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    expect(new anchor.BN(newSubjectAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
-    expect(new anchor.BN(subjectIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
-    expect(new anchor.BN(newSubjectAccount.degreeId).eq(new anchor.BN(1))).to.be.true;
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con categorias", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    assert.equal("QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs", newSubjectAccount.teachingProjectReference)
+    const creatorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
 
-    // Checking if the course is equal to SubjectCourse::First (enum in Rust)
-    expect(newSubjectAccount.course).to.deep.equal({ first: {} });
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    // The program return data must be the boolean 'true'
-    expect(program_return).to.be.true;
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Nueva bibliografia", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { bibliography: {} }, ["libros", "2024"])
+    await connection.confirmTransaction(signature.toString())
+
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.category).to.deep.equal({ bibliography: {} });
+    expect(proposalAccount.tags).to.deep.equal(["libros", "2024"]);
+    expect(proposalAccount.approvalThreshold).eq(60);
+    expect(proposalAccount.highRankValidationRequired).to.be.false;
 
   });
 
-  it("Subject is initializated with incorrect code", async () => {
+  it("Proposal creation locks a deposit and is capped per user and subject", async () => {
 
-    await initializeHighRank(program, wallet1)
+    const depositLamports = anchor.web3.LAMPORTS_PER_SOL;
+    await updateProposalDepositConfig(program, wallet1, depositLamports, 1)
 
-    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
-    var idExpected = 1;
+    const subjectCode = 43124;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con depositos", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    const account = await fetchIdAccount(program, "subject");
-    idExpected = account.smallerIdAvailable
+    const creatorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
+
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con deposito", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
+    const proposalDeposit = await program.account.proposalDeposit.fetch(await findPDAforProposalDeposit(program.programId, proposalId, subjectCode));
+    expect(Number(proposalDeposit.amount)).eq(depositLamports);
+    expect(proposalDeposit.depositor.toBase58()).to.deep.equal(creatorWallet.publicKey.toBase58());
 
+    // Only one proposal can be open at the same time by the same user in the subject
     try {
-      await initializeSubject(program, wallet1, idExpected, "Especialidad de prueba", 1, 1, { first: {} }, -43112, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+      await initializeProposalByStudent(program, creatorWallet, proposalId + 1, "Segunda propuesta", "Resumen", subjectId, professorProposalId + 1, subjectCode)
+      assert.fail("Creating a second open proposal was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "RangeError [ERR_OUT_OF_RANGE]");
-      return;
+      assert.include(err.toString(), "TooManyOpenProposals");
     }
 
-    assert.fail("Expected an error to be thrown");
+    // The deposit cannot be settled until the votation has finished
+    try {
+      await settleProposalDeposit(program, wallet1, proposalId, subjectId, subjectCode, creatorWallet.publicKey)
+      assert.fail("Settling the deposit of an open proposal was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "ProposalDepositCannotBeSettledYet");
+    }
 
-  });
+    // Withdrawing the proposal returns the deposit and frees a slot for a new proposal
+    const balanceBeforeWithdrawing = await connection.getBalance(creatorWallet.publicKey);
+    await withdrawProposal(program, creatorWallet, proposalId, subjectId, professorProposalId, subjectCode)
+    const balanceAfterWithdrawing = await connection.getBalance(creatorWallet.publicKey);
+    expect(balanceAfterWithdrawing - balanceBeforeWithdrawing).to.be.greaterThan(depositLamports - 10000);
 
-  /*
-  * This two next tests will only run properly once (since it will initializate the subject 43500, which cannot be created again) --> 
-  * For this test to pass again, validator node must be restarted with command 'solana-test-validator -r'
-  */
+    const openProposalsCounter = await program.account.openProposalsCounter.fetch(await findPDAforOpenProposalsCounter(program.programId, creatorWallet.publicKey, subjectCode));
+    expect(openProposalsCounter.openProposals).eq(0);
 
-  it("Proposal is created properly by Student", async () => {
+    // Restoring a configuration that does not interfere with the rest of the tests
+    await updateProposalDepositConfig(program, wallet1, 0, 255)
 
-    await initializeHighRank(program, wallet1);
+  });
 
-    // Initializing new subject and fetching it
+  it("Duplicated proposals are rejected within the cooldown of the subject", async () => {
 
-    var subjectId = 1;
-    const subjectCode = 43600;
+    const subjectCode = 43125;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura sin duplicados", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
+    await updateDuplicateProposalCooldown(program, wallet1, subjectId, subjectCode, 604800)
 
+    const firstWallet = await createWallet(connection, 10);
+    const secondWallet = await createWallet(connection, 10);
+    await initializeStudent(program, firstWallet, [subjectCode]);
+    await initializeStudent(program, secondWallet, [subjectCode]);
 
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
-    await connection.confirmTransaction(subjectSignature.toString())
+    const signature = await initializeProposalByStudent(program, firstWallet, proposalId, "Nuevo temario", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
-    const codeIdSubjectRelationAfter = await fetchCodeIdRelationAccount(program);
+    // Differences in case and whitespace do not prevent the duplicate from being detected
+    try {
+      await initializeProposalByStudent(program, secondWallet, proposalId + 1, "nuevo   TEMARIO", "Resumen", subjectId, professorProposalId + 1, subjectCode)
+      assert.fail("Creating a duplicated proposal was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "DuplicatedProposal");
+    }
 
-    /*
-    * Using logging on console to verify some aspects of the codeIdRelation that are difficult to check via tests
-    */
+    // Editing another proposal into the same text is detected as well
+    await initializeProposalByStudent(program, secondWallet, proposalId + 1, "Otro temario", "Resumen", subjectId, professorProposalId + 1, subjectCode)
 
-    console.log(newSubjectAccount)
-    console.log(codeIdSubjectRelationAfter.keyId)
-    console.log(codeIdSubjectRelationAfter.codeValue)
+    try {
+      await editProposal(program, secondWallet, proposalId + 1, subjectId, subjectCode, "Nuevo temario", "Resumen")
+      assert.fail("Editing a proposal into a duplicated one was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "DuplicatedProposal");
+    }
 
-    // Creating new student
+    // Once withdrawn, the first proposal does not block its text any more
+    await withdrawProposal(program, firstWallet, proposalId, subjectId, professorProposalId, subjectCode)
+    await editProposal(program, secondWallet, proposalId + 1, subjectId, subjectCode, "Nuevo temario", "Resumen")
 
-    await initializeStudent(program, wallet3, [subjectCode]);
-    getExtraFunds(connection, 50, wallet3) // wallet3 is allowed by a Student
+    const proposalIndex = await program.account.proposalIndex.fetch(await findPDAforProposalIndex(program.programId, subjectCode));
+    expect(Number(proposalIndex.cooldown)).eq(604800);
+    expect(proposalIndex.entries.length).eq(1);
+    expect(proposalIndex.entries[0].proposalId).eq(proposalId + 1);
 
-    // Creating new proposal
+  });
 
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+  it("Proposal is co-authored by the users that sign it", async () => {
 
+    const subjectCode = 43126;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con coautores", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
+    const creatorWallet = await createWallet(connection, 10);
+    const coAuthorWallet = await createWallet(connection, 10);
+    const otherCoAuthorWallet = await createWallet(connection, 10);
+    const outsiderWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeStudent(program, coAuthorWallet, [subjectCode]);
+    await initializeProfessor(program, otherCoAuthorWallet, [subjectCode]);
+    await initializeStudent(program, outsiderWallet, [43125]);
+
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
+
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta del grupo de estudio", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    // Getting the professorProposal's id from professorProposalIdGenerator and if it does not exist, the ID expected (and used for the calculation of PDA) must be 0
+    // Co-authors must be users of the subject with the user type they are invited as
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await inviteCoAuthor(program, creatorWallet, proposalId, subjectId, subjectCode, otherCoAuthorWallet.publicKey, { student: {} }, 10)
+      assert.fail("Inviting a professor as a student co-author was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "IncorrectCoAuthor");
     }
 
-    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta de prueba", "Desarollo o contenido de la propuesta de prueba", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
-    await connection.confirmTransaction(signature.toString())
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
-    const [key, data, buffer] = getReturnLog(transaction);
-
-    const newProposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
-    const proposalIdGeneratorAccount = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    const studentAccount = await fetchStudentAccount(program, wallet3.publicKey)
-    const subjectAccount = await fetchSubjectAccount(program, newProposalAccount.subjectId)
-
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
+    try {
+      await inviteCoAuthor(program, creatorWallet, proposalId, subjectId, subjectCode, outsiderWallet.publicKey, { student: {} }, 10)
+      assert.fail("Inviting a student of another subject was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "UserDoesNotBelongToTheSubject");
+    }
 
-    expect(new anchor.BN(newProposalAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
-    expect(new anchor.BN(proposalIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+    await inviteCoAuthor(program, creatorWallet, proposalId, subjectId, subjectCode, coAuthorWallet.publicKey, { student: {} }, 40)
 
-    // Checking that the subjectId of the proposal is correct (the subject that the proposal is derived from)
-    expect(new anchor.BN(newProposalAccount.subjectId).eq(new anchor.BN(subjectAccount.id))).to.be.true;
+    // The creator must keep part of the reward
+    try {
+      await inviteCoAuthor(program, creatorWallet, proposalId, subjectId, subjectCode, otherCoAuthorWallet.publicKey, { professor: {} }, 60)
+      assert.fail("Inviting a co-author with a share that leaves nothing to the creator was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectCoAuthorShare");
+    }
 
-    // The ending_timestamp field must be equal to the publishingTimestamp plus 1 month (duration of the proposal) which is equal to 2592000 seconds in Unix Timestamp format
-    expect(Number(newProposalAccount.publishingTimestamp) + 2592000).eq(Number(newProposalAccount.endingTimestamp));
+    // Only invited users can sign the proposal
+    try {
+      await coSignProposal(program, otherCoAuthorWallet, proposalId, subjectId, subjectCode)
+      assert.fail("Signing a proposal without being invited was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "CoAuthorNotInvited");
+    }
 
-    // The ID of the creator (sender) of the transaction must be properly registered in the field creatorId in the proposal
-    expect(new anchor.BN(studentAccount.id).eq(new anchor.BN(newProposalAccount.creatorId))).to.be.true;
+    await coSignProposal(program, coAuthorWallet, proposalId, subjectId, subjectCode)
 
-    // The expected votes (as established in the SC) must be equal to the amount of professors and students that owe the subject (1 students and 0 teachers + 20 extra votes expected)
-    // 20 votes are added in order to bear in mind the votes of professionals that are not registered in the subjects neither as students nor as professors
-    expect(new anchor.BN(newProposalAccount.expectedVotes).eq(new anchor.BN(21))).to.be.true;
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.coAuthors.length).eq(1);
+    expect(proposalAccount.coAuthors[0].authority.toBase58()).to.deep.equal(coAuthorWallet.publicKey.toBase58());
+    expect(proposalAccount.coAuthors[0].share).eq(40);
+    expect(proposalAccount.coAuthors[0].hasSigned).to.be.true;
 
-    expect(program_return).to.be.true;
   });
 
-  it("Proposal is created properly by Professor", async () => {
-
-    await initializeHighRank(program, wallet1);
-
-    // Initializing new subject and fetching it
-
-    var subjectId = 1;
-    const subjectCode = 43700;
-
+  it("Rejected proposal is appealed by its creator and the votation is reopened by a HighRank", async () => {
 
+    const subjectCode = 43127;
     const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con recursos", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
+    const creatorWallet = await createWallet(connection, 10);
+    const otherWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
-    await connection.confirmTransaction(subjectSignature.toString())
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
-    const codeIdSubjectRelationAfter = await fetchCodeIdRelationAccount(program);
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta recurrida", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    // Creating new professor
+    // Proposals that are still being voted cannot be appealed
+    try {
+      await fileAppeal(program, creatorWallet, proposalId, subjectId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+      assert.fail("Appealing a proposal that has not been rejected was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "VotationIsNotRejected");
+    }
 
-    await initializeProfessor(program, wallet2, [subjectCode]);
-    getExtraFunds(connection, 50, wallet2)
+    // The votation is forced to finalize by voting against the proposal
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, false, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    // Creating new proposal
+    // Only the creator of the proposal can appeal it
+    try {
+      await fileAppeal(program, otherWallet, proposalId, subjectId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+      assert.fail("Appealing a proposal by a user that did not create it was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "UserIsNotTheProposalCreator");
+    }
 
-    var correct = true;
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+    await fileAppeal(program, creatorWallet, proposalId, subjectId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
+    const appealedProposal = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(appealedProposal.state).to.deep.equal({ underAppeal: {} });
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    const newEndingTimestamp = Math.floor(Date.now() / 1000) + 86400
+    await resolveAppeal(program, wallet1, proposalId, subjectId, subjectCode, true, DEFAULT_PROPOSAL_CONTENT_REFERENCE, newEndingTimestamp)
 
+    const reopenedProposal = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(reopenedProposal.state).to.deep.equal({ votationInProgress: {} });
+    expect(Number(reopenedProposal.endingTimestamp)).eq(newEndingTimestamp);
+    expect(reopenedProposal.supportingVotes + reopenedProposal.againstVotes).eq(0);
 
+    const appealAccount = await program.account.appeal.fetch(await findPDAforAppeal(program.programId, proposalId, subjectCode));
+    expect(appealAccount.resolution).to.deep.equal({ votationReopened: {} });
 
-    // Getting the professorProposal's id from professorProposalIdGenerator and if it does not exist, the ID expected (and used for the calculation of PDA) must be 0
+    // An appeal can only be resolved once
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await resolveAppeal(program, wallet1, proposalId, subjectId, subjectCode, false, DEFAULT_PROPOSAL_CONTENT_REFERENCE, 0)
+      assert.fail("Resolving an appeal twice was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "ProposalIsNotUnderAppeal");
     }
 
-    const signature = await initializeProposalByProfessor(program, wallet2, idExpected, "Propuesta de prueba", "Desarollo o contenido de la propuesta de prueba", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
-    await connection.confirmTransaction(signature.toString())
-    const transaction = await connection.getTransaction(signature.toString(), { commitment: "confirmed" });
-    const [key, data, buffer] = getReturnLog(transaction);
-
-    const newProposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
-    const proposalIdGeneratorAccount = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    const professorAccount = await fetchProfessorAccount(program, wallet2.publicKey)
-
-    const reader_U8 = new Borsh.BinaryReader(buffer).readU8;
-    const program_return = Boolean(reader_U8);
-
-    expect(new anchor.BN(newProposalAccount.id).eq(new anchor.BN(idExpected))).to.be.true;
-    expect(new anchor.BN(proposalIdGeneratorAccount.smallerIdAvailable).eq(new anchor.BN(idExpected + 1))).to.be.true;
+  });
 
-    // Checking that the subjectId of the proposal is correct (the subject that the proposal is derived from)
-    expect(new anchor.BN(newProposalAccount.subjectId).eq(new anchor.BN(subjectId))).to.be.true;
+  it("HighRank requests changes on a proposal before rejecting it for good", async () => {
 
-    // The ending_timestamp field must be equal to the publishingTimestamp plus 1 month (duration of the proposal) which is equal to 2592000 seconds in Unix Timestamp format
-    expect(Number(newProposalAccount.publishingTimestamp) + 2592000).eq(Number(newProposalAccount.endingTimestamp));
+    const subjectCode = 43128;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    // The ID of the creator (sender) of the transaction must be properly registered in the field creatorId in the proposal
-    expect(new anchor.BN(professorAccount.id).eq(new anchor.BN(newProposalAccount.creatorId))).to.be.true;
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con revisiones", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    // The expected votes (as established in the SC) must be equal to the amount of professors and students that owe the subject (1 students and 0 teachers + 20 extra votes expected)
-    // 20 votes are added in order to bear in mind the votes of professionals that are not registered in the subjects neither as students nor as professors
-    expect(new anchor.BN(newProposalAccount.expectedVotes).eq(new anchor.BN(21))).to.be.true;
+    const creatorWallet = await createWallet(connection, 10);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
 
-    expect(program_return).to.be.true;
-  });
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-  it("Proposal is properly voted by a student", async () => {
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta revisada", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    // INITIALIZATING HIGH RANK 
-    await initializeHighRank(program, wallet1);
+    // The votation is forced to finalize by voting in favour of the proposal
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    // INITIALIZATING A SUBJECT
+    await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [professorWallet.publicKey])
+    await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
-    var subjectId = 1;
-    const subjectCode = 43123;
+    // The feedback of every decision must be stored on IPFS
+    try {
+      await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, { requestChanges: {} }, "feedback")
+      assert.fail("Reviewing a proposal without a correct feedback reference was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectFeedbackReference");
+    }
 
+    await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, { requestChanges: {} })
 
-    const subjectAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectAccount.smallerIdAvailable
+    expect((await fetchProposalAccount(program, proposalId, subjectCode)).state).to.deep.equal({ waitingForTeacher: {} });
+    expect((await fetchProfessorProposalAccount(program, professorProposalId, subjectCode)).state).to.deep.equal({ pending: {} });
 
+    // The professor submits the requested changes, but the HighRank finally rejects the proposal
+    await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+    await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, { rejectFinal: {} })
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
-    await connection.confirmTransaction(subjectSignature.toString())
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    const professorProposalAccount = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    expect(proposalAccount.state).to.deep.equal({ rejectedByHighRank: {} });
+    expect(professorProposalAccount.state).to.deep.equal({ rejected: {} });
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+    // The final rejection of the HighRank can not be appealed
+    try {
+      await fileAppeal(program, creatorWallet, proposalId, subjectId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+      assert.fail("Appealing a proposal finally rejected by a HighRank was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "VotationIsNotRejected");
+    }
 
+    expect(professorProposalAccount.reviewRounds.length).eq(2);
+    expect(professorProposalAccount.reviewRounds[0].decision).to.deep.equal({ requestChanges: {} });
+    expect(professorProposalAccount.reviewRounds[1].decision).to.deep.equal({ rejectFinal: {} });
+    expect(professorProposalAccount.reviewRounds[1].feedbackReference.identifier).eq(DEFAULT_PROPOSAL_CONTENT_REFERENCE);
 
-    // INITIALIZATING STUDENT
+  });
 
-    await initializeStudent(program, wallet3, [subjectCode]);
-    getExtraFunds(connection, 50, wallet3)
+  it("Proposal is published for a reading period before its votation starts", async () => {
 
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+    const subjectCode = 43129;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con periodo de lectura", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    // CREATING NEW PROPOSAL (just exactly the previous tests)
+    const creatorWallet = await createWallet(connection, 10);
+    const voterWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeStudent(program, voterWallet, [subjectCode]);
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
+    // The votation cannot be scheduled before the publication of the proposal
+    try {
+      await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con lectura previa", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { other: {} }, [], Math.floor(Date.now() / 1000) - 86400)
+      assert.fail("Scheduling the votation in the past was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectVotingStartTime");
+    }
 
+    // Nor can the reading period last longer than a month
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con lectura previa", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { other: {} }, [], Math.floor(Date.now() / 1000) + 2 * 2592000)
+      assert.fail("Scheduling the votation beyond the maximum discussion period was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "IncorrectVotingStartTime");
     }
 
-    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    const votingStartsAt = Math.floor(Date.now() / 1000) + 86400
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con lectura previa", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { other: {} }, [], votingStartsAt)
     await connection.confirmTransaction(signature.toString())
 
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.state).to.deep.equal({ discussion: {} });
+    expect(Number(proposalAccount.votingStartsAt)).eq(votingStartsAt);
+    expect(Number(proposalAccount.endingTimestamp)).eq(votingStartsAt + 2592000);
 
-    // Fetching the proposal account before voting
-    const proposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    // The proposal can be discussed during the reading period, but not voted
+    await commentProposalByStudent(program, voterWallet, proposalId, subjectId, subjectCode, 0, -1, "", "Pregunta sobre la propuesta")
 
+    try {
+      await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      assert.fail("Voting a proposal before its votation starts was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "VotationHasNotStarted");
+    }
 
-    // VOTING THE PROPOSAL (by the creator)
-    const vote_signature = await voteProposalByStudent(program, wallet3, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), true, newSubjectAccount.code)
-    await connection.confirmTransaction(vote_signature.toString())
-    const transaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
+  });
 
+  it("Work of the proposal is assigned to a professor of the subject who can add collaborators", async () => {
 
-    // Getting the Program Return
-    const [key, data, buffer] = getReturnLog(transaction);
-    const program_return = new Borsh.BinaryReader(buffer).readString();
+    const subjectCode = 43130;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    // Fetching the proposal account after voting
-    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con profesores asignados", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    // The creator is voting in favor of the proposal, so the supporting_votes field must be incremented in +1
-    expect(new anchor.BN(proposalAccount.supportingVotes + 1).eq(new anchor.BN(proposalAccountAfterVoting.supportingVotes))).to.be.true;
+    const creatorWallet = await createWallet(connection, 10);
+    const firstProfessorWallet = await createWallet(connection, 10);
+    const secondProfessorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, firstProfessorWallet, [subjectCode]);
+    await initializeProfessor(program, secondProfessorWallet, [subjectCode]);
 
-    // The against_votes field must remain equal (i.e. with value 0)
-    expect(new anchor.BN(proposalAccount.againstVotes).eq(new anchor.BN(proposalAccountAfterVoting.againstVotes))).to.be.true;
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    // After voting, the votation must continue being 'VotationInProgress'
-    expect(program_return).to.deep.equal("VotationInProgress")
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con profesor asignado", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-  });
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-  it("Proposal trying to be voted by the same student (the creator, in this case)", async () => {
+    // While the work is unassigned, only the professors of the subject can submit it
+    const outsiderProfessorWallet = await createWallet(connection, 10);
+    await initializeProfessor(program, outsiderProfessorWallet, [43129]);
 
-    // INITIALIZATING HIGH RANK 
-    await initializeHighRank(program, wallet1);
+    try {
+      await updateProposalByProfessor(program, outsiderProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+      assert.fail("Updating a proposal by a professor of another subject was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "ProfessorIsNotAssigned");
+    }
 
-    // INITIALIZATING A SUBJECT
+    // Every professor of the subject must take part in the round-robin
+    try {
+      await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [secondProfessorWallet.publicKey])
+      assert.fail("Skipping a professor of the subject in the round-robin was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectSubjectProfessorAccounts");
+    }
 
-    var subjectId = 1;
-    const subjectCode = 44230;
+    await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [secondProfessorWallet.publicKey, firstProfessorWallet.publicKey])
 
+    const firstProfessorAccount = await program.account.professor.fetch(await findPDAforProfessor(program.programId, firstProfessorWallet.publicKey));
+    const professorProposalAccount = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    expect(professorProposalAccount.assignedProfessorId).eq(firstProfessorAccount.id);
 
-    const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
+    // The assigned professor shares the work with the other professor of the subject
+    await addProfessorProposalCollaborator(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, secondProfessorWallet.publicKey)
+    await submitDraftRevision(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+    await signOffDraft(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
+    await signOffDraft(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
+    await updateProposalByProfessor(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.state).to.deep.equal({ waitingForHighRank: {} });
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
-    await connection.confirmTransaction(subjectSignature.toString())
+  });
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+  it("Professor requests an extension of the deadline that is approved by a HighRank", async () => {
+
+    const subjectCode = 43131;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con prorrogas", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    // INITIALIZATING STUDENT
+    const creatorWallet = await createWallet(connection, 10);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
 
-    await initializeStudent(program, wallet3, [subjectCode]);
-    getExtraFunds(connection, 50, wallet3)
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con prorroga", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    // CREATING NEW PROPOSAL (just exactly the previous tests)
+    await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [professorWallet.publicKey])
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    const professorProposalBefore = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    const requestedEndingTimestamp = Number(professorProposalBefore.endingTimestamp) + 604800
+
+    await requestExtension(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE, requestedEndingTimestamp)
 
+    // Only one extension request can be pending at a time
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await requestExtension(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE, requestedEndingTimestamp + 604800)
+      assert.fail("Requesting an extension while another one is pending was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "ExtensionRequestIsPending");
     }
 
-    // CREATING PROPOSAL 
-
-    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
-    await connection.confirmTransaction(signature.toString())
+    await resolveExtension(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, true)
 
+    const professorProposalAfter = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    expect(Number(professorProposalAfter.endingTimestamp)).eq(requestedEndingTimestamp);
+    expect(professorProposalAfter.extensions.length).eq(1);
+    expect(professorProposalAfter.extensions[0].state).to.deep.equal({ approved: {} });
+    expect(Number(professorProposalAfter.extensions[0].previousEndingTimestamp)).eq(Number(professorProposalBefore.endingTimestamp));
 
-    // Fetching the proposal account before voting
-    const proposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+  });
 
+  it("Professor penalty policy is configured by a HighRank", async () => {
 
-    // VOTING THE PROPOSAL (by the creator)
-    const vote_signature = await voteProposalByStudent(program, wallet3, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), true, newSubjectAccount.code)
-    await connection.confirmTransaction(vote_signature.toString())
+    // The penalty of a single delay can not be lower than the penalty of one interval
+    try {
+      await updatePenaltyPolicy(program, wallet1, 0, 1296000, 5, 2, 1, 15552000)
+      assert.fail("Configuring a cap lower than the penalty per interval was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectPenaltyPolicy");
+    }
 
     try {
-      await voteProposalByStudent(program, wallet3, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), false, newSubjectAccount.code)
+      await updatePenaltyPolicy(program, wallet1, 0, 0, 1, 10, 1, 15552000)
+      assert.fail("Configuring an empty penalty interval was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectPenaltyPolicy");
+    }
 
+    // Periods are bounded (≈ 10 years) so that the penalties can always be computed
+    try {
+      await updatePenaltyPolicy(program, wallet1, 0, 315360001, 1, 10, 1, 15552000)
+      assert.fail("Configuring a penalty interval longer than the bound was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "UserHasAlreadyVoted");
-      return
+      assert.include(err.toString(), "IncorrectPenaltyPolicy");
     }
 
-    assert.fail("Expected an error to be thrown");
+    // One week of grace, one punishment per started week of delay (up to 4) and one punishment forgiven per term
+    await updatePenaltyPolicy(program, wallet1, 604800, 604800, 1, 4, 1, 15552000)
+
+    const penaltyPolicy = await program.account.penaltyPolicy.fetch(await findPDAforPenaltyPolicy(program.programId));
+    expect(Number(penaltyPolicy.gracePeriod)).eq(604800);
+    expect(Number(penaltyPolicy.penaltyInterval)).eq(604800);
+    expect(penaltyPolicy.penaltyPerInterval).eq(1);
+    expect(penaltyPolicy.maximumPenalty).eq(4);
+    expect(penaltyPolicy.decayPerTerm).eq(1);
+    expect(Number(penaltyPolicy.termDuration)).eq(15552000);
 
   });
 
-  it("Proposal is trying to be created by an student that does not belong to it", async () => {
+  it("Reward of the professors that deliver accepted teaching projects is configured by a HighRank", async () => {
 
-    // INITIALIZATING HIGH RANK 
-    await initializeHighRank(program, wallet1);
+    try {
+      await updateProfessorRewardConfig(program, wallet1, 2, 1, -1)
+      assert.fail("Configuring a negative early delivery period was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectProfessorRewardConfig");
+    }
 
-    // INITIALIZATING A SUBJECT
+    await updateProfessorRewardConfig(program, wallet1, 2, 1, 1209600)
 
-    var subjectId = 1;
-    const subjectCode = 18000;
+    const professorRewardConfig = await program.account.professorRewardConfig.fetch(await findPDAforProfessorRewardConfig(program.programId));
+    expect(professorRewardConfig.rewardTokens).eq(2);
+    expect(professorRewardConfig.earlyDeliveryBonusTokens).eq(1);
+    expect(Number(professorRewardConfig.earlyDeliveryPeriod)).eq(1209600);
 
+    // Restoring the default reward so that the rest of the tests are not affected
+    await updateProfessorRewardConfig(program, wallet1, 1, 1, 604800)
 
-    const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
+  });
 
+  it("Professors of a shared work submit drafts that must be signed off by all of them", async () => {
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    const subjectCode = 43132;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
+
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con borradores", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
     await connection.confirmTransaction(subjectSignature.toString())
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+    const creatorWallet = await createWallet(connection, 10);
+    const firstProfessorWallet = await createWallet(connection, 10);
+    const secondProfessorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, firstProfessorWallet, [subjectCode]);
+    await initializeProfessor(program, secondProfessorWallet, [subjectCode]);
 
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    // INITIALIZATING STUDENT
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta con borradores", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    await initializeStudent(program, wallet3, [subjectCode + 3]);
-    getExtraFunds(connection, 50, wallet3)
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
+    await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [firstProfessorWallet.publicKey, secondProfessorWallet.publicKey])
+    await addProfessorProposalCollaborator(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, secondProfessorWallet.publicKey)
 
-    // CREATING NEW PROPOSAL (just exactly the previous tests)
+    // A shared work can not be submitted without a signed off draft
+    try {
+      await updateProposalByProfessor(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+      assert.fail("Submitting a shared work without drafts was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "DraftRevisionNotFound");
+    }
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    const firstDraft = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+    await submitDraftRevision(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, firstDraft)
+    await signOffDraft(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
 
+    try {
+      await signOffDraft(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
+      assert.fail("Signing off the same draft twice was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "ProfessorHasAlreadySignedOff");
+    }
 
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await updateProposalByProfessor(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, firstDraft)
+      assert.fail("Submitting a draft that has not been signed off by every professor was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "MissingDraftSignOffs");
     }
 
-    // CREATING PROPOSAL 
+    // A new revision invalidates the previous sign-offs
+    await submitDraftRevision(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+    let professorProposalAccount = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    expect(professorProposalAccount.draftRevisions.length).eq(2);
+    expect(professorProposalAccount.signOffs.length).eq(0);
 
+    await signOffDraft(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
+    await signOffDraft(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
 
     try {
-      const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
-      await connection.confirmTransaction(signature.toString())
+      await updateProposalByProfessor(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, firstDraft)
+      assert.fail("Submitting a reference other than the last draft was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "UserDoesNotBelongToTheSubject");
+      assert.include(err.toString(), "IncorrectTeachingProjectReference");
     }
-  });
 
-  it("Proposal is trying to be voted by an student that does not allow to it", async () => {
+    await updateProposalByProfessor(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
-    // INITIALIZATING HIGH RANK 
-    await initializeHighRank(program, wallet1);
+    professorProposalAccount = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    expect(professorProposalAccount.teachingProjectReference.identifier).eq(DEFAULT_PROPOSAL_CONTENT_REFERENCE);
+    let proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.state).to.deep.equal({ waitingForHighRank: {} });
 
-    // INITIALIZATING A SUBJECT
+    // Drafts start over in every review round, so the limit of revisions does not block the requested changes
+    await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, { requestChanges: {} })
+    professorProposalAccount = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    expect(professorProposalAccount.draftRevisions.length).eq(0);
+    expect(professorProposalAccount.signOffs.length).eq(0);
 
-    var subjectId = 1;
+    await submitDraftRevision(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, firstDraft)
+    await signOffDraft(program, firstProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
+    await signOffDraft(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId)
+    await updateProposalByProfessor(program, secondProfessorWallet, proposalId, professorProposalId, subjectCode, subjectId, firstDraft)
 
-    const subjectCode = 43193;
+    proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.state).to.deep.equal({ waitingForHighRank: {} });
 
+  });
 
-    const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
+  it("Every accepted teaching project is recorded as a new version linked to the previous one", async () => {
 
+    const subjectCode = 43133;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    const originalReference = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con historial", 1, 1, { second: {} }, subjectCode, originalReference)
     await connection.confirmTransaction(subjectSignature.toString())
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
+    const highRankAccount = await fetchHighRankAccount(program, wallet1.publicKey);
 
+    const proposalIds = []
+    for (var round = 0; round < 2; round++) {
 
-    // INITIALIZATING STUDENT
+      const creatorWallet = await createWallet(connection, 10);
+      await initializeStudent(program, creatorWallet, [subjectCode]);
 
-    await initializeStudent(program, wallet3, [subjectCode]);
-    getExtraFunds(connection, 50, wallet3)
+      const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+      const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+      const proposalId = proposalIdAccount.smallerIdAvailable
+      const professorProposalId = professorProposalIdAccount.smallerIdAvailable
+      proposalIds.push(proposalId)
 
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+      const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta versionada", "Resumen", subjectId, professorProposalId, subjectCode)
+      await connection.confirmTransaction(signature.toString())
 
+      for (var i = 0; i < 20; i++) {
+        const voterWallet = await createWallet(connection, 10);
+        await initializeStudent(program, voterWallet, [subjectCode])
+        const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+        await connection.confirmTransaction(vote_signature.toString())
+      }
 
-    // CREATING NEW PROPOSAL (just exactly the previous tests)
+      await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [professorWallet.publicKey])
+      await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+      await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId)
+    }
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    const subjectAccount = await program.account.subject.fetch(await findPDAforSubject(program.programId, subjectId));
+    expect(subjectAccount.teachingProjectVersion).eq(2);
+    expect(subjectAccount.teachingProjectReference.identifier).eq(DEFAULT_PROPOSAL_CONTENT_REFERENCE);
+
+    // The history is walked backwards from the last version down to the one given when the subject was created
+    const lastVersion = await program.account.teachingProjectVersion.fetch(await findPDAforTeachingProjectVersion(program.programId, subjectCode, 2));
+    expect(lastVersion.previousVersion).eq(1);
+    expect(lastVersion.proposalId).eq(proposalIds[1]);
+    expect(lastVersion.approvedByHighRankId).eq(highRankAccount.id);
+
+    const firstVersion = await program.account.teachingProjectVersion.fetch(await findPDAforTeachingProjectVersion(program.programId, subjectCode, lastVersion.previousVersion));
+    expect(firstVersion.version).eq(1);
+    expect(firstVersion.previousVersion).eq(0);
+    expect(firstVersion.previousReference.identifier).eq(originalReference);
+    expect(firstVersion.teachingProjectReference.identifier).eq(DEFAULT_PROPOSAL_CONTENT_REFERENCE);
+    expect(firstVersion.proposalId).eq(proposalIds[0]);
+
+  });
+
+  it("Teaching projects and proposals can reference CIDv1 and Arweave documents", async () => {
+
+    const subjectCode = 43134;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
+    // A CIDv0 is not a valid Arweave transaction id
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await initializeSubject(program, wallet1, subjectId, "Asignatura con referencias tipadas", 1, 1, { second: {} }, subjectCode, { backend: { arweave: {} }, identifier: DEFAULT_PROPOSAL_CONTENT_REFERENCE, sha256: null })
+      assert.fail("Creating a subject with an incorrect Arweave reference was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "IncorrectTeachingProjectReference");
     }
 
-    // CREATING PROPOSAL 
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con referencias tipadas", 1, 1, { second: {} }, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    const creatorWallet = await createWallet(connection, 10);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
+
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
+
+    // The sha256 of the body (if given) must match the one used for the content hash chain
+    const arweaveBody = { backend: { arweave: {} }, identifier: "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U", sha256: CryptoJS.SHA256("Otro resumen").toString() }
+    try {
+      await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta en Arweave", "Resumen", subjectId, professorProposalId, subjectCode, arweaveBody)
+      assert.fail("Creating a proposal whose body hash does not match was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectProposalContentHash");
+    }
+
+    arweaveBody.sha256 = CryptoJS.SHA256("Resumen").toString()
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta en Arweave", "Resumen", subjectId, professorProposalId, subjectCode, arweaveBody)
     await connection.confirmTransaction(signature.toString())
 
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.contentReference).to.deep.equal(arweaveBody);
 
-    // Fetching the proposal account before voting
-    const proposalAccount = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    // INITIALIZING VOTING STUDENT
-    await initializeStudent(program, alternativeWallet, [])
+    await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [professorWallet.publicKey])
 
-    // VOTING THE PROPOSAL (by the creator)
+    // Base32 CIDv1 are lowercase
+    const cidV1 = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
     try {
-      await voteProposalByStudent(program, alternativeWallet, Number(proposalAccount.id), Number(proposalAccount.subjectId), Number(proposalAccount.associatedProfessorProposalId), true, newSubjectAccount.code)
-      await connection.confirmTransaction(signature.toString())
+      await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, cidV1.toUpperCase())
+      assert.fail("Submitting an incorrect CIDv1 was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "UserDoesNotBelongToTheSubject");
+      assert.include(err.toString(), "IncorrectTeachingProjectReference");
     }
+
+    const teachingProject = { backend: { ipfs: {} }, identifier: cidV1, sha256: CryptoJS.SHA256("Proyecto docente").toString() }
+    await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, teachingProject)
+    await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId)
+
+    // The subject grows to fit the longer reference
+    const subjectAccount = await program.account.subject.fetch(await findPDAforSubject(program.programId, subjectId));
+    expect(subjectAccount.teachingProjectReference).to.deep.equal(teachingProject);
+
+    const version = await program.account.teachingProjectVersion.fetch(await findPDAforTeachingProjectVersion(program.programId, subjectCode, 1));
+    expect(version.teachingProjectReference).to.deep.equal(teachingProject);
+    expect(version.previousReference.identifier).eq(DEFAULT_PROPOSAL_CONTENT_REFERENCE);
+
   });
 
+  it("Proposals declare the sections of the teaching project they change and only those are updated", async () => {
 
-  //--------------------------TEST THAT ONLY WORK BY MODIFYING CERTAIN CONDITIONS OF THE SMART CONTRACT-----------------------//
+    const subjectCode = 43135;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
+    const sectionReference = (section: any, text: string) => ({ section, reference: { backend: { ipfs: {} }, identifier: DEFAULT_PROPOSAL_CONTENT_REFERENCE, sha256: CryptoJS.SHA256(text).toString() } })
 
-  /*
-    Condition modified: set maximum of votes as the minimum (expected_votes) instead of 2500
-  */
+    // The index of sections can be seeded when the subject is created
+    try {
+      await initializeSubject(program, wallet1, subjectId, "Asignatura con secciones", 1, 1, { second: {} }, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], [], [sectionReference({ methodology: {} }, "Metodologia v1"), sectionReference({ methodology: {} }, "Metodologia v2")])
+      assert.fail("Seeding a section twice was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectSectionReferences");
+    }
 
-  it("Reaching the number of expecting votes while voting true ", async () => {
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con secciones", 1, 1, { second: {} }, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], [], [sectionReference({ methodology: {} }, "Metodologia v1")])
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    // INITIALIZATING HIGH RANK 
-    await initializeHighRank(program, wallet1);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
 
-    // INITIALIZATING A SUBJECT
+    const rounds = [
+      { sections: [{ bibliography: {} }, { evaluation: {} }], references: [sectionReference({ bibliography: {} }, "Bibliografia v1"), sectionReference({ evaluation: {} }, "Evaluacion v1")] },
+      { sections: [{ bibliography: {} }], references: [sectionReference({ bibliography: {} }, "Bibliografia v2")] }
+    ]
 
-    var subjectId = 1;
-    const subjectCode = 50003;
+    const acceptRound = async (round: { sections: Array<any>, references: Array<any> }) => {
+
+      const creatorWallet = await createWallet(connection, 10);
+      await initializeStudent(program, creatorWallet, [subjectCode]);
+
+      const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+      const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+      const proposalId = proposalIdAccount.smallerIdAvailable
+      const professorProposalId = professorProposalIdAccount.smallerIdAvailable
+
+      try {
+        await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta por secciones", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { other: {} }, [], null, [{ bibliography: {} }, { bibliography: {} }])
+        assert.fail("Declaring a section twice was expected to fail")
+      } catch (err) {
+        assert.instanceOf(err, Error);
+        assert.include(err.toString(), "IncorrectTeachingProjectSections");
+      }
+
+      const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta por secciones", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { other: {} }, [], null, round.sections)
+      await connection.confirmTransaction(signature.toString())
 
+      for (var i = 0; i < 20; i++) {
+        const voterWallet = await createWallet(connection, 10);
+        await initializeStudent(program, voterWallet, [subjectCode])
+        const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+        await connection.confirmTransaction(vote_signature.toString())
+      }
+
+      await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [professorWallet.publicKey])
+
+      // Every declared section needs its own reference (and nothing else)
+      try {
+        await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [...round.references, sectionReference({ schedule: {} }, "Horario")])
+        assert.fail("Submitting a section that was not declared by the proposal was expected to fail")
+      } catch (err) {
+        assert.instanceOf(err, Error);
+        assert.include(err.toString(), "IncorrectSectionReferences");
+      }
+
+      await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE, round.references)
+      await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId)
+    }
 
-    const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
+    for (const round of rounds) {
+      await acceptRound(round)
+    }
 
+    // The evaluation keeps the reference of the first proposal, while the bibliography has been replaced by the second one
+    let subjectAccount = await program.account.subject.fetch(await findPDAforSubject(program.programId, subjectId));
+    expect(subjectAccount.sections.length).eq(3);
+    expect(subjectAccount.sections.find((x) => x.section.methodology !== undefined).reference.sha256).eq(CryptoJS.SHA256("Metodologia v1").toString());
+    expect(subjectAccount.sections.find((x) => x.section.evaluation !== undefined).reference.sha256).eq(CryptoJS.SHA256("Evaluacion v1").toString());
+    expect(subjectAccount.sections.find((x) => x.section.bibliography !== undefined).reference.sha256).eq(CryptoJS.SHA256("Bibliografia v2").toString());
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
-    await connection.confirmTransaction(subjectSignature.toString())
+    // A proposal that replaces the whole teaching project leaves no section of the previous index
+    await acceptRound({ sections: [], references: [] })
+    subjectAccount = await program.account.subject.fetch(await findPDAforSubject(program.programId, subjectId));
+    expect(subjectAccount.sections.length).eq(0);
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId)
+  });
 
-    // INITIALIZATING STUDENT
+  it("Proposals carry a new evaluation scheme that is validated and applied when accepted", async () => {
 
-    await initializeStudent(program, wallet3, [subjectCode]);
-    getExtraFunds(connection, 50, wallet3)
+    const subjectCode = 43136;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con esquema de evaluacion", 1, 1, { second: {} }, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+    await connection.confirmTransaction(subjectSignature.toString())
 
+    const evaluationScheme = (examWeight: number, practiceWeight: number) => ({
+      components: [
+        { name: "Examen final", weight: examWeight, minimumGrade: 40, resitAllowed: true },
+        { name: "Practicas", weight: practiceWeight, minimumGrade: 0, resitAllowed: false }
+      ],
+      minimumPassingGrade: 50,
+      resitMaximumGrade: 100
+    })
 
-    // CREATING NEW PROPOSAL (just exactly the previous tests)
+    // The resit can not be capped below the passing grade
+    try {
+      await createEvaluationScheme(program, wallet1, subjectId, subjectCode, { ...evaluationScheme(60, 40), resitMaximumGrade: 40 })
+      assert.fail("Creating an evaluation scheme whose resit can not be passed was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "IncorrectEvaluationGrades");
+    }
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    await createEvaluationScheme(program, wallet1, subjectId, subjectCode, evaluationScheme(60, 40))
 
+    const creatorWallet = await createWallet(connection, 10);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
 
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await initializeProposalByStudent(program, creatorWallet, proposalId, "Cambio de ponderaciones", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { other: {} }, [], null, [{ evaluation: {} }], evaluationScheme(50, 40))
+      assert.fail("Proposing weights that do not sum to 100 was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "EvaluationWeightsDoNotSumTo100");
     }
 
-    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Cambio de ponderaciones", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { other: {} }, [], null, [], evaluationScheme(40, 60))
     await connection.confirmTransaction(signature.toString())
 
-    // VOTING TRUE BY DIFFERENT STUDENTS 
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    // Fetching the proposal account before voting
-    const proposalAccountBeforeVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    await assignProfessorProposalByRoundRobin(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId, [professorWallet.publicKey])
+    await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
-    const number_of_votes_expected = 20 // Must be set to 20 on SC (MAXIMUM_PARTICIPATION const)
+    // The scheme of the subject is not modified until the proposal is accepted
+    let evaluationSchemeAccount = await program.account.evaluationScheme.fetch(await findPDAforEvaluationScheme(program.programId, subjectCode));
+    expect(evaluationSchemeAccount.scheme.components[0].weight).eq(60);
+    expect(evaluationSchemeAccount.proposalId).eq(-1);
 
-    // Subscribing to the event emitted when the votation is finished and accepted 
-    let event_emitted: any;
-    program.addEventListener("NewProfessorProposalCreated", (event, _slot, _signature) => { event_emitted = event })
+    await updateProposalByHighRank(program, wallet1, proposalId, professorProposalId, subjectCode, subjectId)
 
-    // We create students to vote in favor until the number of votes expected is reached and the votation is forced to finalize
-    var vote_signature: String;
+    evaluationSchemeAccount = await program.account.evaluationScheme.fetch(await findPDAforEvaluationScheme(program.programId, subjectCode));
+    expect(evaluationSchemeAccount.scheme).to.deep.equal(evaluationScheme(40, 60));
+    expect(evaluationSchemeAccount.proposalId).eq(proposalId);
 
-    for (var i: number = 0; i < number_of_votes_expected; i++) {
-      alternativeWallet = await createWallet(connection, 10);
-      await initializeStudent(program, alternativeWallet, [subjectCode])
-      vote_signature = await voteProposalByStudent(program, alternativeWallet, proposalAccountBeforeVoting.id, proposalAccountBeforeVoting.subjectId, proposalAccountBeforeVoting.associatedProfessorProposalId, true, newSubjectAccount.code)
-      await connection.confirmTransaction(vote_signature.toString())
+  });
 
-    }
+  it("Withdrawn proposal with comments does not hand out its id again", async () => {
 
-    // Fetching the proposal account after voting
-    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    const subjectCode = 43137;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    // The Creator's Public Key must be equal to the Wallet3's (Student) PublicKey
-    expect(proposalAccountAfterVoting.creatorPublicKey.toString()).to.deep.equal(wallet3.publicKey.toString())
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de ids reservados", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    expect(new anchor.BN(proposalAccountAfterVoting.againstVotes + proposalAccountAfterVoting.supportingVotes).eq(new anchor.BN(number_of_votes_expected))).to.be.true;
+    const creatorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
 
-    // Getting the Program Return
-    const lastVotingTransaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
-    const [key, data, buffer] = getReturnLog(lastVotingTransaction);
-    const program_return = new Borsh.BinaryReader(buffer).readString();
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    // After completing the voting process, the votation must continue being 'WaitingForTeacher' since all students voted 'true' (supporting the proposal)
-    expect(program_return).to.deep.equal("WaitingForTeacher")
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta comentada", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    // fetching the professorProposalIdHandler and the professorProposal
-    const professorProposalIdHandler = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-    const idExpectedFromProfessorProposal = Number(professorProposalIdHandler.smallerIdAvailable - 1)
+    await commentProposalByStudent(program, creatorWallet, proposalId, subjectId, subjectCode, 0, -1, null, "Comentario que seguira existiendo")
+    await withdrawProposal(program, creatorWallet, proposalId, subjectId, professorProposalId, subjectCode)
 
-    const professorProposal = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
+    // The comment is seeded by the proposal id, so only the id of the professor proposal can be recycled
+    const proposalIdAccountAfterWithdrawing = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccountAfterWithdrawing = await fetchProposalIdAccount(program, true, subjectCode)
+    expect(proposalIdAccountAfterWithdrawing.reusedId.map((x) => Number(x))).to.not.include(proposalId);
+    expect(professorProposalIdAccountAfterWithdrawing.reusedId.map((x) => Number(x))).to.include(professorProposalId);
 
-    // Checking the result emitted is correct
-    expect(event_emitted).to.deep.equal({ proposalId: proposalAccountAfterVoting.id, professorProposalId: idExpectedFromProfessorProposal })
+    const nextProposalId = proposalIdAccountAfterWithdrawing.smallerIdAvailable
+    expect(nextProposalId).to.not.equal(proposalId);
+    await initializeProposalByStudent(program, creatorWallet, nextProposalId, "Nueva propuesta", "Otro resumen", subjectId, professorProposalId, subjectCode)
+    await commentProposalByStudent(program, creatorWallet, nextProposalId, subjectId, subjectCode, 0, -1, null, "Primer comentario de la nueva propuesta")
 
-    // Evaluating the professorProposal is properly initializated 
-    expect(new anchor.BN(proposalAccountAfterVoting.associatedProfessorProposalId).eq(new anchor.BN(professorProposal.id))).to.be.true;
-    expect(professorProposal.state).to.deep.equal({ pending: {} });
+    const proposalAccount = await fetchProposalAccount(program, nextProposalId, subjectCode);
+    expect(proposalAccount.numberOfComments).eq(1);
 
   });
 
 
-  it("Forcing the test to finalize (reaching the number of expecting votes) and tokens -credits- are properly delivered ", async () => {
+  it("Batch of subjects with a repeated code is rejected", async () => {
 
-    // INITIALIZATING HIGH RANK 
-    await initializeHighRank(program, wallet1);
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const firstId = subjectIdAccount.smallerIdAvailable
+    const subjectData = (code: number) => ({ name: "Asignatura en lote", degreeId: 1, specialtyId: 1, course: { second: {} }, code, teachingProjectReference: "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs", prerequisites: [], corequisites: [] })
 
-    // INITIALIZATING A SUBJECT
+    try {
+      await createSubjectsBatch(program, wallet1, firstId, [subjectData(43138), subjectData(43138)])
+      assert.fail("Creating a batch with a repeated subject code was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "DuplicatedSubjectCodeInBatch");
+    }
 
-    var subjectId = 1;
-    const subjectCode = 80003;
+    // Nothing of the batch is created
+    const subjectIdAccountAfter = await fetchIdAccount(program, "subject");
+    expect(subjectIdAccountAfter.smallerIdAvailable).eq(firstId);
 
+  });
 
-    const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
 
+  it("Unassigned work of the proposal is submitted by any professor of the subject", async () => {
 
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
-    await connection.confirmTransaction(subjectSignature.toString())
+    const subjectCode = 43139;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId)
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura sin asignaciones", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    // INITIALIZATING STUDENT
+    const creatorWallet = await createWallet(connection, 10);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
 
-    await initializeStudent(program, wallet3, [subjectCode]);
-    getExtraFunds(connection, 50, wallet3)
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta sin profesor asignado", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    // CREATING NEW PROPOSAL (just exactly the previous tests)
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
+    const professorProposalAccount = await fetchProfessorProposalAccount(program, professorProposalId, subjectCode);
+    expect(professorProposalAccount.assignedProfessorId).eq(-1);
+    expect(professorProposalAccount.state).to.deep.equal({ complete: {} });
+    expect(professorProposalAccount.deliveredBy.toBase58()).to.deep.equal(professorWallet.publicKey.toBase58());
 
+  });
 
-    try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
-    } catch (err) {
-      assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
-    }
 
-    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
-    await connection.confirmTransaction(signature.toString())
+  it("Professor delivering on time is not penalized and only existing penalties can be voided", async () => {
 
-    // VOTING TRUE BY DIFFERENT STUDENTS 
+    const subjectCode = 43140;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    // Fetching the proposal account before voting
-    const proposalAccountBeforeVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura entregada a tiempo", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    const number_of_votes_expected = 20 // Must be set to 20 on SC (MAXIMUM_PARTICIPATION const)
+    const creatorWallet = await createWallet(connection, 10);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
 
-    // Subscribing to the event emitted when the votation is finished and accepted 
-    let event_emitted: any;
-    program.addEventListener("NewProfessorProposalCreated", (event, _slot, _signature) => { event_emitted = event })
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    // We create students to vote in favor until the number of votes expected is reached and the votation is forced to finalize
-    var vote_signature: String;
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta entregada a tiempo", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    for (var i: number = 0; i < number_of_votes_expected; i++) {
-      alternativeWallet = await createWallet(connection, 10);
-      await initializeStudent(program, alternativeWallet, [subjectCode])
-      vote_signature = await voteProposalByStudent(program, alternativeWallet, proposalAccountBeforeVoting.id, proposalAccountBeforeVoting.subjectId, proposalAccountBeforeVoting.associatedProfessorProposalId, true, newSubjectAccount.code)
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
       await connection.confirmTransaction(vote_signature.toString())
-
     }
 
-    // Fetching the proposal account after voting
-    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
+    // The policy of the HighRank is always applied, even without grace period
+    await updatePenaltyPolicy(program, wallet1, 0, 604800, 1, 4, 1, 15552000)
+    await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
-    expect(new anchor.BN(proposalAccountAfterVoting.againstVotes + proposalAccountAfterVoting.supportingVotes).eq(new anchor.BN(number_of_votes_expected))).to.be.true;
+    const professorAccount = await program.account.professor.fetch(await findPDAforProfessor(program.programId, professorWallet.publicKey));
+    expect(professorAccount.punishments).eq(0);
+    expect(professorAccount.numberOfPenalties).eq(0);
+    expect(Number(professorAccount.lastPenaltyDecayTimestamp)).to.be.greaterThan(0);
 
-    // Getting the Program Return
-    const lastVotingTransaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
-    const [key, data, buffer] = getReturnLog(lastVotingTransaction);
-    const program_return = new Borsh.BinaryReader(buffer).readString();
+    const penaltyRecordPda = await findPDAforPenaltyRecord(program.programId, professorAccount.id, 0)
+    expect(await connection.getAccountInfo(penaltyRecordPda)).to.be.null;
 
-    // After completing the voting process, the votation must continue being 'WaitingForTeacher' since all students voted 'true' (supporting the proposal)
-    expect(program_return).to.deep.equal("WaitingForTeacher")
+    // A penalty that was never imposed can not be voided
+    try {
+      await voidPenalty(program, wallet1, professorWallet.publicKey, 0, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
+      assert.fail("Voiding a penalty that does not exist was expected to fail")
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.include(err.toString(), "AccountNotInitialized");
+    }
 
-    // fetching the professorProposalIdHandler and the professorProposal
-    const professorProposalIdHandler = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-    const idExpectedFromProfessorProposal = Number(professorProposalIdHandler.smallerIdAvailable - 1)
+    await updatePenaltyPolicy(program, wallet1, 604800, 604800, 1, 4, 1, 15552000)
 
-    const professorProposal = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
+  });
 
-    // Checking the result emitted is correct
-    expect(event_emitted).to.deep.equal({ proposalId: proposalAccountAfterVoting.id, professorProposalId: idExpectedFromProfessorProposal })
 
-    // Evaluating the professorProposal is properly initializated 
-    expect(new anchor.BN(proposalAccountAfterVoting.associatedProfessorProposalId).eq(new anchor.BN(professorProposal.id))).to.be.true;
-    expect(professorProposal.state).to.deep.equal({ pending: {} });
+  it("Teaching project accepted without HighRank validation rewards the professor and the co-authors", async () => {
 
-    // Updating Proposal by Professor
-    await initializeProfessor(program, wallet2, [subjectCode])
-    await updateProposalByProfessor(program, wallet2, proposalAccountAfterVoting.id, professorProposal.id, newSubjectAccount.code, newSubjectAccount.id, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    const subjectCode = 43141;
+    const subjectIdAccount = await fetchIdAccount(program, "subject");
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-    const proposalAccountAfterProfessorUpdating = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
-    const professorProposalAfterProfessorUpdating = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con bibliografia", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    await connection.confirmTransaction(subjectSignature.toString())
 
-    expect(professorProposalAfterProfessorUpdating.state).to.deep.equal({ complete: {} });
-    expect(proposalAccountAfterProfessorUpdating.state).to.deep.equal({ waitingForHighRank: {} });
+    const creatorWallet = await createWallet(connection, 10);
+    const coAuthorWallet = await createWallet(connection, 10);
+    const professorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
+    await initializeProfessor(program, coAuthorWallet, [subjectCode]);
+    await initializeProfessor(program, professorWallet, [subjectCode]);
 
-    // Updating Proposal by HighRank
-    await updateProposalByHighRank(program, wallet1, proposalAccountAfterVoting.id, professorProposal.id, newSubjectAccount.code, newSubjectAccount.id)
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    const proposalAccountAfterHighRankUpdating = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
-    const professorProposalAfterHighRankUpdating = await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code)
+    // Bibliography updates skip the HighRank validation (as configured in a previous test)
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Bibliografia compartida", "Resumen", subjectId, professorProposalId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE, [], { bibliography: {} }, ["libros"])
+    await connection.confirmTransaction(signature.toString())
 
-    expect(professorProposalAfterHighRankUpdating.state).to.deep.equal({ complete: {} });
-    expect(proposalAccountAfterHighRankUpdating.state).to.deep.equal({ accepted: {} });
+    await inviteCoAuthor(program, creatorWallet, proposalId, subjectId, subjectCode, coAuthorWallet.publicKey, { professor: {} }, 40)
+    await coSignProposal(program, coAuthorWallet, proposalId, subjectId, subjectCode)
 
-    //Checking the giving of tokens
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, true, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
-    const mint = await findPDAforMint(program.programId)
-    const studentAssociatedTokenAccount = await getAssociatedTokenAddress(mint, proposalAccountAfterHighRankUpdating.creatorPublicKey, false);
+    await updateProposalByProfessor(program, professorWallet, proposalId, professorProposalId, subjectCode, subjectId, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
-    var balanceBeforeGiving: number = 0
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.state).to.deep.equal({ accepted: {} });
 
-    const tokenAccountBuyerBefore = await getAccount(connection, studentAssociatedTokenAccount);
-    balanceBeforeGiving = Number(tokenAccountBuyerBefore.amount)
+    // The delivery is recorded even though no HighRank reviews the work
+    const professorAccount = await program.account.professor.fetch(await findPDAforProfessor(program.programId, professorWallet.publicKey));
+    expect(professorAccount.rewards).eq(1);
 
-    await giveCreditToStudent(program, wallet1, proposalAccountAfterHighRankUpdating.id, proposalAccountAfterHighRankUpdating.creatorPublicKey, "1111", newSubjectAccount.code)
+    const professorReputation = await program.account.reputation.fetch(await findPDAforReputation(program.programId, professorWallet.publicKey));
+    expect(professorReputation.onTimeDeliveries).eq(1);
+    expect(professorReputation.score).eq(10);
 
-    const tokenAccountBuyerAfter = await getAccount(connection, studentAssociatedTokenAccount);
-    const balanceAfterGiving = Number(tokenAccountBuyerAfter.amount);
+    const mint = await findPDAforMint(program.programId)
+    const creatorTokenAccount = await getAssociatedTokenAddress(mint, creatorWallet.publicKey, false)
+    const coAuthorTokenAccount = await getAssociatedTokenAddress(mint, coAuthorWallet.publicKey, false)
+    const creatorBalanceBeforeGiving = Number((await getAccount(connection, creatorTokenAccount)).amount)
+    const coAuthorBalanceBeforeGiving = Number((await getAccount(connection, coAuthorTokenAccount)).amount)
 
-    expect(new anchor.BN(balanceAfterGiving).eq(new anchor.BN(balanceBeforeGiving + 1))).to.be.true;
+    await giveCreditToStudent(program, wallet1, proposalId, creatorWallet.publicKey, "1111", subjectCode, [coAuthorWallet.publicKey])
 
-  });
+    // The reward is 1 token (10 units) --> 40% for the co-author and the rest for the creator
+    expect(Number((await getAccount(connection, creatorTokenAccount)).amount)).eq(creatorBalanceBeforeGiving + 6);
+    expect(Number((await getAccount(connection, coAuthorTokenAccount)).amount)).eq(coAuthorBalanceBeforeGiving + 4);
 
+    const creatorAccount = await program.account.student.fetch(await findPDAforStudent(program.programId, creatorWallet.publicKey));
+    expect(creatorAccount.rewards).eq(1);
 
-  it("Forcing the test to finalize (reaching the number of expecting votes) and voting false", async () => {
+    const coAuthorAccount = await program.account.professor.fetch(await findPDAforProfessor(program.programId, coAuthorWallet.publicKey));
+    expect(coAuthorAccount.rewards).eq(1);
 
-    // INITIALIZATING HIGH RANK 
-    await initializeHighRank(program, wallet1);
+    const coAuthorReputation = await program.account.reputation.fetch(await findPDAforReputation(program.programId, coAuthorWallet.publicKey));
+    expect(coAuthorReputation.coAuthorships).eq(1);
 
-    // INITIALIZATING A SUBJECT
+  });
 
-    var subjectId = 1;
-    const subjectCode = 78003;
 
+  it("Votation of a proposal whose deposit has been settled can not be reopened by an appeal", async () => {
 
+    const subjectCode = 43142;
     const subjectIdAccount = await fetchIdAccount(program, "subject");
-    subjectId = subjectIdAccount.smallerIdAvailable
+    const subjectId = subjectIdAccount.smallerIdAvailable
 
-
-    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura de prueba", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
+    const subjectSignature = await initializeSubject(program, wallet1, subjectId, "Asignatura con deposito liquidado", 1, 1, { second: {} }, subjectCode, "QmPRKpTKznUt6sU8yjYBwWaECVBVBF8nMiL77W2hkhVsQs")
     await connection.confirmTransaction(subjectSignature.toString())
 
-    const newSubjectAccount = await fetchSubjectAccount(program, subjectId)
-
-    // INITIALIZATING STUDENT
-
-    await initializeStudent(program, wallet3, [subjectCode]);
-    getExtraFunds(connection, 50, wallet3)
-    var idExpected = 1;
-    var professorProposalIdExpected = 1;
+    const creatorWallet = await createWallet(connection, 10);
+    await initializeStudent(program, creatorWallet, [subjectCode]);
 
+    const proposalIdAccount = await fetchProposalIdAccount(program, false, subjectCode)
+    const professorProposalIdAccount = await fetchProposalIdAccount(program, true, subjectCode)
+    const proposalId = proposalIdAccount.smallerIdAvailable
+    const professorProposalId = professorProposalIdAccount.smallerIdAvailable
 
-    // CREATING NEW PROPOSAL (just exactly the previous tests)
+    const signature = await initializeProposalByStudent(program, creatorWallet, proposalId, "Propuesta liquidada", "Resumen", subjectId, professorProposalId, subjectCode)
+    await connection.confirmTransaction(signature.toString())
 
-    const account = await fetchProposalIdAccount(program, false, newSubjectAccount.code)
-    idExpected = account.smallerIdAvailable
+    for (var i = 0; i < 20; i++) {
+      const voterWallet = await createWallet(connection, 10);
+      await initializeStudent(program, voterWallet, [subjectCode])
+      const vote_signature = await voteProposalByStudent(program, voterWallet, proposalId, subjectId, professorProposalId, false, subjectCode)
+      await connection.confirmTransaction(vote_signature.toString())
+    }
 
+    // The deposit is settled before the creator appeals the rejection
+    await settleProposalDeposit(program, wallet1, proposalId, subjectId, subjectCode, creatorWallet.publicKey)
+    await fileAppeal(program, creatorWallet, proposalId, subjectId, subjectCode, DEFAULT_PROPOSAL_CONTENT_REFERENCE)
 
     try {
-      const account = await fetchProposalIdAccount(program, true, newSubjectAccount.code)
-      professorProposalIdExpected = account.smallerIdAvailable
+      await resolveAppeal(program, wallet1, proposalId, subjectId, subjectCode, true, DEFAULT_PROPOSAL_CONTENT_REFERENCE, Math.floor(Date.now() / 1000) + 86400)
+      assert.fail("Reopening the votation of a proposal without deposit was expected to fail")
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "ProposalDepositIsSettled");
     }
 
-    const signature = await initializeProposalByStudent(program, wallet3, idExpected, "Propuesta Correcta", "Desarollo o contenido de la propuesta de prueba correcta", newSubjectAccount.id, professorProposalIdExpected, newSubjectAccount.code)
-    await connection.confirmTransaction(signature.toString())
-
-    // VOTING TRUE BY DIFFERENT STUDENTS 
-
-    // Fetching the proposal account before voting
-    const proposalAccountBeforeVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
-
-    const number_of_votes_expected = 20 // Must be set to 20 on SC (MAXIMUM_PARTICIPATION const)
-
-    // We create students to vote in favor until the number of votes expected is reached and the votation is forced to finalize
-    let vote_signature: String;
-    for (var i = 0; i < number_of_votes_expected; i++) {
-      alternativeWallet = await createWallet(connection, 10);
-      await initializeStudent(program, alternativeWallet, [subjectCode])
-      vote_signature = await voteProposalByStudent(program, alternativeWallet, Number(proposalAccountBeforeVoting.id), Number(proposalAccountBeforeVoting.subjectId), Number(proposalAccountBeforeVoting.associatedProfessorProposalId), false, newSubjectAccount.code)
-      await connection.confirmTransaction(vote_signature.toString())
-    }
+    // The rejection can still be confirmed
+    await resolveAppeal(program, wallet1, proposalId, subjectId, subjectCode, false, DEFAULT_PROPOSAL_CONTENT_REFERENCE, 0)
 
-    // Fetching the proposal account after voting
-    const proposalAccountAfterVoting = await fetchProposalAccount(program, idExpected, newSubjectAccount.code);
-    expect(new anchor.BN(proposalAccountAfterVoting.againstVotes + proposalAccountAfterVoting.supportingVotes).eq(new anchor.BN(number_of_votes_expected))).to.be.true;
+    const proposalAccount = await fetchProposalAccount(program, proposalId, subjectCode);
+    expect(proposalAccount.state).to.deep.equal({ rejected: {} });
 
-    // Getting the Program Return
-    const lastVotingTransaction = await connection.getTransaction(vote_signature.toString(), { commitment: "confirmed" });
-    const [key, data, buffer] = getReturnLog(lastVotingTransaction);
-    const program_return = new Borsh.BinaryReader(buffer).readString();
+  });
 
-    // After completing the voting process, the votation must continue being 'Rejecting' since all students voted 'false' (against the proposal)
-    expect(program_return).to.deep.equal("Rejected")
+  it("Subject coordinator must teach the subject", async () => {
 
-    //Trying to delete the rejectedProposal by a HighRank
-    await deleteRejectedProposalByHighRank(program, wallet1, proposalAccountAfterVoting.id, proposalAccountAfterVoting.subjectId, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code);
+    await initializeHighRank(program, wallet1)
+    getExtraFunds(connection, 50, wallet1) //wallet1 is allowed by a HighRank
 
+    const subjectId = 1
+    const professorWallet = await createWallet(connection, 10);
+    await initializeProfessor(program, professorWallet, [43143]);
+    const professorAccount = await fetchProfessorAccount(program, professorWallet.publicKey);
 
-    // Trying to fetch the proposal account and the professor proposal account --> they both must not exist 
     try {
-      await fetchProposalAccount(program, proposalAccountAfterVoting.id, newSubjectAccount.code);
-      assert.fail("Fetching the proposalAccount does not failed and the fail was expected")
+      await updateSubjectMetadata(program, wallet1, subjectId, 60, { second: {} }, "en", { hybrid: {} }, professorAccount.id, 11, professorWallet.publicKey)
     } catch (err) {
       assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
+      assert.include(err.toString(), "UserDoesNotBelongToTheSubject");
+      return;
     }
 
-    try {
-      await fetchProfessorProposalAccount(program, proposalAccountAfterVoting.associatedProfessorProposalId, newSubjectAccount.code);
-      assert.fail("Fetching the proposalAccount does not failed and the fail was expected")
-    } catch (err) {
-      assert.instanceOf(err, Error);
-      assert.include(err.toString(), "Account does not exist");
-    }
+    assert.fail("Expected an error to be thrown");
 
   });
 
-
 });
-
-
-
-
-
-